@@ -0,0 +1,69 @@
+use std::sync::Arc;
+use tokio::sync::{Mutex as AsyncMutex, OwnedMutexGuard};
+
+// Accounts share this many locks (by user id modulo the count), so memory stays fixed however many users exist
+const LOCK_STRIPES: usize = 256;
+
+// Per-account async locks so balance reads and ledger writes for the same user never interleave
+#[derive(Clone)]
+pub struct AccountLocks {
+    stripes: Arc<Vec<Arc<AsyncMutex<()>>>>,
+}
+
+pub struct AccountLockGuard {
+    _guards: Vec<OwnedMutexGuard<()>>,
+}
+
+impl AccountLocks {
+    pub fn new() -> Self {
+        Self {
+            stripes: Arc::new((0..LOCK_STRIPES).map(|_| Arc::new(AsyncMutex::new(()))).collect()),
+        }
+    }
+
+    pub async fn lock(&self, user_ids: &[u32]) -> AccountLockGuard {
+        // Always acquire in ascending stripe order so two transfers A->B and B->A cannot deadlock; two
+        // accounts on the same stripe take it once
+        let mut stripes: Vec<usize> = user_ids.iter().map(|user_id| *user_id as usize % LOCK_STRIPES).collect();
+        stripes.sort_unstable();
+        stripes.dedup();
+
+        let mut guards = Vec::with_capacity(stripes.len());
+        for stripe in stripes {
+            guards.push(self.stripes[stripe].clone().lock_owned().await);
+        }
+
+        AccountLockGuard { _guards: guards }
+    }
+}
+
+impl Default for AccountLocks {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn accounts_sharing_a_stripe_lock_it_once() {
+        let locks = AccountLocks::new();
+        let guard = tokio::time::timeout(Duration::from_secs(1), locks.lock(&[1, 1 + LOCK_STRIPES as u32, 1]))
+            .await
+            .expect("locking two accounts on one stripe must not deadlock");
+        drop(guard);
+    }
+
+    #[tokio::test]
+    async fn a_held_account_blocks_until_released() {
+        let locks = AccountLocks::new();
+        let guard = locks.lock(&[7, 3]).await;
+        assert!(tokio::time::timeout(Duration::from_millis(50), locks.lock(&[3])).await.is_err());
+
+        drop(guard);
+        assert!(tokio::time::timeout(Duration::from_secs(1), locks.lock(&[3, 7])).await.is_ok());
+    }
+}
\ No newline at end of file
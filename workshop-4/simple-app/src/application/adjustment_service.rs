@@ -0,0 +1,224 @@
+use std::sync::Arc;
+use crate::domain::{
+    BalanceAdjustment, AdjustmentStatus, AdjustmentAction, AdjustmentRepository, CreateAdjustmentRequest,
+    ReviewAdjustmentRequest, AdjustmentResponse, AdjustmentListResponse, EventType, UserRepository, UnitOfWorkFactory, Clock,
+};
+use super::AccountLocks;
+
+// Maker-checker for balance corrections: one person proposes, a different one approves or rejects,
+// and only an approval posts an adjust ledger entry
+#[derive(Clone)]
+pub struct AdjustmentService {
+    adjustment_repository: Arc<dyn AdjustmentRepository + Send + Sync>,
+    user_repository: Arc<dyn UserRepository + Send + Sync>,
+    unit_of_work_factory: Arc<dyn UnitOfWorkFactory + Send + Sync>,
+    account_locks: AccountLocks,
+    clock: Arc<dyn Clock + Send + Sync>,
+    approvers: Vec<String>,
+}
+
+impl AdjustmentService {
+    pub fn new(
+        adjustment_repository: Arc<dyn AdjustmentRepository + Send + Sync>,
+        user_repository: Arc<dyn UserRepository + Send + Sync>,
+        unit_of_work_factory: Arc<dyn UnitOfWorkFactory + Send + Sync>,
+        account_locks: AccountLocks,
+        clock: Arc<dyn Clock + Send + Sync>,
+    ) -> Self {
+        Self {
+            adjustment_repository,
+            user_repository,
+            unit_of_work_factory,
+            account_locks,
+            clock,
+            approvers: Vec::new(),
+        }
+    }
+
+    // Who may approve or reject adjustments, by email; until it is set nobody can
+    pub fn with_approvers(mut self, approvers: Vec<String>) -> Self {
+        self.approvers = approvers;
+        self
+    }
+
+    // `requested_by` and `reviewed_by` below are the emails of the signed-in staff members
+    pub async fn request_adjustment(&self, request: CreateAdjustmentRequest, requested_by: &str) -> Result<AdjustmentResponse, String> {
+        // Validate request
+        request.validate()?;
+
+        // Check if user exists
+        let _user = self.user_repository.get_user_by_id(request.user_id).await?
+            .ok_or("User not found".to_string())?;
+
+        let now = self.clock.now();
+        let uow = self.unit_of_work_factory.begin().await?;
+
+        let adjustment = uow.adjustments().create_adjustment(request, requested_by, now).await?;
+        let audit_entry = uow.adjustments().add_audit_entry(
+            adjustment.id,
+            AdjustmentAction::Requested,
+            &adjustment.requested_by,
+            Some(adjustment.justification.clone()),
+            now,
+        ).await?;
+
+        uow.commit().await?;
+
+        Ok(AdjustmentResponse {
+            adjustment,
+            audit: vec![audit_entry],
+        })
+    }
+
+    pub async fn get_adjustment(&self, id: u32) -> Result<AdjustmentResponse, String> {
+        let adjustment = self.adjustment_repository.get_adjustment(id).await?
+            .ok_or("Adjustment not found".to_string())?;
+        let audit = self.adjustment_repository.get_audit_entries(id).await?;
+
+        Ok(AdjustmentResponse { adjustment, audit })
+    }
+
+    pub async fn list_adjustments(&self, status: Option<AdjustmentStatus>, user_id: Option<u32>) -> Result<AdjustmentListResponse, String> {
+        let data = self.adjustment_repository.list_adjustments(status, user_id).await?;
+
+        Ok(AdjustmentListResponse { data })
+    }
+
+    pub async fn approve_adjustment(&self, id: u32, reviewed_by: &str, request: ReviewAdjustmentRequest) -> Result<AdjustmentResponse, String> {
+        request.validate()?;
+        let adjustment = self.adjustment_repository.get_adjustment(id).await?
+            .ok_or("Adjustment not found".to_string())?;
+
+        let _account_lock = self.account_locks.lock(&[adjustment.user_id]).await;
+        let uow = self.unit_of_work_factory.begin().await?;
+
+        // Re-read inside the transaction so two reviewers cannot both act on it
+        let mut adjustment = uow.adjustments().get_adjustment(id).await?
+            .ok_or("Adjustment not found".to_string())?;
+        self.check_reviewable(&adjustment, reviewed_by)?;
+
+        let point_ledger_repository = uow.point_ledger();
+        let balance = point_ledger_repository.get_current_balance(adjustment.user_id).await?;
+        let amount = adjustment.amount.unsigned_abs();
+        let balance_after = if adjustment.amount < 0 {
+            // A debit cannot eat into points that are on hold
+            if point_ledger_repository.get_available_balance(adjustment.user_id).await? < amount {
+                return Err("Insufficient points".to_string());
+            }
+            balance - amount
+        } else {
+            balance.checked_add(amount)
+                .ok_or("Balance would exceed the maximum number of points".to_string())?
+        };
+
+        let entry = point_ledger_repository.create_ledger_entry(
+            adjustment.user_id,
+            adjustment.amount,
+            balance_after,
+            EventType::Adjust,
+            None,
+            Some(adjustment.justification.clone()),
+            Some(serde_json::json!({
+                "adjustment_id": adjustment.id,
+                "requested_by": adjustment.requested_by,
+                "approved_by": reviewed_by
+            }).to_string()),
+        ).await?;
+
+        let now = self.clock.now();
+        adjustment.status = AdjustmentStatus::Approved;
+        adjustment.reviewed_by = Some(reviewed_by.to_string());
+        adjustment.review_note = request.note.clone();
+        adjustment.ledger_entry_id = Some(entry.id);
+        adjustment.reviewed_at = Some(now);
+        uow.adjustments().update_adjustment(&adjustment).await?;
+        uow.adjustments().add_audit_entry(adjustment.id, AdjustmentAction::Approved, reviewed_by, request.note, now).await?;
+        let audit = uow.adjustments().get_audit_entries(adjustment.id).await?;
+
+        uow.commit().await?;
+
+        Ok(AdjustmentResponse { adjustment, audit })
+    }
+
+    pub async fn reject_adjustment(&self, id: u32, reviewed_by: &str, request: ReviewAdjustmentRequest) -> Result<AdjustmentResponse, String> {
+        request.validate()?;
+        let uow = self.unit_of_work_factory.begin().await?;
+
+        let mut adjustment = uow.adjustments().get_adjustment(id).await?
+            .ok_or("Adjustment not found".to_string())?;
+        self.check_reviewable(&adjustment, reviewed_by)?;
+
+        let now = self.clock.now();
+        adjustment.status = AdjustmentStatus::Rejected;
+        adjustment.reviewed_by = Some(reviewed_by.to_string());
+        adjustment.review_note = request.note.clone();
+        adjustment.reviewed_at = Some(now);
+        uow.adjustments().update_adjustment(&adjustment).await?;
+        uow.adjustments().add_audit_entry(adjustment.id, AdjustmentAction::Rejected, reviewed_by, request.note, now).await?;
+        let audit = uow.adjustments().get_audit_entries(adjustment.id).await?;
+
+        uow.commit().await?;
+
+        Ok(AdjustmentResponse { adjustment, audit })
+    }
+
+    fn check_reviewable(&self, adjustment: &BalanceAdjustment, reviewer: &str) -> Result<(), String> {
+        let reviewer = reviewer.trim();
+
+        if adjustment.status != AdjustmentStatus::Pending {
+            return Err(format!("Only pending adjustments can be reviewed (current status: {})", adjustment.status));
+        }
+
+        if adjustment.requested_by.trim().eq_ignore_ascii_case(reviewer) {
+            return Err("The requester cannot review their own adjustment".to_string());
+        }
+
+        if self.approvers.is_empty() {
+            return Err("Nobody is allowed to review adjustments until approvers are configured".to_string());
+        }
+        if !self.approvers.iter().any(|approver| approver.eq_ignore_ascii_case(reviewer)) {
+            return Err(format!("{} is not allowed to review adjustments", reviewer));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use crate::domain::{CreateAdjustmentRequest, ReviewAdjustmentRequest, AdjustmentStatus, SystemClock};
+    use crate::infrastructure::{SqliteAdjustmentRepository, SqliteUserRepository, SqliteUnitOfWorkFactory};
+    use crate::infrastructure::test_support::test_pool;
+    use crate::application::AccountLocks;
+    use super::AdjustmentService;
+
+    #[tokio::test]
+    async fn adjustments_are_only_reviewed_by_a_configured_approver_other_than_the_requester() {
+        let pool = test_pool().await;
+        let service = AdjustmentService::new(
+            Arc::new(SqliteAdjustmentRepository::new(pool.clone())),
+            Arc::new(SqliteUserRepository::new(pool.clone())),
+            Arc::new(SqliteUnitOfWorkFactory::new(pool.clone())),
+            AccountLocks::new(),
+            Arc::new(SystemClock),
+        );
+        let request = CreateAdjustmentRequest { user_id: 3, amount: 50, justification: "Missing receipt".to_string() };
+        let review = || ReviewAdjustmentRequest { note: None };
+
+        let id = service.request_adjustment(request.clone(), "maker@example.com").await.unwrap().adjustment.id;
+        let err = service.approve_adjustment(id, "checker@example.com", review()).await.unwrap_err();
+        assert!(err.contains("until approvers are configured"), "{}", err);
+
+        let service = service.with_approvers(vec!["maker@example.com".to_string(), "checker@example.com".to_string()]);
+        let err = service.approve_adjustment(id, "maker@example.com", review()).await.unwrap_err();
+        assert!(err.contains("cannot review their own"), "{}", err);
+        let err = service.approve_adjustment(id, "someone@example.com", review()).await.unwrap_err();
+        assert!(err.contains("not allowed to review"), "{}", err);
+
+        let approved = service.approve_adjustment(id, "checker@example.com", review()).await.unwrap().adjustment;
+        assert_eq!(approved.status, AdjustmentStatus::Approved);
+        assert_eq!(approved.requested_by, "maker@example.com");
+        assert_eq!(approved.reviewed_by.as_deref(), Some("checker@example.com"));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,317 @@
+use std::sync::Arc;
+use argon2::Argon2;
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use chrono::{DateTime, Duration, Utc};
+use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use sha2::{Digest, Sha256};
+use crate::domain::{
+    User, CreateUserRequest, RegisterRequest, LoginRequest, OtpRequest, OtpRequestResponse, RefreshRequest, TokenResponse,
+    AccessTokenClaims, RefreshToken, UserRepository, AuthRepository, OtpSender, Clock,
+};
+
+// Wrong codes allowed before a login code stops working
+const MAX_OTP_ATTEMPTS: u32 = 5;
+
+// Login codes a user can be sent per hour; each new code comes with fresh attempts, so this caps the guesses too
+const MAX_OTPS_PER_HOUR: u32 = 5;
+
+// Every credential failure gets this message so a caller cannot tell which part was wrong
+const INVALID_CREDENTIALS: &str = "Invalid credentials";
+
+// Issues short-lived signed access tokens (HS256 JWTs) and single-use refresh tokens. A refresh token that is
+// presented a second time means it leaked, so every session of its user is signed out.
+#[derive(Clone)]
+pub struct AuthService {
+    user_repository: Arc<dyn UserRepository + Send + Sync>,
+    auth_repository: Arc<dyn AuthRepository + Send + Sync>,
+    otp_sender: Arc<dyn OtpSender + Send + Sync>,
+    clock: Arc<dyn Clock + Send + Sync>,
+    encoding_key: EncodingKey,
+    decoding_key: DecodingKey,
+    access_token_ttl: Duration,
+    refresh_token_ttl: Duration,
+    otp_ttl: Duration,
+}
+
+impl AuthService {
+    pub fn new(
+        user_repository: Arc<dyn UserRepository + Send + Sync>,
+        auth_repository: Arc<dyn AuthRepository + Send + Sync>,
+        otp_sender: Arc<dyn OtpSender + Send + Sync>,
+        clock: Arc<dyn Clock + Send + Sync>,
+        jwt_secret: &[u8],
+    ) -> Self {
+        Self {
+            user_repository,
+            auth_repository,
+            otp_sender,
+            clock,
+            encoding_key: EncodingKey::from_secret(jwt_secret),
+            decoding_key: DecodingKey::from_secret(jwt_secret),
+            access_token_ttl: Duration::minutes(15),
+            refresh_token_ttl: Duration::days(30),
+            otp_ttl: Duration::minutes(5),
+        }
+    }
+
+    pub fn with_access_token_ttl(mut self, ttl: Duration) -> Self {
+        self.access_token_ttl = ttl;
+        self
+    }
+
+    pub fn with_refresh_token_ttl(mut self, ttl: Duration) -> Self {
+        self.refresh_token_ttl = ttl;
+        self
+    }
+
+    pub async fn register(&self, request: RegisterRequest) -> Result<TokenResponse, String> {
+        request.validate()?;
+
+        let password_hash = hash_password(request.password).await?;
+        let user = self.user_repository.create_user(CreateUserRequest {
+            first_name: request.first_name,
+            last_name: request.last_name,
+            phone: request.phone.trim().to_string(),
+            email: request.email.trim().to_string(),
+        }).await?;
+        self.auth_repository.set_password_hash(user.id, &password_hash, self.clock.now()).await?;
+
+        self.issue_tokens(user).await
+    }
+
+    pub async fn login(&self, request: LoginRequest) -> Result<TokenResponse, String> {
+        request.validate()?;
+
+        let user = self.find_user(&request.identifier).await?
+            .ok_or(INVALID_CREDENTIALS.to_string())?;
+
+        match (request.password, request.otp) {
+            (Some(password), _) => {
+                let password_hash = self.auth_repository.get_password_hash(user.id).await?
+                    .ok_or(INVALID_CREDENTIALS.to_string())?;
+                if !verify_password(password, password_hash).await? {
+                    return Err(INVALID_CREDENTIALS.to_string());
+                }
+            }
+            (None, Some(code)) => self.check_login_otp(user.id, code.trim()).await?,
+            (None, None) => return Err(INVALID_CREDENTIALS.to_string()),
+        }
+
+        self.issue_tokens(user).await
+    }
+
+    // Sends a one-time login code to the email address or phone number. Unknown identifiers, and users who
+    // have had MAX_OTPS_PER_HOUR codes already, get the same answer but no code, so it never tells whether
+    // an account exists
+    pub async fn request_login_otp(&self, request: OtpRequest) -> Result<OtpRequestResponse, String> {
+        let identifier = request.identifier.trim();
+        if identifier.is_empty() {
+            return Err("identifier is required".to_string());
+        }
+
+        let now = self.clock.now();
+        if let Some(user) = self.find_user(identifier).await?
+            && self.auth_repository.count_login_otps_since(user.id, now - Duration::hours(1)).await? < MAX_OTPS_PER_HOUR
+        {
+            let code = format!("{:06}", OsRng.next_u32() % 1_000_000);
+            self.auth_repository.create_login_otp(user.id, &sha256_hex(&code), now + self.otp_ttl, now).await?;
+            self.otp_sender.send(identifier, &code)?;
+        }
+
+        Ok(OtpRequestResponse {
+            expires_in_seconds: self.otp_ttl.num_seconds(),
+        })
+    }
+
+    // Trades a refresh token for a new access token and a new refresh token; the old one stops working
+    pub async fn refresh(&self, request: RefreshRequest) -> Result<TokenResponse, String> {
+        let now = self.clock.now();
+        let token = self.auth_repository.get_refresh_token(&sha256_hex(&request.refresh_token)).await?
+            .ok_or("Invalid refresh token".to_string())?;
+
+        if token.revoked_at.is_some() {
+            if token.replaced_by.is_some() {
+                self.auth_repository.revoke_user_refresh_tokens(token.user_id, now).await?;
+            }
+            return Err("Invalid refresh token".to_string());
+        }
+        if token.expires_at <= now {
+            return Err("Invalid refresh token".to_string());
+        }
+
+        let user = self.user_repository.get_user_by_id(token.user_id).await?
+            .ok_or("Invalid refresh token".to_string())?;
+        let (refresh_token, replacement) = self.create_refresh_token(user.id).await?;
+
+        // Lost a race with another refresh of the same token: treat it as reuse
+        if !self.auth_repository.revoke_refresh_token(token.id, Some(replacement.id), now).await? {
+            self.auth_repository.revoke_user_refresh_tokens(user.id, now).await?;
+            return Err("Invalid refresh token".to_string());
+        }
+
+        self.token_response(user, refresh_token, replacement.expires_at)
+    }
+
+    // Revokes the refresh token; access tokens already issued run until they expire
+    pub async fn logout(&self, request: RefreshRequest) -> Result<(), String> {
+        if let Some(token) = self.auth_repository.get_refresh_token(&sha256_hex(&request.refresh_token)).await? {
+            self.auth_repository.revoke_refresh_token(token.id, None, self.clock.now()).await?;
+        }
+
+        Ok(())
+    }
+
+    // Checks the signature and expiry of an access token and returns the user it was issued to
+    pub async fn authenticate(&self, access_token: &str) -> Result<User, String> {
+        let mut validation = Validation::new(Algorithm::HS256);
+        validation.leeway = 0;
+        let claims = jsonwebtoken::decode::<AccessTokenClaims>(access_token, &self.decoding_key, &validation)
+            .map_err(|e| format!("Invalid access token: {}", e))?
+            .claims;
+        if claims.exp <= self.clock.now().timestamp() {
+            return Err("Invalid access token: expired".to_string());
+        }
+
+        let user_id = claims.sub.parse::<u32>()
+            .map_err(|_| "Invalid access token: bad subject".to_string())?;
+        self.user_repository.get_user_by_id(user_id).await?
+            .ok_or("Invalid access token: user no longer exists".to_string())
+    }
+
+    async fn find_user(&self, identifier: &str) -> Result<Option<User>, String> {
+        let identifier = identifier.trim();
+        if identifier.contains('@') {
+            self.user_repository.get_user_by_email(identifier).await
+        } else {
+            self.user_repository.get_user_by_phone(identifier).await
+        }
+    }
+
+    async fn check_login_otp(&self, user_id: u32, code: &str) -> Result<(), String> {
+        let now = self.clock.now();
+        let otp = self.auth_repository.get_active_login_otp(user_id, now).await?
+            .filter(|otp| otp.attempts < MAX_OTP_ATTEMPTS)
+            .ok_or(INVALID_CREDENTIALS.to_string())?;
+
+        if otp.code_hash != sha256_hex(code) {
+            self.auth_repository.record_login_otp_attempt(otp.id).await?;
+            return Err(INVALID_CREDENTIALS.to_string());
+        }
+        if !self.auth_repository.consume_login_otp(otp.id, now).await? {
+            return Err(INVALID_CREDENTIALS.to_string());
+        }
+
+        Ok(())
+    }
+
+    async fn issue_tokens(&self, user: User) -> Result<TokenResponse, String> {
+        let (refresh_token, stored) = self.create_refresh_token(user.id).await?;
+
+        self.token_response(user, refresh_token, stored.expires_at)
+    }
+
+    async fn create_refresh_token(&self, user_id: u32) -> Result<(String, RefreshToken), String> {
+        let now = self.clock.now();
+        let refresh_token = random_token();
+        let stored = self.auth_repository
+            .create_refresh_token(user_id, &sha256_hex(&refresh_token), now + self.refresh_token_ttl, now)
+            .await?;
+
+        Ok((refresh_token, stored))
+    }
+
+    fn token_response(&self, user: User, refresh_token: String, refresh_token_expires_at: DateTime<Utc>) -> Result<TokenResponse, String> {
+        let now = self.clock.now();
+        let claims = AccessTokenClaims {
+            sub: user.id.to_string(),
+            iat: now.timestamp(),
+            exp: (now + self.access_token_ttl).timestamp(),
+        };
+        let access_token = jsonwebtoken::encode(&Header::new(Algorithm::HS256), &claims, &self.encoding_key)
+            .map_err(|e| format!("Failed to sign access token: {}", e))?;
+
+        Ok(TokenResponse {
+            access_token,
+            token_type: "Bearer".to_string(),
+            expires_in: self.access_token_ttl.num_seconds(),
+            refresh_token,
+            refresh_token_expires_at,
+            user,
+        })
+    }
+}
+
+// 256 random bits, hex encoded
+pub fn random_token() -> String {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+fn sha256_hex(value: &str) -> String {
+    hex::encode(Sha256::digest(value.as_bytes()))
+}
+
+// Argon2 is deliberately slow, so it runs off the async workers
+async fn hash_password(password: String) -> Result<String, String> {
+    tokio::task::spawn_blocking(move || {
+        let salt = SaltString::generate(&mut OsRng);
+        Argon2::default()
+            .hash_password(password.as_bytes(), &salt)
+            .map(|hash| hash.to_string())
+            .map_err(|e| format!("Failed to hash password: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Failed to hash password: {}", e))?
+}
+
+async fn verify_password(password: String, password_hash: String) -> Result<bool, String> {
+    tokio::task::spawn_blocking(move || {
+        let parsed = PasswordHash::new(&password_hash)
+            .map_err(|e| format!("Invalid stored password hash: {}", e))?;
+        Ok(Argon2::default().verify_password(password.as_bytes(), &parsed).is_ok())
+    })
+    .await
+    .map_err(|e| format!("Failed to verify password: {}", e))?
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use chrono::{Duration, Utc};
+    use crate::domain::OtpRequest;
+    use crate::infrastructure::{SqliteUserRepository, SqliteAuthRepository, ConsoleOtpSender};
+    use crate::infrastructure::test_support::{test_pool, FakeClock};
+    use super::AuthService;
+
+    #[tokio::test]
+    async fn login_codes_are_rate_limited_per_user() {
+        let pool = test_pool().await;
+        let clock = Arc::new(FakeClock::new(Utc::now()));
+        let auth_service = AuthService::new(
+            Arc::new(SqliteUserRepository::new(pool.clone())),
+            Arc::new(SqliteAuthRepository::new(pool.clone())),
+            Arc::new(ConsoleOtpSender),
+            clock.clone(),
+            b"test-secret",
+        );
+        let request = || OtpRequest { identifier: "+66812345678".to_string() };
+
+        let codes_sent = || async {
+            sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM login_otps").fetch_one(&pool).await.unwrap()
+        };
+
+        // Past the limit the answer does not change, so it cannot be told apart from an unknown identifier
+        let unknown = auth_service.request_login_otp(OtpRequest { identifier: "nobody@example.com".to_string() }).await.unwrap();
+        for _ in 0..7 {
+            let response = auth_service.request_login_otp(request()).await.unwrap();
+            assert_eq!(response.expires_in_seconds, unknown.expires_in_seconds);
+        }
+        assert_eq!(codes_sent().await, 5);
+
+        clock.advance(Duration::minutes(61));
+        auth_service.request_login_otp(request()).await.unwrap();
+        assert_eq!(codes_sent().await, 6);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,237 @@
+use std::sync::Arc;
+use uuid::Uuid;
+use crate::domain::{
+    TransferBatch, BatchItem, BatchMode, BatchStatus, BatchItemStatus, BatchRepository, CreateBatchRequest,
+    TransferStatus, IdempotencyRecord, Clock,
+};
+use super::TransferService;
+use super::transfer_worker::is_transient_error;
+
+// Error code reported on a batch item, matching what POST /transfers would have answered
+pub fn transfer_error_code(error: &str) -> &'static str {
+    if error.contains("different request") {
+        "IDEMPOTENCY_KEY_REUSED"
+    } else if error.contains("not found") {
+        "USER_NOT_FOUND"
+    } else if error.contains("Insufficient points") {
+        "INSUFFICIENT_POINTS"
+    } else if error.contains("Transfer limit exceeded") {
+        "LIMIT_EXCEEDED"
+    } else if error.contains("Cannot transfer to the same user") {
+        "INVALID_TRANSFER"
+    } else {
+        "VALIDATION_ERROR"
+    }
+}
+
+// Idempotency-Key of one item, so a batch resumed after a restart never executes an item twice
+fn item_idempotency_key(batch_id: &str, index: u32) -> String {
+    format!("batch-{}-item-{}", batch_id, index)
+}
+
+#[derive(Clone)]
+pub struct BatchService {
+    batch_repository: Arc<dyn BatchRepository + Send + Sync>,
+    transfer_service: TransferService,
+    clock: Arc<dyn Clock + Send + Sync>,
+}
+
+impl BatchService {
+    pub fn new(
+        batch_repository: Arc<dyn BatchRepository + Send + Sync>,
+        transfer_service: TransferService,
+        clock: Arc<dyn Clock + Send + Sync>,
+    ) -> Self {
+        Self {
+            batch_repository,
+            transfer_service,
+            clock,
+        }
+    }
+
+    // Stores the batch for the BatchWorker and returns it; the flag is true when the key was seen before
+    // and the original batch is returned instead
+    pub async fn submit_batch(&self, request: CreateBatchRequest, idempotency_key: String) -> Result<(TransferBatch, bool), String> {
+        // Validate request
+        request.validate()?;
+        IdempotencyRecord::validate_key(&idempotency_key)?;
+        let fingerprint = serde_json::to_string(&request)
+            .map_err(|e| format!("Failed to fingerprint request: {}", e))?;
+
+        if let Some(batch) = self.find_replayed_batch(&idempotency_key, &fingerprint).await? {
+            return Ok((batch, true));
+        }
+
+        let now = self.clock.now();
+        let mut batch = TransferBatch {
+            batch_id: Uuid::new_v4().to_string(),
+            idempotency_key,
+            request_fingerprint: fingerprint,
+            mode: request.mode,
+            status: BatchStatus::Pending,
+            total_items: request.items.len() as u32,
+            succeeded_items: 0,
+            failed_items: 0,
+            created_at: now,
+            updated_at: now,
+            completed_at: None,
+            items: Vec::with_capacity(request.items.len()),
+        };
+
+        // Items that fail validation are reported straight away
+        for (index, item_request) in request.items.into_iter().enumerate() {
+            let validation = item_request.validate();
+            let mut item = BatchItem::new(index as u32, item_request);
+            if let Err(e) = validation {
+                item.fail(transfer_error_code(&e), e);
+            }
+            batch.items.push(item);
+        }
+
+        let has_invalid_items = batch.items.iter().any(|item| item.status == BatchItemStatus::Failed);
+        if batch.mode == BatchMode::AllOrNothing && has_invalid_items {
+            skip_pending_items(&mut batch.items, "Not executed: another item in the batch is invalid");
+            batch.status = BatchStatus::Failed;
+            batch.completed_at = Some(now);
+        } else if batch.items.iter().all(|item| item.status != BatchItemStatus::Pending) {
+            batch.status = BatchStatus::Completed;
+            batch.completed_at = Some(now);
+        }
+        batch.refresh_counts();
+
+        if let Err(e) = self.batch_repository.create_batch(&batch).await {
+            // Lost a race with a concurrent request using the same key
+            if e.contains("UNIQUE")
+                && let Some(existing) = self.find_replayed_batch(&batch.idempotency_key, &batch.request_fingerprint).await?
+            {
+                return Ok((existing, true));
+            }
+            return Err(e);
+        }
+
+        Ok((batch, false))
+    }
+
+    pub async fn get_batch(&self, batch_id: &str) -> Result<TransferBatch, String> {
+        self.batch_repository.get_batch(batch_id).await?
+            .ok_or("Batch not found".to_string())
+    }
+
+    pub async fn list_unfinished_batches(&self, limit: u32) -> Result<Vec<TransferBatch>, String> {
+        self.batch_repository.get_unfinished_batches(limit).await
+    }
+
+    // Executes the batch's pending items. Transient database errors are returned with the batch left
+    // processing; items already finished keep their results and the next attempt picks up the rest.
+    pub async fn process_batch(&self, batch_id: &str) -> Result<TransferBatch, String> {
+        let mut batch = self.get_batch(batch_id).await?;
+        if !matches!(batch.status, BatchStatus::Pending | BatchStatus::Processing) {
+            return Ok(batch);
+        }
+
+        batch.status = BatchStatus::Processing;
+        batch.updated_at = self.clock.now();
+        self.batch_repository.update_batch(&batch).await?;
+
+        match batch.mode {
+            BatchMode::AllOrNothing => self.process_all_or_nothing(&mut batch).await?,
+            BatchMode::BestEffort => self.process_best_effort(&mut batch).await?,
+        }
+
+        let now = self.clock.now();
+        batch.refresh_counts();
+        batch.updated_at = now;
+        batch.completed_at = Some(now);
+        self.batch_repository.update_batch(&batch).await?;
+
+        Ok(batch)
+    }
+
+    async fn process_all_or_nothing(&self, batch: &mut TransferBatch) -> Result<(), String> {
+        let requests = batch.items.iter()
+            .filter(|item| item.status == BatchItemStatus::Pending)
+            .map(|item| (item.request(), item_idempotency_key(&batch.batch_id, item.index)))
+            .collect();
+
+        match self.transfer_service.create_transfers_atomically(requests).await? {
+            Ok(transfers) => {
+                let pending = batch.items.iter_mut().filter(|item| item.status == BatchItemStatus::Pending);
+                for (item, transfer) in pending.zip(transfers) {
+                    item.status = BatchItemStatus::Succeeded;
+                    item.transfer_idem_key = Some(transfer.idem_key);
+                    item.transfer_status = Some(transfer.status);
+                }
+                batch.status = BatchStatus::Completed;
+            }
+            Err((position, e)) => {
+                if let Some(item) = batch.items.iter_mut().filter(|item| item.status == BatchItemStatus::Pending).nth(position) {
+                    item.fail(transfer_error_code(&e), e);
+                }
+                skip_pending_items(&mut batch.items, "Not executed: another item in the batch failed");
+                batch.status = BatchStatus::Failed;
+            }
+        }
+
+        for item in &batch.items {
+            self.batch_repository.update_batch_item(&batch.batch_id, item).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn process_best_effort(&self, batch: &mut TransferBatch) -> Result<(), String> {
+        for index in 0..batch.items.len() {
+            if batch.items[index].status != BatchItemStatus::Pending {
+                continue;
+            }
+
+            let item = &mut batch.items[index];
+            let key = item_idempotency_key(&batch.batch_id, item.index);
+            match self.transfer_service.create_transfer(item.request(), Some(key)).await {
+                Ok(response) => {
+                    let transfer = response.transfer;
+                    item.transfer_idem_key = Some(transfer.idem_key);
+                    item.transfer_status = Some(transfer.status);
+                    match transfer.fail_reason {
+                        Some(fail_reason) if transfer.status == TransferStatus::Failed => {
+                            item.fail(transfer_error_code(&fail_reason), fail_reason);
+                        }
+                        _ => item.status = BatchItemStatus::Succeeded,
+                    }
+                }
+                Err(e) if is_transient_error(&e) => return Err(e),
+                Err(e) => item.fail(transfer_error_code(&e), e),
+            }
+            self.batch_repository.update_batch_item(&batch.batch_id, &batch.items[index]).await?;
+
+            // Keep the counters current so GET /transfers/batches/{id} shows progress
+            batch.refresh_counts();
+            batch.updated_at = self.clock.now();
+            self.batch_repository.update_batch(batch).await?;
+        }
+
+        batch.status = BatchStatus::Completed;
+
+        Ok(())
+    }
+
+    async fn find_replayed_batch(&self, idempotency_key: &str, fingerprint: &str) -> Result<Option<TransferBatch>, String> {
+        let Some(batch) = self.batch_repository.get_batch_by_idempotency_key(idempotency_key).await? else {
+            return Ok(None);
+        };
+
+        if batch.request_fingerprint != fingerprint {
+            return Err("Idempotency key already used with a different request".to_string());
+        }
+
+        Ok(Some(batch))
+    }
+}
+
+fn skip_pending_items(items: &mut [BatchItem], reason: &str) {
+    for item in items.iter_mut().filter(|item| item.status == BatchItemStatus::Pending) {
+        item.status = BatchItemStatus::Skipped;
+        item.error_code = Some("BATCH_ABORTED".to_string());
+        item.error_message = Some(reason.to_string());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,52 @@
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use super::BatchService;
+
+// Background loop that executes submitted transfer batches, oldest first
+#[derive(Clone)]
+pub struct BatchWorker {
+    batch_service: BatchService,
+    poll_interval: Duration,
+    batch_size: u32,
+}
+
+impl BatchWorker {
+    pub fn new(batch_service: BatchService) -> Self {
+        Self {
+            batch_service,
+            poll_interval: Duration::from_millis(500),
+            batch_size: 10,
+        }
+    }
+
+    pub fn spawn(self) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            loop {
+                match self.run_once().await {
+                    // Something moved on, so more may be waiting: go again straight away. A poll where nothing
+                    // moved (everything hit a transient error) waits, so the same page is not retried in a tight loop
+                    Ok(moved) if moved > 0 => continue,
+                    Ok(_) => {}
+                    Err(e) => eprintln!("⚠️  Batch worker failed to poll unfinished batches: {}", e),
+                }
+                tokio::time::sleep(self.poll_interval).await;
+            }
+        })
+    }
+
+    // Processes the unfinished transfer batches and returns how many of them were processed
+    pub async fn run_once(&self) -> Result<usize, String> {
+        let batches = self.batch_service.list_unfinished_batches(self.batch_size).await?;
+
+        let mut moved = 0;
+        for batch in &batches {
+            match self.batch_service.process_batch(&batch.batch_id).await {
+                Ok(_) => moved += 1,
+                // Left processing; the next poll resumes it
+                Err(e) => eprintln!("⚠️  Batch {} could not be processed: {}", batch.batch_id, e),
+            }
+        }
+
+        Ok(moved)
+    }
+}
\ No newline at end of file
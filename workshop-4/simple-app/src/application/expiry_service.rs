@@ -0,0 +1,242 @@
+use std::sync::Arc;
+use chrono::{DateTime, Duration, Months, Utc};
+use crate::domain::{
+    EventType, ExpiringPoints, ExpiringPointsResponse, PointLedgerRepository, UserRepository, UnitOfWorkFactory, Clock,
+};
+use super::AccountLocks;
+
+const PAGE_SIZE: u32 = 100;
+
+// Expires points that were not spent within their lifetime. Each credit is a lot; spending drains the oldest
+// lots first, so whatever is left of a lot when it reaches the end of its lifetime goes.
+#[derive(Clone)]
+pub struct PointsExpiryService {
+    point_ledger_repository: Arc<dyn PointLedgerRepository + Send + Sync>,
+    user_repository: Arc<dyn UserRepository + Send + Sync>,
+    unit_of_work_factory: Arc<dyn UnitOfWorkFactory + Send + Sync>,
+    account_locks: AccountLocks,
+    clock: Arc<dyn Clock + Send + Sync>,
+    lifetime_months: u32,
+}
+
+impl PointsExpiryService {
+    pub fn new(
+        point_ledger_repository: Arc<dyn PointLedgerRepository + Send + Sync>,
+        user_repository: Arc<dyn UserRepository + Send + Sync>,
+        unit_of_work_factory: Arc<dyn UnitOfWorkFactory + Send + Sync>,
+        account_locks: AccountLocks,
+        clock: Arc<dyn Clock + Send + Sync>,
+    ) -> Self {
+        Self {
+            point_ledger_repository,
+            user_repository,
+            unit_of_work_factory,
+            account_locks,
+            clock,
+            lifetime_months: 24,
+        }
+    }
+
+    // 0 turns expiry off
+    pub fn with_lifetime_months(mut self, months: u32) -> Self {
+        self.lifetime_months = months;
+        self
+    }
+
+    // Posts one expire entry per user with expired lots and returns how many entries were posted
+    pub async fn expire_points(&self) -> Result<usize, String> {
+        if self.lifetime_months == 0 {
+            return Ok(0);
+        }
+        let now = self.clock.now();
+        let cutoff = now.checked_sub_months(Months::new(self.lifetime_months))
+            .ok_or("Date out of range".to_string())?;
+
+        let mut posted = 0;
+        let mut after_user_id = 0;
+        loop {
+            let user_ids = self.point_ledger_repository.get_users_with_lots_earned_before(cutoff, after_user_id, PAGE_SIZE).await?;
+            for &user_id in &user_ids {
+                after_user_id = user_id;
+                if self.expire_user_points(user_id, cutoff).await? {
+                    posted += 1;
+                }
+            }
+            if (user_ids.len() as u32) < PAGE_SIZE {
+                break;
+            }
+        }
+
+        Ok(posted)
+    }
+
+    async fn expire_user_points(&self, user_id: u32, cutoff: DateTime<Utc>) -> Result<bool, String> {
+        let _account_lock = self.account_locks.lock(&[user_id]).await;
+        let uow = self.unit_of_work_factory.begin().await?;
+        let point_ledger_repository = uow.point_ledger();
+
+        let expired: u32 = point_ledger_repository.get_open_lots(user_id).await?
+            .iter()
+            .filter(|lot| lot.earned_at <= cutoff)
+            .map(|lot| lot.remaining)
+            .sum();
+
+        // Points under a hold or a pending transfer are already promised; they expire once that is settled
+        let available = point_ledger_repository.get_available_balance(user_id).await?;
+        let amount = expired.min(available);
+        if amount == 0 {
+            uow.rollback().await?;
+            return Ok(false);
+        }
+
+        // The expired lots are the oldest, so this debit drains exactly those
+        let balance = point_ledger_repository.get_current_balance(user_id).await?;
+        point_ledger_repository.create_ledger_entry(
+            user_id,
+            -(amount as i32),
+            balance - amount,
+            EventType::Expire,
+            None,
+            Some("Points expired".to_string()),
+            Some(serde_json::json!({
+                "earned_before": cutoff.to_rfc3339(),
+                "lifetime_months": self.lifetime_months
+            }).to_string()),
+        ).await?;
+
+        uow.commit().await?;
+
+        Ok(true)
+    }
+
+    pub async fn list_expiring(&self, user_id: u32, within_days: u32) -> Result<ExpiringPointsResponse, String> {
+        if within_days == 0 || within_days > 3650 {
+            return Err("withinDays must be between 1 and 3650".to_string());
+        }
+
+        // Check if user exists
+        let _user = self.user_repository.get_user_by_id(user_id).await?
+            .ok_or("User not found".to_string())?;
+
+        let mut data = Vec::new();
+        if self.lifetime_months > 0 {
+            let horizon = self.clock.now() + Duration::days(within_days as i64);
+            for lot in self.point_ledger_repository.get_open_lots(user_id).await? {
+                let expires_at = lot.earned_at.checked_add_months(Months::new(self.lifetime_months))
+                    .ok_or("Date out of range".to_string())?;
+                // Lots are oldest first, so the rest expire later still
+                if expires_at > horizon {
+                    break;
+                }
+                data.push(ExpiringPoints {
+                    ledger_entry_id: lot.ledger_entry_id,
+                    points: lot.remaining,
+                    earned_at: lot.earned_at,
+                    expires_at,
+                });
+            }
+        }
+
+        Ok(ExpiringPointsResponse {
+            user_id,
+            within_days,
+            total_expiring: data.iter().map(|lot| lot.points).sum(),
+            data,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use chrono::{Duration, Months, Utc};
+    use sqlx::SqlitePool;
+    use crate::domain::{Clock, CreateHoldRequest, EventType, PointLedgerRepository};
+    use crate::infrastructure::{SqliteHoldRepository, SqlitePointLedgerRepository, SqliteUserRepository, SqliteUnitOfWorkFactory};
+    use crate::infrastructure::test_support::{test_pool, FakeClock};
+    use crate::application::{AccountLocks, HoldService};
+    use super::PointsExpiryService;
+
+    async fn expired(pool: &SqlitePool, user_id: u32) -> i64 {
+        sqlx::query_scalar("SELECT COALESCE(SUM(-change), 0) FROM point_ledger WHERE user_id = ? AND event_type = 'expire'")
+            .bind(user_id as i64)
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn points_left_unspent_for_their_lifetime_expire_oldest_first() {
+        let pool = test_pool().await;
+        let clock = Arc::new(FakeClock::new(Utc::now()));
+        let ledger = Arc::new(SqlitePointLedgerRepository::new(pool.clone()));
+        let user_repository = Arc::new(SqliteUserRepository::new(pool.clone()));
+        let unit_of_work_factory = Arc::new(SqliteUnitOfWorkFactory::new(pool.clone()));
+        let account_locks = AccountLocks::new();
+        let service = PointsExpiryService::new(
+            ledger.clone(),
+            user_repository.clone(),
+            unit_of_work_factory.clone(),
+            account_locks.clone(),
+            clock.clone(),
+        );
+        let holds = HoldService::new(
+            Arc::new(SqliteHoldRepository::new(pool.clone())),
+            ledger.clone(),
+            user_repository,
+            unit_of_work_factory,
+            account_locks,
+            clock.clone(),
+        );
+
+        // John spends 300 of his seed points, which come out of the oldest lot, and earns 100 a year later
+        ledger.create_ledger_entry(1, -300, 1200, EventType::Redeem, None, None, None).await.unwrap();
+        let later = ledger.create_ledger_entry(1, 100, 1300, EventType::Earn, None, None, None).await.unwrap();
+        sqlx::query("UPDATE point_lots SET earned_at = ? WHERE ledger_entry_id = ?")
+            .bind((clock.now() + Months::new(12)).to_rfc3339())
+            .bind(later.id as i64)
+            .execute(&pool)
+            .await
+            .unwrap();
+        // Jane has most of hers on hold
+        let hold = holds.create_hold(CreateHoldRequest { user_id: 2, amount: 700, reference: None, expires_at: None }).await.unwrap();
+
+        assert!(service.list_expiring(1, 30).await.unwrap().data.is_empty());
+        clock.advance(Duration::days(720));
+        let expiring = service.list_expiring(1, 30).await.unwrap();
+        assert_eq!(expiring.total_expiring, 1200);
+        assert_eq!(expiring.data.len(), 1);
+
+        clock.advance(Duration::days(11));
+        assert_eq!(service.expire_points().await.unwrap(), 3);
+        assert_eq!((expired(&pool, 1).await, expired(&pool, 2).await, expired(&pool, 3).await), (1200, 50, 200));
+        assert_eq!(ledger.get_current_balance(1).await.unwrap(), 100);
+
+        // The held points go once the hold no longer needs them, and not twice
+        assert_eq!(service.expire_points().await.unwrap(), 0);
+        holds.release_hold(&hold.hold_id).await.unwrap();
+        assert_eq!(service.expire_points().await.unwrap(), 1);
+        assert_eq!(expired(&pool, 2).await, 750);
+
+        clock.advance(Duration::days(366));
+        assert_eq!(service.expire_points().await.unwrap(), 1);
+        assert_eq!(ledger.get_current_balance(1).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn a_lifetime_of_zero_turns_expiry_off() {
+        let pool = test_pool().await;
+        let clock = Arc::new(FakeClock::new(Utc::now() + Duration::days(3650)));
+        let service = PointsExpiryService::new(
+            Arc::new(SqlitePointLedgerRepository::new(pool.clone())),
+            Arc::new(SqliteUserRepository::new(pool.clone())),
+            Arc::new(SqliteUnitOfWorkFactory::new(pool.clone())),
+            AccountLocks::new(),
+            clock,
+        ).with_lifetime_months(0);
+
+        assert_eq!(service.expire_points().await.unwrap(), 0);
+        assert!(service.list_expiring(1, 3650).await.unwrap().data.is_empty());
+        assert_eq!(expired(&pool, 1).await, 0);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,35 @@
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use super::PointsExpiryService;
+
+// Background loop that expires unspent points once a day
+#[derive(Clone)]
+pub struct ExpiryWorker {
+    expiry_service: PointsExpiryService,
+    interval: Duration,
+}
+
+impl ExpiryWorker {
+    pub fn new(expiry_service: PointsExpiryService) -> Self {
+        Self {
+            expiry_service,
+            interval: Duration::from_secs(24 * 3600),
+        }
+    }
+
+    pub fn spawn(self) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            loop {
+                if let Err(e) = self.run_once().await {
+                    eprintln!("⚠️  Expiry worker failed to expire points: {}", e);
+                }
+                tokio::time::sleep(self.interval).await;
+            }
+        })
+    }
+
+    // Expires everything that is due and returns how many expire entries were posted
+    pub async fn run_once(&self) -> Result<usize, String> {
+        self.expiry_service.expire_points().await
+    }
+}
\ No newline at end of file
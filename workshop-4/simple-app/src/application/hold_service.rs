@@ -0,0 +1,219 @@
+use std::sync::Arc;
+use chrono::Duration;
+use uuid::Uuid;
+use crate::domain::{
+    PointHold, HoldStatus, HoldRepository, CreateHoldRequest, CaptureHoldRequest, HoldListResponse, PointBalance,
+    EventType, PointLedgerRepository, UserRepository, UnitOfWorkFactory, Clock,
+};
+use super::AccountLocks;
+
+#[derive(Clone)]
+pub struct HoldService {
+    hold_repository: Arc<dyn HoldRepository + Send + Sync>,
+    point_ledger_repository: Arc<dyn PointLedgerRepository + Send + Sync>,
+    user_repository: Arc<dyn UserRepository + Send + Sync>,
+    unit_of_work_factory: Arc<dyn UnitOfWorkFactory + Send + Sync>,
+    account_locks: AccountLocks,
+    clock: Arc<dyn Clock + Send + Sync>,
+    default_expiry: Duration,
+    max_expiry: Duration,
+}
+
+impl HoldService {
+    pub fn new(
+        hold_repository: Arc<dyn HoldRepository + Send + Sync>,
+        point_ledger_repository: Arc<dyn PointLedgerRepository + Send + Sync>,
+        user_repository: Arc<dyn UserRepository + Send + Sync>,
+        unit_of_work_factory: Arc<dyn UnitOfWorkFactory + Send + Sync>,
+        account_locks: AccountLocks,
+        clock: Arc<dyn Clock + Send + Sync>,
+    ) -> Self {
+        Self {
+            hold_repository,
+            point_ledger_repository,
+            user_repository,
+            unit_of_work_factory,
+            account_locks,
+            clock,
+            default_expiry: Duration::hours(24),
+            max_expiry: Duration::days(30),
+        }
+    }
+
+    pub async fn create_hold(&self, request: CreateHoldRequest) -> Result<PointHold, String> {
+        // Validate request
+        request.validate()?;
+        let now = self.clock.now();
+        let expires_at = request.expires_at.unwrap_or(now + self.default_expiry);
+        if expires_at <= now {
+            return Err("expiresAt must be in the future".to_string());
+        }
+        if expires_at > now + self.max_expiry {
+            return Err(format!("expiresAt cannot be more than {} days away", self.max_expiry.num_days()));
+        }
+
+        // Check if user exists
+        let _user = self.user_repository.get_user_by_id(request.user_id).await?
+            .ok_or("User not found".to_string())?;
+
+        let _account_lock = self.account_locks.lock(&[request.user_id]).await;
+        let uow = self.unit_of_work_factory.begin().await?;
+
+        let available_balance = uow.point_ledger().get_available_balance(request.user_id).await?;
+        if available_balance < request.amount {
+            return Err("Insufficient points".to_string());
+        }
+
+        let hold = PointHold {
+            hold_id: Uuid::new_v4().to_string(),
+            user_id: request.user_id,
+            amount: request.amount,
+            captured_amount: 0,
+            status: HoldStatus::Active,
+            reference: request.reference,
+            ledger_entry_id: None,
+            expires_at,
+            created_at: now,
+            updated_at: now,
+        };
+        uow.holds().create_hold(&hold).await?;
+
+        uow.commit().await?;
+
+        Ok(hold)
+    }
+
+    pub async fn get_hold(&self, hold_id: &str) -> Result<PointHold, String> {
+        self.hold_repository.get_hold(hold_id).await?
+            .ok_or("Hold not found".to_string())
+    }
+
+    pub async fn list_holds(&self, user_id: u32) -> Result<HoldListResponse, String> {
+        // Check if user exists
+        let _user = self.user_repository.get_user_by_id(user_id).await?
+            .ok_or("User not found".to_string())?;
+
+        let data = self.hold_repository.get_holds_by_user_id(user_id).await?;
+
+        Ok(HoldListResponse { data })
+    }
+
+    // Turns the hold into a redeem ledger entry; a partial capture releases the rest
+    pub async fn capture_hold(&self, hold_id: &str, captured_by: &str, request: CaptureHoldRequest) -> Result<PointHold, String> {
+        let hold = self.get_hold(hold_id).await?;
+
+        let _account_lock = self.account_locks.lock(&[hold.user_id]).await;
+        let uow = self.unit_of_work_factory.begin().await?;
+
+        // Re-read inside the transaction so a capture cannot race a release or the expiry sweep
+        let mut hold = uow.holds().get_hold(hold_id).await?
+            .ok_or("Hold not found".to_string())?;
+        let now = self.clock.now();
+        if hold.status != HoldStatus::Active {
+            return Err(format!("Only active holds can be captured (current status: {})", hold.status));
+        }
+        if hold.is_expired(now) {
+            return Err("Hold has expired".to_string());
+        }
+
+        let amount = request.amount.unwrap_or(hold.amount);
+        if amount == 0 || amount > hold.amount {
+            return Err(format!("Capture amount must be between 1 and the held amount ({})", hold.amount));
+        }
+
+        // The hold itself already kept these points out of every other debit
+        let point_ledger_repository = uow.point_ledger();
+        let balance = point_ledger_repository.get_current_balance(hold.user_id).await?;
+        if balance < amount {
+            return Err("Insufficient points".to_string());
+        }
+
+        let entry = point_ledger_repository.create_ledger_entry(
+            hold.user_id,
+            -(amount as i32),
+            balance - amount,
+            EventType::Redeem,
+            None,
+            Some(format!("Capture of hold {}", hold.hold_id)),
+            Some(serde_json::json!({
+                "hold_id": hold.hold_id,
+                "held_amount": hold.amount,
+                "reference": hold.reference,
+                "captured_by": captured_by
+            }).to_string()),
+        ).await?;
+
+        hold.status = HoldStatus::Captured;
+        hold.captured_amount = amount;
+        hold.ledger_entry_id = Some(entry.id);
+        hold.updated_at = now;
+        uow.holds().update_hold(&hold).await?;
+
+        uow.commit().await?;
+
+        Ok(hold)
+    }
+
+    pub async fn release_hold(&self, hold_id: &str) -> Result<PointHold, String> {
+        let uow = self.unit_of_work_factory.begin().await?;
+
+        let mut hold = uow.holds().get_hold(hold_id).await?
+            .ok_or("Hold not found".to_string())?;
+        if hold.status != HoldStatus::Active {
+            return Err(format!("Only active holds can be released (current status: {})", hold.status));
+        }
+
+        // No ledger rows are touched: the points never left the account
+        hold.status = HoldStatus::Released;
+        hold.updated_at = self.clock.now();
+        uow.holds().update_hold(&hold).await?;
+
+        uow.commit().await?;
+
+        Ok(hold)
+    }
+
+    // Frees holds that reached expiresAt and returns how many were picked up
+    pub async fn expire_holds(&self, limit: u32) -> Result<usize, String> {
+        let now = self.clock.now();
+        let expired = self.hold_repository.get_expired_holds(now, limit).await?;
+
+        for hold in &expired {
+            let uow = self.unit_of_work_factory.begin().await?;
+
+            // Captured or released in the meantime
+            let Some(mut hold) = uow.holds().get_hold(&hold.hold_id).await? else {
+                uow.rollback().await?;
+                continue;
+            };
+            if hold.status != HoldStatus::Active {
+                uow.rollback().await?;
+                continue;
+            }
+
+            hold.status = HoldStatus::Expired;
+            hold.updated_at = now;
+            uow.holds().update_hold(&hold).await?;
+
+            uow.commit().await?;
+        }
+
+        Ok(expired.len())
+    }
+
+    pub async fn get_balance(&self, user_id: u32) -> Result<PointBalance, String> {
+        // Check if user exists
+        let _user = self.user_repository.get_user_by_id(user_id).await?
+            .ok_or("User not found".to_string())?;
+
+        let ledger_balance = self.point_ledger_repository.get_current_balance(user_id).await?;
+        let held_points = self.point_ledger_repository.get_held_amount(user_id).await?;
+
+        Ok(PointBalance {
+            user_id,
+            ledger_balance,
+            held_points,
+            available_balance: ledger_balance.saturating_sub(held_points),
+        })
+    }
+}
\ No newline at end of file
@@ -0,0 +1,40 @@
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use super::HoldService;
+
+// Background loop that expires point holds nobody captured or released in time
+#[derive(Clone)]
+pub struct HoldWorker {
+    hold_service: HoldService,
+    poll_interval: Duration,
+    batch_size: u32,
+}
+
+impl HoldWorker {
+    pub fn new(hold_service: HoldService) -> Self {
+        Self {
+            hold_service,
+            poll_interval: Duration::from_secs(1),
+            batch_size: 50,
+        }
+    }
+
+    pub fn spawn(self) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            loop {
+                match self.run_once().await {
+                    // A full batch means there is likely more waiting, so go again straight away
+                    Ok(expired) if expired as u32 >= self.batch_size => continue,
+                    Ok(_) => {}
+                    Err(e) => eprintln!("⚠️  Hold worker failed to expire holds: {}", e),
+                }
+                tokio::time::sleep(self.poll_interval).await;
+            }
+        })
+    }
+
+    // Expires one batch of holds past their expiresAt and returns how many were picked up
+    pub async fn run_once(&self) -> Result<usize, String> {
+        self.hold_service.expire_holds(self.batch_size).await
+    }
+}
\ No newline at end of file
@@ -0,0 +1,214 @@
+use std::sync::Arc;
+use chrono::{DateTime, Duration, NaiveTime, Utc};
+use crate::domain::{
+    PointLedgerRepository, TransferRepository, UserRepository, LedgerFilter, LedgerListResponse, LedgerEntryDetail,
+    LedgerCounterparty, HistoricalBalance, BalanceInterval, BalanceSeriesPoint, BalanceSeriesResponse, MAX_SERIES_POINTS,
+};
+
+#[derive(Clone)]
+pub struct LedgerService {
+    point_ledger_repository: Arc<dyn PointLedgerRepository + Send + Sync>,
+    transfer_repository: Arc<dyn TransferRepository + Send + Sync>,
+    user_repository: Arc<dyn UserRepository + Send + Sync>,
+}
+
+impl LedgerService {
+    pub fn new(
+        point_ledger_repository: Arc<dyn PointLedgerRepository + Send + Sync>,
+        transfer_repository: Arc<dyn TransferRepository + Send + Sync>,
+        user_repository: Arc<dyn UserRepository + Send + Sync>,
+    ) -> Self {
+        Self {
+            point_ledger_repository,
+            transfer_repository,
+            user_repository,
+        }
+    }
+
+    pub async fn list_ledger(&self, user_id: u32, filter: LedgerFilter, cursor: Option<u32>, page: u32, page_size: u32) -> Result<LedgerListResponse, String> {
+        // Validate parameters
+        if page == 0 {
+            return Err("Page must be greater than 0".to_string());
+        }
+        if page_size == 0 || page_size > 200 {
+            return Err("Page size must be between 1 and 200".to_string());
+        }
+        filter.validate()?;
+
+        // Check if user exists
+        let _user = self.user_repository.get_user_by_id(user_id).await?
+            .ok_or("User not found".to_string())?;
+
+        let (entries, totals) = self.point_ledger_repository.get_ledger_by_user_id(user_id, &filter, cursor, page, page_size).await?;
+
+        // A short page means there is nothing older left to fetch
+        let next_cursor = match entries.last() {
+            Some(last) if entries.len() == page_size as usize => Some(last.sequence),
+            _ => None,
+        };
+
+        Ok(LedgerListResponse {
+            data: entries,
+            page,
+            page_size,
+            total: totals.total,
+            total_in: totals.total_in,
+            total_out: totals.total_out,
+            next_cursor,
+        })
+    }
+
+    pub async fn get_ledger_entry(&self, entry_id: u32) -> Result<LedgerEntryDetail, String> {
+        let entry = self.point_ledger_repository.get_ledger_entry(entry_id).await?
+            .ok_or("Ledger entry not found".to_string())?;
+
+        let transfer = match entry.transfer_id {
+            Some(transfer_id) => self.transfer_repository.get_transfer_by_id(transfer_id).await?,
+            None => None,
+        };
+
+        // The other party is whichever side of the transfer this entry's user is not; a split parent pays itself
+        let counterparty_id = transfer.as_ref()
+            .filter(|t| t.from_user_id != t.to_user_id)
+            .map(|t| if t.from_user_id == entry.user_id { t.to_user_id } else { t.from_user_id });
+
+        let counterparty = match counterparty_id {
+            Some(user_id) => self.user_repository.get_user_by_id(user_id).await?
+                .map(|user| LedgerCounterparty {
+                    user_id: user.id,
+                    first_name: user.first_name,
+                    last_name: user.last_name,
+                }),
+            None => None,
+        };
+
+        Ok(LedgerEntryDetail {
+            entry,
+            counterparty,
+            transfer,
+        })
+    }
+
+    pub async fn balance_at(&self, user_id: u32, at: DateTime<Utc>) -> Result<HistoricalBalance, String> {
+        // Check if user exists
+        let _user = self.user_repository.get_user_by_id(user_id).await?
+            .ok_or("User not found".to_string())?;
+
+        let last_entry = self.point_ledger_repository.get_balance_at(user_id, at).await?;
+
+        Ok(HistoricalBalance {
+            user_id,
+            as_of: at,
+            ledger_balance: last_entry.map(|(balance, _)| balance).unwrap_or(0),
+            last_sequence: last_entry.map(|(_, sequence)| sequence),
+        })
+    }
+
+    // The balance at the end of each period from `from` through `to`; the first period starts at the beginning
+    // of the period `from` falls in
+    pub async fn balance_series(
+        &self,
+        user_id: u32,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        interval: BalanceInterval,
+    ) -> Result<BalanceSeriesResponse, String> {
+        if from > to {
+            return Err("from must be before to".to_string());
+        }
+
+        // Check if user exists
+        let _user = self.user_repository.get_user_by_id(user_id).await?
+            .ok_or("User not found".to_string())?;
+
+        let mut data = Vec::new();
+        let mut period_start = interval.period_start(from.date_naive());
+        while period_start.and_time(NaiveTime::MIN).and_utc() <= to {
+            if data.len() == MAX_SERIES_POINTS {
+                return Err(format!("A series cannot have more than {} periods; use a longer interval or a shorter range", MAX_SERIES_POINTS));
+            }
+
+            let next_start = interval.next_period_start(period_start)
+                .ok_or("Date out of range".to_string())?;
+            let period_end = next_start.and_time(NaiveTime::MIN).and_utc() - Duration::nanoseconds(1);
+            let as_of = period_end.min(to);
+            let balance = self.point_ledger_repository.get_balance_at(user_id, as_of).await?
+                .map(|(balance, _)| balance)
+                .unwrap_or(0);
+
+            data.push(BalanceSeriesPoint {
+                period_start,
+                as_of,
+                balance,
+            });
+            period_start = next_start;
+        }
+
+        Ok(BalanceSeriesResponse {
+            user_id,
+            interval,
+            from,
+            to,
+            data,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use chrono::{DateTime, Utc};
+    use crate::domain::BalanceInterval;
+    use crate::infrastructure::{SqlitePointLedgerRepository, SqliteTransferRepository, SqliteUserRepository};
+    use crate::infrastructure::test_support::test_pool;
+    use super::LedgerService;
+
+    fn instant(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[tokio::test]
+    async fn balances_are_taken_at_the_instant_whatever_offset_an_entry_was_written_with() {
+        let pool = test_pool().await;
+        let service = LedgerService::new(
+            Arc::new(SqlitePointLedgerRepository::new(pool.clone())),
+            Arc::new(SqliteTransferRepository::new(pool.clone())),
+            Arc::new(SqliteUserRepository::new(pool.clone())),
+        );
+        // Bob's opening 200 is dated today; these two follow it years later. The second is 02:00 UTC on
+        // 1 January, but written as the evening before in UTC-6
+        for (sequence, change, balance_after, created_at) in [
+            (2, 100, 300, "2029-12-31T12:00:00+00:00"),
+            (3, 50, 350, "2029-12-31T20:00:00-06:00"),
+        ] {
+            sqlx::query(
+                "INSERT INTO point_ledger (user_id, sequence, change, balance_after, event_type, created_at, hash, prev_hash) \
+                 VALUES (3, ?, ?, ?, 'earn', ?, 'test', 'test')",
+            )
+            .bind(sequence)
+            .bind(change)
+            .bind(balance_after)
+            .bind(created_at)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let balance = service.balance_at(3, instant("2030-01-01T01:00:00Z")).await.unwrap();
+        assert_eq!((balance.ledger_balance, balance.last_sequence), (300, Some(2)));
+        let balance = service.balance_at(3, instant("2030-01-01T02:00:00Z")).await.unwrap();
+        assert_eq!((balance.ledger_balance, balance.last_sequence), (350, Some(3)));
+
+        let to = instant("2030-01-02T12:00:00Z");
+        let series = service.balance_series(3, instant("2029-12-30T08:00:00Z"), to, BalanceInterval::Day).await.unwrap();
+        let balances: Vec<_> = series.data.iter().map(|point| (point.period_start.to_string(), point.balance)).collect();
+        assert_eq!(balances, [
+            ("2029-12-30".to_string(), 200),
+            ("2029-12-31".to_string(), 300),
+            ("2030-01-01".to_string(), 350),
+            ("2030-01-02".to_string(), 350),
+        ]);
+        assert_eq!(series.data.last().unwrap().as_of, to);
+        assert_eq!(series.data[1].as_of, instant("2029-12-31T23:59:59.999999999Z"));
+    }
+}
\ No newline at end of file
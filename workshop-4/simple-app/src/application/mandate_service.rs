@@ -0,0 +1,373 @@
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use crate::domain::{
+    TransferMandate, MandateStatus, MandateRepository, CreateMandateRequest, UpdateMandateRequest, MandateListResponse,
+    CreateTransferRequest, UserRepository, Clock,
+};
+use super::TransferService;
+use super::transfer_worker::is_transient_error;
+
+#[derive(Clone)]
+pub struct MandateService {
+    mandate_repository: Arc<dyn MandateRepository + Send + Sync>,
+    user_repository: Arc<dyn UserRepository + Send + Sync>,
+    transfer_service: TransferService,
+    clock: Arc<dyn Clock + Send + Sync>,
+    // Serializes edits, pause/resume and runs so a run never saves over a concurrent change
+    mandate_lock: Arc<Mutex<()>>,
+}
+
+impl MandateService {
+    pub fn new(
+        mandate_repository: Arc<dyn MandateRepository + Send + Sync>,
+        user_repository: Arc<dyn UserRepository + Send + Sync>,
+        transfer_service: TransferService,
+        clock: Arc<dyn Clock + Send + Sync>,
+    ) -> Self {
+        Self {
+            mandate_repository,
+            user_repository,
+            transfer_service,
+            clock,
+            mandate_lock: Arc::new(Mutex::new(())),
+        }
+    }
+
+    pub async fn create_mandate(&self, request: CreateMandateRequest) -> Result<TransferMandate, String> {
+        // Validate request
+        request.validate()?;
+        let now = self.clock.now();
+        let start_at = request.start_at.unwrap_or(now);
+        if start_at < now {
+            return Err("startAt cannot be in the past".to_string());
+        }
+        if request.end_at.is_some_and(|end_at| end_at < start_at) {
+            return Err("endAt cannot be before startAt".to_string());
+        }
+
+        // Check if users exist
+        let _from_user = self.user_repository.get_user_by_id(request.from_user_id).await?
+            .ok_or("From user not found".to_string())?;
+
+        let _to_user = self.user_repository.get_user_by_id(request.to_user_id).await?
+            .ok_or("To user not found".to_string())?;
+
+        self.mandate_repository.create_mandate(request, start_at).await
+    }
+
+    pub async fn get_mandate(&self, id: u32) -> Result<TransferMandate, String> {
+        self.mandate_repository.get_mandate(id).await?
+            .ok_or("Mandate not found".to_string())
+    }
+
+    pub async fn list_mandates(&self, user_id: u32) -> Result<MandateListResponse, String> {
+        // Check if user exists
+        let _user = self.user_repository.get_user_by_id(user_id).await?
+            .ok_or("User not found".to_string())?;
+
+        let mandates = self.mandate_repository.get_mandates_by_user_id(user_id).await?;
+
+        Ok(MandateListResponse {
+            total: mandates.len() as u32,
+            data: mandates,
+        })
+    }
+
+    // Only the payer (the signed-in user `user_id`) may change, cancel, pause or resume a mandate
+    async fn get_payer_mandate(&self, id: u32, user_id: u32) -> Result<TransferMandate, String> {
+        let mandate = self.get_mandate(id).await?;
+        if mandate.from_user_id != user_id {
+            return Err("Only the payer can change this mandate".to_string());
+        }
+
+        Ok(mandate)
+    }
+
+    pub async fn update_mandate(&self, id: u32, user_id: u32, request: UpdateMandateRequest) -> Result<TransferMandate, String> {
+        // Validate request
+        request.validate()?;
+
+        let _lock = self.mandate_lock.lock().await;
+        let mut mandate = self.get_payer_mandate(id, user_id).await?;
+        if !matches!(mandate.status, MandateStatus::Active | MandateStatus::Paused) {
+            return Err(format!("Only active or paused mandates can be changed (current status: {})", mandate.status));
+        }
+        if request.end_at.is_some_and(|end_at| end_at < mandate.start_at) {
+            return Err("endAt cannot be before startAt".to_string());
+        }
+
+        if let Some(amount) = request.amount {
+            mandate.amount = amount;
+        }
+        if let Some(note) = request.note {
+            mandate.note = Some(note);
+        }
+        if let Some(end_at) = request.end_at {
+            mandate.end_at = Some(end_at);
+        }
+        if let Some(max_runs) = request.max_runs {
+            mandate.max_runs = Some(max_runs);
+        }
+
+        // A tighter endAt or maxRuns may leave nothing left to run
+        let next_occurrence = mandate.next_occurrence;
+        mandate.schedule_next(next_occurrence, None);
+        mandate.updated_at = self.clock.now();
+
+        self.mandate_repository.update_mandate(&mandate).await?;
+
+        Ok(mandate)
+    }
+
+    pub async fn cancel_mandate(&self, id: u32, user_id: u32) -> Result<TransferMandate, String> {
+        let _lock = self.mandate_lock.lock().await;
+        let mut mandate = self.get_payer_mandate(id, user_id).await?;
+        if !matches!(mandate.status, MandateStatus::Active | MandateStatus::Paused) {
+            return Err(format!("Only active or paused mandates can be cancelled (current status: {})", mandate.status));
+        }
+
+        mandate.status = MandateStatus::Cancelled;
+        mandate.next_run_at = None;
+        mandate.updated_at = self.clock.now();
+
+        self.mandate_repository.update_mandate(&mandate).await?;
+
+        Ok(mandate)
+    }
+
+    pub async fn pause_mandate(&self, id: u32, user_id: u32) -> Result<TransferMandate, String> {
+        let _lock = self.mandate_lock.lock().await;
+        let mut mandate = self.get_payer_mandate(id, user_id).await?;
+        if mandate.status != MandateStatus::Active {
+            return Err(format!("Only active mandates can be paused (current status: {})", mandate.status));
+        }
+
+        mandate.status = MandateStatus::Paused;
+        mandate.updated_at = self.clock.now();
+
+        self.mandate_repository.update_mandate(&mandate).await?;
+
+        Ok(mandate)
+    }
+
+    pub async fn resume_mandate(&self, id: u32, user_id: u32) -> Result<TransferMandate, String> {
+        let _lock = self.mandate_lock.lock().await;
+        let mut mandate = self.get_payer_mandate(id, user_id).await?;
+        if mandate.status != MandateStatus::Paused {
+            return Err(format!("Only paused mandates can be resumed (current status: {})", mandate.status));
+        }
+
+        // Runs that fell due while paused are skipped, not caught up
+        let now = self.clock.now();
+        mandate.status = MandateStatus::Active;
+        let next_occurrence = mandate.next_occurrence;
+        mandate.schedule_next(next_occurrence, Some(now));
+        mandate.updated_at = now;
+
+        self.mandate_repository.update_mandate(&mandate).await?;
+
+        Ok(mandate)
+    }
+
+    // Active mandates with a run due at the clock's now
+    pub async fn list_due_mandates(&self, limit: u32) -> Result<Vec<TransferMandate>, String> {
+        self.mandate_repository.get_due_mandates(self.clock.now(), limit).await
+    }
+
+    // Makes the mandate's next due run as a normal transfer. The run is recorded per mandate and occurrence
+    // together with the transfer, so if the process dies before the mandate is advanced the retry gets
+    // the transfer that was already created instead of sending the points again, however late it comes.
+    pub async fn run_mandate(&self, id: u32) -> Result<TransferMandate, String> {
+        let _lock = self.mandate_lock.lock().await;
+        let mut mandate = self.get_mandate(id).await?;
+        let now = self.clock.now();
+        if mandate.status != MandateStatus::Active || mandate.next_run_at.is_none_or(|next_run_at| next_run_at > now) {
+            return Ok(mandate);
+        }
+
+        let request = CreateTransferRequest {
+            from_user_id: mandate.from_user_id,
+            to_user_id: mandate.to_user_id,
+            amount: mandate.amount,
+            note: mandate.note.clone(),
+            execute_at: None,
+        };
+
+        match self.transfer_service.create_mandate_transfer(request, &mandate).await {
+            Ok(response) => {
+                mandate.last_transfer_idem_key = Some(response.transfer.idem_key);
+                mandate.last_error = response.transfer.fail_reason;
+            }
+            // Left due; the next poll tries again
+            Err(e) if is_transient_error(&e) => return Err(e),
+            // This occurrence already ran under the mandate's earlier terms
+            Err(e) if e.contains("different request") => {}
+            // The run is skipped (e.g. insufficient points) and the mandate moves on
+            Err(e) => mandate.last_error = Some(e),
+        }
+
+        mandate.run_count += 1;
+        let next_occurrence = mandate.next_occurrence + 1;
+        mandate.schedule_next(next_occurrence, None);
+        mandate.updated_at = now;
+
+        self.mandate_repository.update_mandate(&mandate).await?;
+
+        Ok(mandate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+    use sqlx::SqlitePool;
+    use crate::domain::{MandateFrequency, MandateRun, Transfer, TransferRepository};
+    use crate::infrastructure::{
+        SqliteMandateRepository, SqliteTransferRepository, SqliteUserRepository, SqliteTransferLimitRepository, SqliteUnitOfWorkFactory,
+    };
+    use crate::infrastructure::test_support::{test_pool, FakeClock};
+
+    fn service(pool: &SqlitePool, clock: Arc<FakeClock>) -> MandateService {
+        let transfer_service = TransferService::new(
+            Arc::new(SqliteTransferRepository::new(pool.clone())),
+            Arc::new(SqliteUserRepository::new(pool.clone())),
+            Arc::new(SqliteTransferLimitRepository::new(pool.clone())),
+            Arc::new(SqliteUnitOfWorkFactory::new(pool.clone())),
+            clock.clone(),
+        );
+        MandateService::new(
+            Arc::new(SqliteMandateRepository::new(pool.clone())),
+            Arc::new(SqliteUserRepository::new(pool.clone())),
+            transfer_service,
+            clock,
+        )
+    }
+
+    fn daily(from_user_id: u32, to_user_id: u32, amount: u32, max_runs: Option<u32>) -> CreateMandateRequest {
+        CreateMandateRequest {
+            from_user_id,
+            to_user_id,
+            amount,
+            note: None,
+            frequency: MandateFrequency::Daily,
+            start_at: None,
+            end_at: None,
+            max_runs,
+        }
+    }
+
+    async fn transfer_count(pool: &SqlitePool) -> i64 {
+        sqlx::query_scalar("SELECT COUNT(*) FROM transfers").fetch_one(pool).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn an_occurrence_pays_out_once_even_after_its_idempotency_key_expired() {
+        let pool = test_pool().await;
+        let clock = Arc::new(FakeClock::new(Utc::now()));
+        let service = service(&pool, clock.clone());
+        let mandate = service.create_mandate(daily(1, 2, 10, None)).await.unwrap();
+        let ran = service.run_mandate(mandate.id).await.unwrap();
+        assert_eq!(ran.run_count, 1);
+
+        // The process died before the mandate was advanced, and came back after the key's retention
+        sqlx::query("UPDATE transfer_mandates SET run_count = 0, next_occurrence = 0, next_run_at = ? WHERE id = ?")
+            .bind(mandate.next_run_at.unwrap().to_rfc3339())
+            .bind(mandate.id as i64)
+            .execute(&pool)
+            .await
+            .unwrap();
+        clock.advance(Duration::days(2));
+        sqlx::query("DELETE FROM idempotency_keys").execute(&pool).await.unwrap();
+
+        let retried = service.run_mandate(mandate.id).await.unwrap();
+        assert_eq!(retried.run_count, 1);
+        assert_eq!(retried.last_transfer_idem_key, ran.last_transfer_idem_key);
+        assert_eq!(transfer_count(&pool).await, 1);
+    }
+
+    #[tokio::test]
+    async fn a_daily_mandate_runs_once_a_day_skips_paused_days_and_stops_at_max_runs() {
+        let pool = test_pool().await;
+        let clock = Arc::new(FakeClock::new(Utc::now()));
+        let service = service(&pool, clock.clone());
+        let start = clock.now();
+        let mandate = service.create_mandate(daily(1, 2, 10, Some(3))).await.unwrap();
+        assert_eq!(mandate.next_run_at, Some(start));
+
+        let ran = service.run_mandate(mandate.id).await.unwrap();
+        assert_eq!(ran.run_count, 1);
+        assert_eq!(ran.next_run_at, Some(start + Duration::days(1)));
+        // Not due again until tomorrow
+        assert_eq!(service.run_mandate(mandate.id).await.unwrap().run_count, 1);
+        assert!(service.list_due_mandates(10).await.unwrap().is_empty());
+
+        // Days 1 and 2 fall due while paused and are skipped, not caught up
+        service.pause_mandate(mandate.id, 1).await.unwrap();
+        clock.advance(Duration::days(3));
+        assert_eq!(service.run_mandate(mandate.id).await.unwrap().run_count, 1);
+        let resumed = service.resume_mandate(mandate.id, 1).await.unwrap();
+        assert_eq!(resumed.next_run_at, Some(start + Duration::days(3)));
+        assert_eq!(service.list_due_mandates(10).await.unwrap().iter().map(|due| due.id).collect::<Vec<_>>(), [mandate.id]);
+
+        assert_eq!(service.run_mandate(mandate.id).await.unwrap().run_count, 2);
+        clock.advance(Duration::days(1));
+        let last = service.run_mandate(mandate.id).await.unwrap();
+        assert_eq!(last.run_count, 3);
+        assert_eq!(last.status, MandateStatus::Completed);
+        assert_eq!(last.next_run_at, None);
+
+        clock.advance(Duration::days(1));
+        assert_eq!(service.run_mandate(mandate.id).await.unwrap().run_count, 3);
+        assert_eq!(transfer_count(&pool).await, 3);
+    }
+
+    #[tokio::test]
+    async fn a_run_the_payer_cannot_cover_is_skipped_and_the_mandate_moves_on() {
+        let pool = test_pool().await;
+        let clock = Arc::new(FakeClock::new(Utc::now()));
+        let service = service(&pool, clock.clone());
+        // Bob has 200 points, enough for one run
+        let mandate = service.create_mandate(daily(3, 1, 150, None)).await.unwrap();
+
+        let first = service.run_mandate(mandate.id).await.unwrap();
+        assert_eq!(first.last_error, None);
+        clock.advance(Duration::days(1));
+        let second = service.run_mandate(mandate.id).await.unwrap();
+        assert!(second.last_error.as_deref().unwrap_or_default().contains("Insufficient points"), "{:?}", second.last_error);
+        assert_eq!(second.run_count, 2);
+        assert_eq!(second.status, MandateStatus::Active);
+        assert_eq!(second.next_run_at, Some(mandate.start_at + Duration::days(2)));
+    }
+
+    #[tokio::test]
+    async fn a_second_run_for_the_same_occurrence_is_rejected() {
+        let pool = test_pool().await;
+        let repository = SqliteMandateRepository::new(pool.clone());
+        let mandate = repository.create_mandate(CreateMandateRequest {
+            from_user_id: 1,
+            to_user_id: 2,
+            amount: 10,
+            note: None,
+            frequency: MandateFrequency::Weekly,
+            start_at: None,
+            end_at: None,
+            max_runs: None,
+        }, Utc::now()).await.unwrap();
+        let transfers = SqliteTransferRepository::new(pool.clone());
+        let request = CreateTransferRequest { from_user_id: 1, to_user_id: 2, amount: 10, note: None, execute_at: None };
+        let first = transfers.create_transfer(request.clone(), Utc::now()).await.unwrap();
+        let second = transfers.create_transfer(request, Utc::now()).await.unwrap();
+        let run = |transfer: &Transfer| MandateRun {
+            mandate_id: mandate.id,
+            occurrence: 0,
+            transfer_idem_key: transfer.idem_key.clone(),
+            created_at: Utc::now(),
+        };
+
+        repository.create_run(&run(&first)).await.unwrap();
+        let err = repository.create_run(&run(&second)).await.unwrap_err();
+        assert!(err.contains("UNIQUE constraint failed"), "{}", err);
+        assert_eq!(repository.get_run(mandate.id, 0).await.unwrap().unwrap().transfer_idem_key, first.idem_key);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,52 @@
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use super::MandateService;
+
+// Background loop that turns due transfer mandates into transfers
+#[derive(Clone)]
+pub struct MandateWorker {
+    mandate_service: MandateService,
+    poll_interval: Duration,
+    batch_size: u32,
+}
+
+impl MandateWorker {
+    pub fn new(mandate_service: MandateService) -> Self {
+        Self {
+            mandate_service,
+            poll_interval: Duration::from_secs(1),
+            batch_size: 50,
+        }
+    }
+
+    pub fn spawn(self) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            loop {
+                match self.run_once().await {
+                    // Something moved on, so more may be waiting: go again straight away. A poll where nothing
+                    // moved (everything hit a transient error) waits, so the same page is not retried in a tight loop
+                    Ok(moved) if moved > 0 => continue,
+                    Ok(_) => {}
+                    Err(e) => eprintln!("⚠️  Mandate worker failed to poll due mandates: {}", e),
+                }
+                tokio::time::sleep(self.poll_interval).await;
+            }
+        })
+    }
+
+    // Runs one batch of due mandates and returns how many of them ran
+    pub async fn run_once(&self) -> Result<usize, String> {
+        let due = self.mandate_service.list_due_mandates(self.batch_size).await?;
+
+        let mut moved = 0;
+        for mandate in &due {
+            match self.mandate_service.run_mandate(mandate.id).await {
+                Ok(_) => moved += 1,
+                // Left due; the next poll tries again
+                Err(e) => eprintln!("⚠️  Mandate {} could not be run: {}", mandate.id, e),
+            }
+        }
+
+        Ok(moved)
+    }
+}
\ No newline at end of file
@@ -1,5 +1,41 @@
 pub mod user_service;
 pub mod transfer_service;
+pub mod account_locks;
+pub mod transfer_worker;
+pub mod mandate_service;
+pub mod mandate_worker;
+pub mod batch_service;
+pub mod batch_worker;
+pub mod hold_service;
+pub mod hold_worker;
+pub mod ledger_service;
+pub mod points_service;
+pub mod adjustment_service;
+pub mod reconciliation_service;
+pub mod reconciliation_worker;
+pub mod expiry_service;
+pub mod expiry_worker;
+pub mod tier_service;
+pub mod tier_worker;
+pub mod auth_service;
 
 pub use user_service::UserService;
-pub use transfer_service::TransferService;
\ No newline at end of file
+pub use transfer_service::TransferService;
+pub use account_locks::AccountLocks;
+pub use transfer_worker::TransferWorker;
+pub use mandate_service::MandateService;
+pub use mandate_worker::MandateWorker;
+pub use batch_service::BatchService;
+pub use batch_worker::BatchWorker;
+pub use hold_service::HoldService;
+pub use hold_worker::HoldWorker;
+pub use ledger_service::LedgerService;
+pub use points_service::PointsService;
+pub use adjustment_service::AdjustmentService;
+pub use reconciliation_service::ReconciliationService;
+pub use reconciliation_worker::ReconciliationWorker;
+pub use expiry_service::PointsExpiryService;
+pub use expiry_worker::ExpiryWorker;
+pub use tier_service::TierService;
+pub use tier_worker::TierWorker;
+pub use auth_service::{AuthService, random_token};
\ No newline at end of file
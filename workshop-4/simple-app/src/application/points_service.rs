@@ -0,0 +1,151 @@
+use std::sync::Arc;
+use crate::domain::{
+    PointsOperationRequest, PointsOperationResponse, PointLedger, EventType, UserRepository, UnitOfWork,
+    UnitOfWorkFactory, IdempotencyRecord,
+};
+use super::AccountLocks;
+
+#[derive(Clone)]
+pub struct PointsService {
+    user_repository: Arc<dyn UserRepository + Send + Sync>,
+    unit_of_work_factory: Arc<dyn UnitOfWorkFactory + Send + Sync>,
+    account_locks: AccountLocks,
+}
+
+impl PointsService {
+    pub fn new(
+        user_repository: Arc<dyn UserRepository + Send + Sync>,
+        unit_of_work_factory: Arc<dyn UnitOfWorkFactory + Send + Sync>,
+        account_locks: AccountLocks,
+    ) -> Self {
+        Self {
+            user_repository,
+            unit_of_work_factory,
+            account_locks,
+        }
+    }
+
+    pub async fn earn_points(&self, user_id: u32, credited_by: &str, request: PointsOperationRequest, idempotency_key: Option<String>) -> Result<PointsOperationResponse, String> {
+        self.post_points(user_id, EventType::Earn, Some(credited_by), request, idempotency_key).await
+    }
+
+    pub async fn redeem_points(&self, user_id: u32, request: PointsOperationRequest, idempotency_key: Option<String>) -> Result<PointsOperationResponse, String> {
+        self.post_points(user_id, EventType::Redeem, None, request, idempotency_key).await
+    }
+
+    async fn post_points(&self, user_id: u32, event_type: EventType, credited_by: Option<&str>, request: PointsOperationRequest, idempotency_key: Option<String>) -> Result<PointsOperationResponse, String> {
+        // Validate request
+        request.validate()?;
+        if let Some(key) = &idempotency_key {
+            IdempotencyRecord::validate_key(key)?;
+        }
+
+        // Check if user exists
+        let user = self.user_repository.get_user_by_id(user_id).await?
+            .ok_or("User not found".to_string())?;
+
+        let change = match event_type {
+            EventType::Redeem => -(request.amount as i32),
+            _ => request.amount as i32,
+        };
+        let reference = Some(request.reason.clone());
+        let mut metadata = serde_json::json!({
+            "source_reference": request.source_reference,
+            "metadata": request.metadata
+        });
+        // Points credited by hand record who did it, as adjustments do
+        if let Some(credited_by) = credited_by {
+            metadata["credited_by"] = serde_json::json!(credited_by);
+        }
+        let metadata = Some(metadata.to_string());
+
+        // Same lock as transfers and holds so balance_after builds on the last entry
+        let _account_lock = self.account_locks.lock(&[user_id]).await;
+        let uow = self.unit_of_work_factory.begin().await?;
+
+        // A retry with a known key gets the original entry back instead of posting the points twice
+        if let Some(key) = &idempotency_key
+            && let Some(entry) = uow.point_ledger().get_ledger_entry_by_idempotency_key(user_id, key).await?
+        {
+            if entry.event_type != event_type || entry.change != change || entry.reference != reference || entry.metadata != metadata {
+                return Err("Idempotency key already used with a different request".to_string());
+            }
+
+            // The tier bonus was posted with the original entry and is not repeated
+            return self.respond(uow, entry, None).await;
+        }
+
+        let point_ledger_repository = uow.point_ledger();
+        let balance = point_ledger_repository.get_current_balance(user_id).await?;
+        let tier_bonus = match event_type {
+            EventType::Earn => user.membership_level.earn_bonus(request.amount),
+            _ => 0,
+        };
+        let balance_after = match event_type {
+            // Held points are not spendable, exactly as for a transfer
+            EventType::Redeem => {
+                if point_ledger_repository.get_available_balance(user_id).await? < request.amount {
+                    return Err("Insufficient points".to_string());
+                }
+                balance - request.amount
+            }
+            _ => balance.checked_add(request.amount)
+                .filter(|balance_after| balance_after.checked_add(tier_bonus).is_some())
+                .ok_or("Balance would exceed the maximum number of points".to_string())?,
+        };
+
+        let entry = match &idempotency_key {
+            Some(key) => point_ledger_repository.create_keyed_ledger_entry(
+                user_id,
+                change,
+                balance_after,
+                event_type,
+                reference,
+                metadata,
+                key,
+            ).await?,
+            None => point_ledger_repository.create_ledger_entry(
+                user_id,
+                change,
+                balance_after,
+                event_type,
+                None,
+                reference,
+                metadata,
+            ).await?,
+        };
+
+        // The tier's earn multiplier is credited as its own entry so the earn entry matches the request
+        let tier_bonus_entry = if tier_bonus > 0 {
+            Some(point_ledger_repository.create_ledger_entry(
+                user_id,
+                tier_bonus as i32,
+                balance_after + tier_bonus,
+                EventType::Earn,
+                None,
+                Some(format!("{} tier bonus", user.membership_level)),
+                Some(serde_json::json!({
+                    "tier_bonus_for": entry.id,
+                    "tier": user.membership_level,
+                    "earn_multiplier_percent": user.membership_level.earn_multiplier_percent()
+                }).to_string()),
+            ).await?)
+        } else {
+            None
+        };
+
+        self.respond(uow, entry, tier_bonus_entry).await
+    }
+
+    async fn respond(&self, uow: Box<dyn UnitOfWork>, entry: PointLedger, tier_bonus: Option<PointLedger>) -> Result<PointsOperationResponse, String> {
+        let available_balance = uow.point_ledger().get_available_balance(entry.user_id).await?;
+
+        uow.commit().await?;
+
+        Ok(PointsOperationResponse {
+            entry,
+            tier_bonus,
+            available_balance,
+        })
+    }
+}
\ No newline at end of file
@@ -0,0 +1,279 @@
+use std::sync::Arc;
+use crate::domain::{
+    Transfer, TransferStatus, TransferType, EventType, PointLedger, Discrepancy, DiscrepancyKind, ReconciliationReport,
+    BrokenLink, LedgerChainReport, UserRepository, TransferRepository, PointLedgerRepository, Clock, GENESIS_HASH,
+};
+
+const PAGE_SIZE: u32 = 500;
+
+// Replays the ledger and checks it against itself, users.points and the transfers table. Read-only;
+// it reports what it finds and leaves fixing it to a person.
+#[derive(Clone)]
+pub struct ReconciliationService {
+    user_repository: Arc<dyn UserRepository + Send + Sync>,
+    transfer_repository: Arc<dyn TransferRepository + Send + Sync>,
+    point_ledger_repository: Arc<dyn PointLedgerRepository + Send + Sync>,
+    clock: Arc<dyn Clock + Send + Sync>,
+}
+
+impl ReconciliationService {
+    pub fn new(
+        user_repository: Arc<dyn UserRepository + Send + Sync>,
+        transfer_repository: Arc<dyn TransferRepository + Send + Sync>,
+        point_ledger_repository: Arc<dyn PointLedgerRepository + Send + Sync>,
+        clock: Arc<dyn Clock + Send + Sync>,
+    ) -> Self {
+        Self {
+            user_repository,
+            transfer_repository,
+            point_ledger_repository,
+            clock,
+        }
+    }
+
+    pub async fn reconcile(&self) -> Result<ReconciliationReport, String> {
+        let generated_at = self.clock.now();
+        let mut discrepancies = Vec::new();
+        let mut users_checked = 0;
+        let mut entries_checked = 0;
+        let mut transfers_checked = 0;
+
+        // Replay each user's ledger in the order their balances were built
+        let mut offset = 0;
+        loop {
+            let users = self.user_repository.list_users(Some(PAGE_SIZE as i64), Some(offset)).await?;
+            for user in &users {
+                let entries = self.point_ledger_repository.get_full_ledger(user.id).await?;
+                users_checked += 1;
+                entries_checked += entries.len() as u32;
+
+                let last_balance = check_balances(user.id, &entries, &mut discrepancies);
+                if user.points != last_balance {
+                    discrepancies.push(Discrepancy {
+                        kind: DiscrepancyKind::UserPointsMismatch,
+                        user_id: Some(user.id),
+                        transfer_id: None,
+                        entry_id: None,
+                        message: format!("users.points is {} but the ledger balance is {}", user.points, last_balance),
+                    });
+                }
+
+                let lot_total: i64 = self.point_ledger_repository.get_open_lots(user.id).await?
+                    .iter()
+                    .map(|lot| lot.remaining as i64)
+                    .sum();
+                if lot_total != last_balance {
+                    discrepancies.push(Discrepancy {
+                        kind: DiscrepancyKind::LotBalanceMismatch,
+                        user_id: Some(user.id),
+                        transfer_id: None,
+                        entry_id: None,
+                        message: format!("Point lots hold {} but the ledger balance is {}", lot_total, last_balance),
+                    });
+                }
+            }
+            if (users.len() as u32) < PAGE_SIZE {
+                break;
+            }
+            offset += PAGE_SIZE as i64;
+        }
+
+        // Every transfer must have exactly the legs its status calls for
+        let mut after_id = 0;
+        loop {
+            let transfers = self.transfer_repository.get_transfers_after(after_id, PAGE_SIZE).await?;
+            for transfer in &transfers {
+                let Some(transfer_id) = transfer.transfer_id else {
+                    continue;
+                };
+                transfers_checked += 1;
+                after_id = transfer_id;
+
+                let entries = self.point_ledger_repository.get_ledger_by_transfer_id(transfer_id).await?;
+                check_transfer_legs(transfer, &entries, &mut discrepancies);
+
+                if transfer.transfer_type == TransferType::Split
+                    && transfer.parent_transfer_id.is_none()
+                    && transfer.status == TransferStatus::Completed
+                {
+                    let children = self.transfer_repository.get_child_transfers(transfer_id).await?;
+                    let children_total: u64 = children.iter().map(|child| child.amount as u64).sum();
+                    if children_total != transfer.amount as u64 {
+                        discrepancies.push(Discrepancy {
+                            kind: DiscrepancyKind::SplitTotalMismatch,
+                            user_id: Some(transfer.from_user_id),
+                            transfer_id: Some(transfer_id),
+                            entry_id: None,
+                            message: format!("Split amount is {} but its children add up to {}", transfer.amount, children_total),
+                        });
+                    }
+                }
+            }
+            if (transfers.len() as u32) < PAGE_SIZE {
+                break;
+            }
+        }
+
+        Ok(ReconciliationReport {
+            generated_at,
+            users_checked,
+            entries_checked,
+            transfers_checked,
+            clean: discrepancies.is_empty(),
+            discrepancies,
+        })
+    }
+
+    // Recomputes every user's hash chain and stops at the first entry that does not match
+    pub async fn verify_chain(&self) -> Result<LedgerChainReport, String> {
+        let generated_at = self.clock.now();
+        let mut users_checked = 0;
+        let mut entries_checked = 0;
+        let mut first_broken_link = None;
+
+        let mut offset = 0;
+        'users: loop {
+            let users = self.user_repository.list_users(Some(PAGE_SIZE as i64), Some(offset)).await?;
+            for user in &users {
+                let entries = self.point_ledger_repository.get_full_ledger(user.id).await?;
+                users_checked += 1;
+
+                let mut prev_hash = GENESIS_HASH.to_string();
+                for (index, entry) in entries.iter().enumerate() {
+                    entries_checked += 1;
+                    if let Some(message) = check_link(entry, index as u32 + 1, &prev_hash) {
+                        first_broken_link = Some(BrokenLink {
+                            user_id: user.id,
+                            entry_id: entry.id,
+                            sequence: entry.sequence,
+                            message,
+                        });
+                        break 'users;
+                    }
+                    prev_hash = entry.hash.clone();
+                }
+            }
+            if (users.len() as u32) < PAGE_SIZE {
+                break;
+            }
+            offset += PAGE_SIZE as i64;
+        }
+
+        Ok(LedgerChainReport {
+            generated_at,
+            users_checked,
+            entries_checked,
+            intact: first_broken_link.is_none(),
+            first_broken_link,
+        })
+    }
+}
+
+// Why this entry does not follow from the one before it, if it does not
+fn check_link(entry: &PointLedger, expected_sequence: u32, prev_hash: &str) -> Option<String> {
+    if entry.hash.is_empty() {
+        return Some("Entry has no hash; it was not written through the ledger".to_string());
+    }
+    if entry.sequence != expected_sequence {
+        return Some(format!("Expected sequence {} but found {}; an entry is missing or out of place", expected_sequence, entry.sequence));
+    }
+    if entry.prev_hash != prev_hash {
+        return Some(format!("prevHash {} does not match the previous entry's hash {}", entry.prev_hash, prev_hash));
+    }
+    let expected_hash = entry.compute_hash(prev_hash);
+    if entry.hash != expected_hash {
+        return Some(format!("Stored hash {} does not match the entry's content (expected {})", entry.hash, expected_hash));
+    }
+    None
+}
+
+// Walks the entries oldest first and returns the last recorded balance
+fn check_balances(user_id: u32, entries: &[PointLedger], discrepancies: &mut Vec<Discrepancy>) -> i64 {
+    let mut previous: i64 = 0;
+    let mut replayed: i64 = 0;
+
+    for (index, entry) in entries.iter().enumerate() {
+        if entry.sequence as usize != index + 1 {
+            discrepancies.push(Discrepancy {
+                kind: DiscrepancyKind::SequenceGap,
+                user_id: Some(user_id),
+                transfer_id: entry.transfer_id,
+                entry_id: Some(entry.id),
+                message: format!("Entry is number {} in the ledger but has sequence {}", index + 1, entry.sequence),
+            });
+        }
+
+        let expected = previous + entry.change as i64;
+        if entry.balance_after as i64 != expected {
+            discrepancies.push(Discrepancy {
+                kind: DiscrepancyKind::BalanceMismatch,
+                user_id: Some(user_id),
+                transfer_id: entry.transfer_id,
+                entry_id: Some(entry.id),
+                message: format!("balanceAfter is {} but the previous balance {} plus change {} is {}", entry.balance_after, previous, entry.change, expected),
+            });
+        }
+
+        replayed += entry.change as i64;
+        if replayed < 0 {
+            discrepancies.push(Discrepancy {
+                kind: DiscrepancyKind::NegativeBalance,
+                user_id: Some(user_id),
+                transfer_id: entry.transfer_id,
+                entry_id: Some(entry.id),
+                message: format!("Balance drops to {} after this entry", replayed),
+            });
+        }
+
+        previous = entry.balance_after as i64;
+    }
+
+    previous
+}
+
+fn check_transfer_legs(transfer: &Transfer, entries: &[PointLedger], discrepancies: &mut Vec<Discrepancy>) {
+    let amount = transfer.amount as i32;
+    let mut expected = Vec::new();
+
+    if matches!(transfer.status, TransferStatus::Completed | TransferStatus::Reversed) {
+        if transfer.parent_transfer_id.is_some() {
+            // A split child is credited here; its debit is on the parent
+            expected.push((transfer.to_user_id, amount, EventType::TransferIn));
+        } else if transfer.transfer_type == TransferType::Split {
+            expected.push((transfer.from_user_id, -amount, EventType::TransferOut));
+        } else {
+            expected.push((transfer.from_user_id, -amount, EventType::TransferOut));
+            expected.push((transfer.to_user_id, amount, EventType::TransferIn));
+        }
+    }
+    if transfer.status == TransferStatus::Reversed {
+        expected.push((transfer.to_user_id, -amount, EventType::TransferOut));
+        expected.push((transfer.from_user_id, amount, EventType::TransferIn));
+    }
+
+    let mut actual: Vec<_> = entries.iter()
+        .map(|entry| (entry.user_id, entry.change, entry.event_type))
+        .collect();
+    let key = |leg: &(u32, i32, EventType)| (leg.0, leg.1, leg.2.to_string());
+    expected.sort_by_key(key);
+    actual.sort_by_key(key);
+
+    if expected != actual {
+        let describe = |legs: &[(u32, i32, EventType)]| legs.iter()
+            .map(|(user_id, change, event_type)| format!("{} {:+} for user {}", event_type, change, user_id))
+            .collect::<Vec<_>>()
+            .join(", ");
+        discrepancies.push(Discrepancy {
+            kind: DiscrepancyKind::TransferLegMismatch,
+            user_id: Some(transfer.from_user_id),
+            transfer_id: transfer.transfer_id,
+            entry_id: None,
+            message: format!(
+                "{} transfer should have [{}] but has [{}]",
+                transfer.status,
+                describe(&expected),
+                describe(&actual),
+            ),
+        });
+    }
+}
\ No newline at end of file
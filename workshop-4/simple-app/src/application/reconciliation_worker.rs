@@ -0,0 +1,39 @@
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use super::ReconciliationService;
+
+// Background loop that reconciles the ledger on a fixed interval and logs what it finds
+#[derive(Clone)]
+pub struct ReconciliationWorker {
+    reconciliation_service: ReconciliationService,
+    interval: Duration,
+}
+
+impl ReconciliationWorker {
+    pub fn new(reconciliation_service: ReconciliationService, interval: Duration) -> Self {
+        Self {
+            reconciliation_service,
+            interval,
+        }
+    }
+
+    pub fn spawn(self) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(self.interval).await;
+                match self.run_once().await {
+                    Ok(0) => {}
+                    Ok(found) => eprintln!("⚠️  Ledger reconciliation found {} discrepancies; see GET /admin/reconciliation", found),
+                    Err(e) => eprintln!("⚠️  Reconciliation worker failed: {}", e),
+                }
+            }
+        })
+    }
+
+    // Runs one reconciliation and returns how many discrepancies it found
+    pub async fn run_once(&self) -> Result<usize, String> {
+        let report = self.reconciliation_service.reconcile().await?;
+
+        Ok(report.discrepancies.len())
+    }
+}
\ No newline at end of file
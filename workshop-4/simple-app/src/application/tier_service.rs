@@ -0,0 +1,292 @@
+use std::sync::Arc;
+use chrono::{DateTime, Months, Utc};
+use crate::domain::{
+    MembershipTier, TierChange, TierChangeReason, TierBenefits, TierListResponse, UserTierStatus, SetTierRequest,
+    TierHistoryResponse, TierEvaluationReport, User, UserRepository, PointLedgerRepository, TransferLimitRepository,
+    TierRepository, UnitOfWorkFactory, Clock, TIER_QUALIFICATION_MONTHS,
+};
+
+const PAGE_SIZE: i64 = 100;
+
+// Moves members between tiers on the points they earned over the qualification window. Promotions happen
+// as soon as a member qualifies; a tier is kept for a full window after it was reached before it can drop.
+#[derive(Clone)]
+pub struct TierService {
+    user_repository: Arc<dyn UserRepository + Send + Sync>,
+    point_ledger_repository: Arc<dyn PointLedgerRepository + Send + Sync>,
+    transfer_limit_repository: Arc<dyn TransferLimitRepository + Send + Sync>,
+    tier_repository: Arc<dyn TierRepository + Send + Sync>,
+    unit_of_work_factory: Arc<dyn UnitOfWorkFactory + Send + Sync>,
+    clock: Arc<dyn Clock + Send + Sync>,
+}
+
+impl TierService {
+    pub fn new(
+        user_repository: Arc<dyn UserRepository + Send + Sync>,
+        point_ledger_repository: Arc<dyn PointLedgerRepository + Send + Sync>,
+        transfer_limit_repository: Arc<dyn TransferLimitRepository + Send + Sync>,
+        tier_repository: Arc<dyn TierRepository + Send + Sync>,
+        unit_of_work_factory: Arc<dyn UnitOfWorkFactory + Send + Sync>,
+        clock: Arc<dyn Clock + Send + Sync>,
+    ) -> Self {
+        Self {
+            user_repository,
+            point_ledger_repository,
+            transfer_limit_repository,
+            tier_repository,
+            unit_of_work_factory,
+            clock,
+        }
+    }
+
+    pub async fn list_tiers(&self) -> Result<TierListResponse, String> {
+        let mut data = Vec::with_capacity(MembershipTier::ALL.len());
+        for tier in MembershipTier::ALL {
+            data.push(self.benefits(tier).await?);
+        }
+
+        Ok(TierListResponse {
+            qualification_months: TIER_QUALIFICATION_MONTHS,
+            data,
+        })
+    }
+
+    pub async fn get_user_tier(&self, user_id: u32) -> Result<UserTierStatus, String> {
+        let user = self.user_repository.get_user_by_id(user_id).await?
+            .ok_or("User not found".to_string())?;
+
+        let now = self.clock.now();
+        let points_earned = self.point_ledger_repository.get_points_earned(user_id, window_start(now)?).await?;
+        let tier_since = self.tier_since(&user).await?;
+        let next_tier = user.membership_level.next();
+
+        Ok(UserTierStatus {
+            user_id,
+            tier: user.membership_level,
+            tier_since,
+            protected_until: window_end(tier_since)?,
+            points_earned,
+            qualifying_tier: MembershipTier::for_points_earned(points_earned),
+            next_tier,
+            points_to_next_tier: next_tier.map(|tier| tier.min_points_earned().saturating_sub(points_earned)),
+            benefits: self.benefits(user.membership_level).await?,
+        })
+    }
+
+    pub async fn get_tier_history(&self, user_id: u32) -> Result<TierHistoryResponse, String> {
+        // Check if user exists
+        let _user = self.user_repository.get_user_by_id(user_id).await?
+            .ok_or("User not found".to_string())?;
+
+        let data = self.tier_repository.get_tier_history(user_id).await?;
+
+        Ok(TierHistoryResponse { user_id, data })
+    }
+
+    // A manual change restarts the tier's protection, so the evaluation will not undo it straight away
+    pub async fn set_tier(&self, user_id: u32, changed_by: &str, request: SetTierRequest) -> Result<TierChange, String> {
+        request.validate()?;
+
+        let now = self.clock.now();
+        let uow = self.unit_of_work_factory.begin().await?;
+
+        let from_tier = uow.tiers().get_user_tier(user_id).await?
+            .ok_or("User not found".to_string())?;
+        if from_tier == request.tier {
+            return Err(format!("User is already {}", from_tier));
+        }
+
+        let points_earned = uow.point_ledger().get_points_earned(user_id, window_start(now)?).await?;
+        let change = uow.tiers().change_tier(
+            user_id,
+            from_tier,
+            request.tier,
+            TierChangeReason::Manual,
+            points_earned,
+            Some(changed_by.to_string()),
+            request.note,
+            now,
+        ).await?;
+
+        uow.commit().await?;
+
+        Ok(change)
+    }
+
+    // Re-evaluates every member and records a tier_history row for each promotion and demotion
+    pub async fn evaluate_tiers(&self) -> Result<TierEvaluationReport, String> {
+        let now = self.clock.now();
+        let mut report = TierEvaluationReport {
+            generated_at: now,
+            users_evaluated: 0,
+            promoted: 0,
+            demoted: 0,
+            changes: Vec::new(),
+        };
+
+        let mut offset = 0;
+        loop {
+            let users = self.user_repository.list_users(Some(PAGE_SIZE), Some(offset)).await?;
+            for user in &users {
+                report.users_evaluated += 1;
+                if let Some(change) = self.evaluate_user(user, now).await? {
+                    match change.reason {
+                        TierChangeReason::Promotion => report.promoted += 1,
+                        _ => report.demoted += 1,
+                    }
+                    report.changes.push(change);
+                }
+            }
+            if (users.len() as i64) < PAGE_SIZE {
+                break;
+            }
+            offset += PAGE_SIZE;
+        }
+
+        Ok(report)
+    }
+
+    async fn evaluate_user(&self, user: &User, now: DateTime<Utc>) -> Result<Option<TierChange>, String> {
+        let uow = self.unit_of_work_factory.begin().await?;
+
+        // Re-read inside the transaction; the user may have been deleted or moved by hand since the listing
+        let Some(tier) = uow.tiers().get_user_tier(user.id).await? else {
+            uow.rollback().await?;
+            return Ok(None);
+        };
+        let points_earned = uow.point_ledger().get_points_earned(user.id, window_start(now)?).await?;
+        let qualifying_tier = MembershipTier::for_points_earned(points_earned);
+
+        let tier_since = uow.tiers().get_last_tier_change(user.id).await?
+            .map(|change| change.created_at)
+            .unwrap_or(user.member_since);
+        let reason = if qualifying_tier > tier {
+            TierChangeReason::Promotion
+        } else if qualifying_tier < tier && window_end(tier_since)? <= now {
+            TierChangeReason::Demotion
+        } else {
+            uow.rollback().await?;
+            return Ok(None);
+        };
+
+        let change = uow.tiers().change_tier(user.id, tier, qualifying_tier, reason, points_earned, None, None, now).await?;
+
+        uow.commit().await?;
+
+        Ok(Some(change))
+    }
+
+    async fn tier_since(&self, user: &User) -> Result<DateTime<Utc>, String> {
+        Ok(self.tier_repository.get_last_tier_change(user.id).await?
+            .map(|change| change.created_at)
+            .unwrap_or(user.member_since))
+    }
+
+    async fn benefits(&self, tier: MembershipTier) -> Result<TierBenefits, String> {
+        Ok(TierBenefits {
+            tier,
+            min_points_earned: tier.min_points_earned(),
+            earn_multiplier_percent: tier.earn_multiplier_percent(),
+            transfer_limits: self.transfer_limit_repository.get_limits(&tier.to_string()).await?,
+        })
+    }
+}
+
+// Points earned at or after this count towards the tier
+fn window_start(now: DateTime<Utc>) -> Result<DateTime<Utc>, String> {
+    now.checked_sub_months(Months::new(TIER_QUALIFICATION_MONTHS))
+        .ok_or("Date out of range".to_string())
+}
+
+// A tier reached at `since` cannot drop before this
+fn window_end(since: DateTime<Utc>) -> Result<DateTime<Utc>, String> {
+    since.checked_add_months(Months::new(TIER_QUALIFICATION_MONTHS))
+        .ok_or("Date out of range".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use chrono::{Duration, Months, Utc};
+    use sqlx::SqlitePool;
+    use crate::domain::{Clock, MembershipTier, PointsOperationRequest, TierChangeReason};
+    use crate::infrastructure::{
+        SqliteUserRepository, SqlitePointLedgerRepository, SqliteTransferLimitRepository, SqliteTierRepository,
+        SqliteUnitOfWorkFactory,
+    };
+    use crate::infrastructure::test_support::{test_pool, FakeClock};
+    use crate::application::{AccountLocks, PointsService};
+    use super::TierService;
+
+    fn service(pool: &SqlitePool, clock: Arc<FakeClock>) -> TierService {
+        TierService::new(
+            Arc::new(SqliteUserRepository::new(pool.clone())),
+            Arc::new(SqlitePointLedgerRepository::new(pool.clone())),
+            Arc::new(SqliteTransferLimitRepository::new(pool.clone())),
+            Arc::new(SqliteTierRepository::new(pool.clone())),
+            Arc::new(SqliteUnitOfWorkFactory::new(pool.clone())),
+            clock,
+        )
+    }
+
+    #[tokio::test]
+    async fn tier_bonuses_do_not_count_towards_the_next_tier() {
+        let pool = test_pool().await;
+        let tiers = service(&pool, Arc::new(FakeClock::new(Utc::now())));
+        let points = PointsService::new(
+            Arc::new(SqliteUserRepository::new(pool.clone())),
+            Arc::new(SqliteUnitOfWorkFactory::new(pool.clone())),
+            AccountLocks::new(),
+        );
+        let earn = |amount| PointsOperationRequest { amount, reason: "Purchase".to_string(), source_reference: None, metadata: None };
+
+        // Jane is Silver
+        let response = points.earn_points(2, "admin@example.com", earn(4_000), None).await.unwrap();
+        // 4000 earned and 1000 Silver bonus on top would be enough for Gold if the bonus counted
+        assert_eq!(response.tier_bonus.unwrap().change, 1_000);
+
+        let report = tiers.evaluate_tiers().await.unwrap();
+        assert!(report.changes.iter().all(|change| change.user_id != 2), "{:?}", report.changes);
+        let status = tiers.get_user_tier(2).await.unwrap();
+        assert_eq!(status.points_earned, 4_000);
+        assert_eq!(status.points_to_next_tier, Some(1_000));
+
+        points.earn_points(2, "admin@example.com", earn(1_000), None).await.unwrap();
+        let report = tiers.evaluate_tiers().await.unwrap();
+        let change = report.changes.iter().find(|change| change.user_id == 2).unwrap();
+        assert_eq!(change.reason, TierChangeReason::Promotion);
+        assert_eq!(change.to_tier, MembershipTier::Gold);
+    }
+
+    #[tokio::test]
+    async fn a_tier_is_kept_for_a_full_window_before_it_drops() {
+        let pool = test_pool().await;
+        let clock = Arc::new(FakeClock::new(Utc::now()));
+        let tiers = service(&pool, clock.clone());
+
+        // John (Gold) and Jane (Silver) have earned nothing, but reached their tiers only now
+        let report = tiers.evaluate_tiers().await.unwrap();
+        assert_eq!(report.users_evaluated, 3);
+        assert!(report.changes.is_empty(), "{:?}", report.changes);
+        clock.advance(Duration::days(364));
+        assert!(tiers.evaluate_tiers().await.unwrap().changes.is_empty());
+
+        clock.advance(Duration::days(2));
+        let report = tiers.evaluate_tiers().await.unwrap();
+        assert_eq!((report.promoted, report.demoted), (0, 2));
+        let mut demotions: Vec<_> = report.changes.iter()
+            .map(|change| (change.user_id, change.from_tier, change.to_tier, change.reason))
+            .collect();
+        demotions.sort_by_key(|demotion| demotion.0);
+        assert_eq!(demotions, [
+            (1, MembershipTier::Gold, MembershipTier::Bronze, TierChangeReason::Demotion),
+            (2, MembershipTier::Silver, MembershipTier::Bronze, TierChangeReason::Demotion),
+        ]);
+
+        let status = tiers.get_user_tier(1).await.unwrap();
+        assert_eq!(status.tier, MembershipTier::Bronze);
+        assert_eq!(status.tier_since, clock.now());
+        assert_eq!(status.protected_until, clock.now() + Months::new(12));
+        assert_eq!(tiers.get_tier_history(1).await.unwrap().data.len(), 1);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,44 @@
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use crate::domain::TierChange;
+use super::TierService;
+
+// Background loop that re-evaluates membership tiers once a day and logs every tier-change event
+#[derive(Clone)]
+pub struct TierWorker {
+    tier_service: TierService,
+    interval: Duration,
+}
+
+impl TierWorker {
+    pub fn new(tier_service: TierService) -> Self {
+        Self {
+            tier_service,
+            interval: Duration::from_secs(24 * 3600),
+        }
+    }
+
+    pub fn spawn(self) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            loop {
+                match self.run_once().await {
+                    Ok(changes) => {
+                        for change in changes {
+                            println!(
+                                "🏅 Tier {}: user {} moved from {} to {} ({} points earned)",
+                                change.reason, change.user_id, change.from_tier, change.to_tier, change.points_earned
+                            );
+                        }
+                    }
+                    Err(e) => eprintln!("⚠️  Tier worker failed to evaluate tiers: {}", e),
+                }
+                tokio::time::sleep(self.interval).await;
+            }
+        })
+    }
+
+    // Evaluates every member and returns the promotions and demotions it made
+    pub async fn run_once(&self) -> Result<Vec<TierChange>, String> {
+        Ok(self.tier_service.evaluate_tiers().await?.changes)
+    }
+}
\ No newline at end of file
@@ -1,33 +1,97 @@
 use std::sync::Arc;
-use chrono::Utc;
+use chrono::Duration;
 use crate::domain::{
-    Transfer, TransferRepository, CreateTransferRequest, TransferCreateResponse, TransferGetResponse, TransferListResponse,
-    PointLedgerRepository, EventType, UserRepository,
+    Transfer, TransferStatus, TransferRepository, CreateTransferRequest, TransferCreateResponse, TransferGetResponse, TransferListResponse,
+    EventType, UserRepository, UnitOfWork, UnitOfWorkFactory, IdempotencyRecord,
+    ReverseTransferRequest, TransferReverseResponse, CancelTransferRequest, TransferCancelResponse, Clock,
+    TransferType, CreateSplitTransferRequest, TransferSplitResponse,
+    TransferLimits, TransferLimitRepository, UpdateTransferLimitsRequest, MembershipTier,
+    AcceptTransferRequest, DeclineTransferRequest, TransferAcceptanceResponse, TransferMandate, MandateRun,
 };
+use super::AccountLocks;
+use super::transfer_worker::is_transient_error;
 
 #[derive(Clone)]
 pub struct TransferService {
     transfer_repository: Arc<dyn TransferRepository + Send + Sync>,
-    point_ledger_repository: Arc<dyn PointLedgerRepository + Send + Sync>,
     user_repository: Arc<dyn UserRepository + Send + Sync>,
+    transfer_limit_repository: Arc<dyn TransferLimitRepository + Send + Sync>,
+    unit_of_work_factory: Arc<dyn UnitOfWorkFactory + Send + Sync>,
+    clock: Arc<dyn Clock + Send + Sync>,
+    account_locks: AccountLocks,
+    idempotency_retention: Duration,
+    async_processing: bool,
+    acceptance_timeout: Option<Duration>,
 }
 
 impl TransferService {
     pub fn new(
         transfer_repository: Arc<dyn TransferRepository + Send + Sync>,
-        point_ledger_repository: Arc<dyn PointLedgerRepository + Send + Sync>,
         user_repository: Arc<dyn UserRepository + Send + Sync>,
+        transfer_limit_repository: Arc<dyn TransferLimitRepository + Send + Sync>,
+        unit_of_work_factory: Arc<dyn UnitOfWorkFactory + Send + Sync>,
+        clock: Arc<dyn Clock + Send + Sync>,
     ) -> Self {
         Self {
             transfer_repository,
-            point_ledger_repository,
             user_repository,
+            transfer_limit_repository,
+            unit_of_work_factory,
+            clock,
+            account_locks: AccountLocks::new(),
+            idempotency_retention: Duration::hours(24),
+            async_processing: false,
+            acceptance_timeout: None,
         }
     }
 
-    pub async fn create_transfer(&self, request: CreateTransferRequest) -> Result<TransferCreateResponse, String> {
+    // Shared with other services that debit accounts, so their ledger writes queue behind transfers
+    pub fn account_locks(&self) -> AccountLocks {
+        self.account_locks.clone()
+    }
+
+    pub fn with_idempotency_retention(mut self, retention: Duration) -> Self {
+        self.idempotency_retention = retention;
+        self
+    }
+
+    // Leave new transfers pending for the background TransferWorker instead of processing them inline
+    pub fn with_async_processing(mut self, async_processing: bool) -> Self {
+        self.async_processing = async_processing;
+        self
+    }
+
+    // Hold transfers to a recipient the sender has never paid until the recipient accepts them,
+    // cancelling them if nobody answers within `timeout` (None turns acceptance off)
+    pub fn with_recipient_acceptance(mut self, timeout: Option<Duration>) -> Self {
+        self.acceptance_timeout = timeout;
+        self
+    }
+
+    pub async fn create_transfer(&self, request: CreateTransferRequest, idempotency_key: Option<String>) -> Result<TransferCreateResponse, String> {
+        self.create_transfer_for_run(request, idempotency_key, None).await
+    }
+
+    // The transfer for the mandate's next occurrence. The run is recorded with the transfer, so an occurrence
+    // retried after its idempotency key has expired still gets the original transfer back
+    pub async fn create_mandate_transfer(&self, request: CreateTransferRequest, mandate: &TransferMandate) -> Result<TransferCreateResponse, String> {
+        let mandate_run = Some((mandate.id, mandate.next_occurrence));
+        self.create_transfer_for_run(request, Some(mandate.run_idempotency_key()), mandate_run).await
+    }
+
+    async fn create_transfer_for_run(&self, request: CreateTransferRequest, idempotency_key: Option<String>, mandate_run: Option<(u32, u32)>) -> Result<TransferCreateResponse, String> {
         // Validate request
         request.validate()?;
+        if let Some(key) = &idempotency_key {
+            IdempotencyRecord::validate_key(key)?;
+        }
+        if let Some(execute_at) = request.execute_at
+            && execute_at <= self.clock.now()
+        {
+            return Err("executeAt must be in the future".to_string());
+        }
+        let fingerprint = serde_json::to_string(&request)
+            .map_err(|e| format!("Failed to fingerprint request: {}", e))?;
 
         // Check if users exist
         let from_user = self.user_repository.get_user_by_id(request.from_user_id).await?
@@ -36,56 +100,541 @@ impl TransferService {
         let _to_user = self.user_repository.get_user_by_id(request.to_user_id).await?
             .ok_or("To user not found".to_string())?;
 
-        // Check if sender has enough points
-        let current_balance = self.point_ledger_repository.get_current_balance(request.from_user_id).await?;
-        if current_balance < request.amount {
-            return Err("Insufficient points".to_string());
+        let limits = self.transfer_limit_repository.get_limits(&from_user.membership_level.to_string()).await?;
+
+        // Serialize transfers touching either account so each balance_after builds on the last one
+        let _account_lock = self.account_locks.lock(&[request.from_user_id, request.to_user_id]).await;
+
+        // Balance check, transfer row, ledger entries and status all commit together
+        let uow = self.unit_of_work_factory.begin().await?;
+
+        // A mandate occurrence that already ran gets its transfer back, even if the mandate was edited since
+        if let Some(transfer) = self.find_mandate_run_transfer(uow.as_ref(), mandate_run).await? {
+            uow.commit().await?;
+            return Ok(TransferCreateResponse { transfer });
         }
 
-        // Create transfer (initially pending)
-        let mut transfer = self.transfer_repository.create_transfer(request.clone()).await?;
+        // A retry with a known key gets the original transfer back instead of a second one
+        if let Some(key) = &idempotency_key
+            && let Some(transfer) = self.find_replayed_transfer(uow.as_ref(), request.from_user_id, key, &fingerprint).await?
+        {
+            uow.commit().await?;
+            return Ok(TransferCreateResponse { transfer });
+        }
 
-        // Process the transfer immediately (in a real system, this might be async)
-        match self.process_transfer(&transfer).await {
-            Ok(_) => {
-                // Update transfer status to completed
-                let completed_at = Some(Utc::now().to_rfc3339());
-                self.transfer_repository.update_transfer_status(
-                    &transfer.idem_key,
-                    "completed",
-                    completed_at.clone(),
-                    None,
-                ).await?;
-
-                // Update transfer object
-                transfer.status = crate::domain::TransferStatus::Completed;
-                transfer.completed_at = completed_at.map(|s| s.parse().unwrap());
-                transfer.updated_at = Utc::now();
+        self.check_transfer_limits(uow.as_ref(), request.from_user_id, limits.as_ref(), request.amount).await?;
+
+        // Check if sender has enough points (scheduled transfers are checked when they run)
+        let scheduled = request.execute_at.is_some();
+        if !scheduled {
+            let available_balance = uow.point_ledger().get_available_balance(request.from_user_id).await?;
+            if available_balance < request.amount {
+                return Err("Insufficient points".to_string());
+            }
+        }
+
+        // The first transfer to a recipient waits for them to accept it; the points stay held meanwhile
+        let accept_by = match self.acceptance_timeout {
+            Some(timeout) if !scheduled && !uow.transfers().has_received_from(request.from_user_id, request.to_user_id).await? => {
+                Some(self.clock.now() + timeout)
             }
+            _ => None,
+        };
+
+        // Create transfer (initially pending)
+        let mut transfer = match accept_by {
+            Some(accept_by) => uow.transfers().create_transfer_awaiting_acceptance(request.clone(), accept_by, self.clock.now()).await?,
+            None => uow.transfers().create_transfer(request.clone(), self.clock.now()).await?,
+        };
+        self.remember_idempotency_key(uow.as_ref(), idempotency_key.as_deref(), &fingerprint, &transfer).await?;
+        self.remember_mandate_run(uow.as_ref(), mandate_run, &transfer).await?;
+
+        // Scheduled transfers, and every transfer in async mode, are left for the background worker;
+        // transfers awaiting acceptance are left for the recipient. The client polls GET /transfers/{id}
+        if scheduled || self.async_processing || accept_by.is_some() {
+            uow.commit().await?;
+            return Ok(TransferCreateResponse { transfer });
+        }
+
+        // Process the transfer immediately
+        match self.execute_transfer(uow.as_ref(), &mut transfer).await {
+            Ok(_) => uow.commit().await?,
             Err(e) => {
-                // Update transfer status to failed
-                self.transfer_repository.update_transfer_status(
-                    &transfer.idem_key,
-                    "failed",
-                    None,
-                    Some(e.clone()),
-                ).await?;
-
-                // Update transfer object
-                transfer.status = crate::domain::TransferStatus::Failed;
-                transfer.fail_reason = Some(e);
-                transfer.updated_at = Utc::now();
+                // Discard any partially posted ledger entries before recording the failure
+                uow.rollback().await?;
+                transfer = self.record_failed_transfer(request, e, idempotency_key.as_deref(), &fingerprint, mandate_run).await?;
             }
         }
 
         Ok(TransferCreateResponse { transfer })
     }
 
+    // One debit from the sender and one credit per recipient, all committed together. Always executed
+    // inline, even in async mode, because the legs only make sense as a whole.
+    pub async fn create_split_transfer(&self, request: CreateSplitTransferRequest, idempotency_key: Option<String>) -> Result<TransferSplitResponse, String> {
+        // Validate request
+        request.validate()?;
+        if let Some(key) = &idempotency_key {
+            IdempotencyRecord::validate_key(key)?;
+        }
+        let total_amount = request.total_amount()
+            .ok_or("Total amount is too large".to_string())?;
+        let fingerprint = serde_json::to_string(&request)
+            .map_err(|e| format!("Failed to fingerprint request: {}", e))?;
+
+        // Check if users exist
+        let from_user = self.user_repository.get_user_by_id(request.from_user_id).await?
+            .ok_or("From user not found".to_string())?;
+        for recipient in &request.recipients {
+            let _to_user = self.user_repository.get_user_by_id(recipient.to_user_id).await?
+                .ok_or(format!("To user {} not found", recipient.to_user_id))?;
+        }
+
+        let limits = self.transfer_limit_repository.get_limits(&from_user.membership_level.to_string()).await?;
+
+        let mut user_ids: Vec<u32> = request.recipients.iter().map(|recipient| recipient.to_user_id).collect();
+        user_ids.push(request.from_user_id);
+        let _account_lock = self.account_locks.lock(&user_ids).await;
+
+        let uow = self.unit_of_work_factory.begin().await?;
+
+        if let Some(key) = &idempotency_key
+            && let Some(transfer) = self.find_replayed_transfer(uow.as_ref(), request.from_user_id, key, &fingerprint).await?
+        {
+            let transfer_id = transfer.transfer_id
+                .ok_or("Transfer has no internal id".to_string())?;
+            let splits = uow.transfers().get_child_transfers(transfer_id).await?;
+            uow.commit().await?;
+            return Ok(TransferSplitResponse { transfer, splits });
+        }
+
+        // Limits see the split as one transfer of the total
+        self.check_transfer_limits(uow.as_ref(), request.from_user_id, limits.as_ref(), total_amount).await?;
+
+        // The total is checked against the balance once, not leg by leg
+        let point_ledger_repository = uow.point_ledger();
+        let from_balance = point_ledger_repository.get_current_balance(request.from_user_id).await?;
+        if point_ledger_repository.get_available_balance(request.from_user_id).await? < total_amount {
+            return Err("Insufficient points".to_string());
+        }
+
+        let (mut transfer, mut splits) = uow.transfers().create_split_transfer(request, self.clock.now()).await?;
+        self.remember_idempotency_key(uow.as_ref(), idempotency_key.as_deref(), &fingerprint, &transfer).await?;
+
+        for leg in std::iter::once(&transfer).chain(splits.iter()) {
+            uow.transfers().update_transfer_status(&leg.idem_key, TransferStatus::Pending, TransferStatus::Processing, None, None, self.clock.now()).await?;
+        }
+
+        // One transfer_out row for the whole amount against the parent...
+        point_ledger_repository.create_ledger_entry(
+            transfer.from_user_id,
+            -(total_amount as i32),
+            from_balance - total_amount,
+            EventType::TransferOut,
+            transfer.transfer_id,
+            Some(format!("Split transfer to {} users", splits.len())),
+            Some(serde_json::json!({
+                "transfer_id": transfer.transfer_id,
+                "idem_key": transfer.idem_key,
+                "split": splits.iter().map(|split| split.transfer_id).collect::<Vec<_>>(),
+                "note": transfer.note
+            }).to_string()),
+        ).await?;
+
+        // ...and one transfer_in row per recipient against its child transfer
+        for split in &splits {
+            let to_balance = point_ledger_repository.get_current_balance(split.to_user_id).await?;
+            point_ledger_repository.create_ledger_entry(
+                split.to_user_id,
+                split.amount as i32,
+                to_balance + split.amount,
+                EventType::TransferIn,
+                split.transfer_id,
+                Some(format!("Transfer from user {}", split.from_user_id)),
+                Some(serde_json::json!({
+                    "transfer_id": split.transfer_id,
+                    "idem_key": split.idem_key,
+                    "parent_transfer_id": split.parent_transfer_id,
+                    "note": split.note
+                }).to_string()),
+            ).await?;
+        }
+
+        let completed_at = self.clock.now();
+        for leg in std::iter::once(&mut transfer).chain(splits.iter_mut()) {
+            uow.transfers().update_transfer_status(
+                &leg.idem_key,
+                TransferStatus::Processing,
+                TransferStatus::Completed,
+                Some(completed_at.to_rfc3339()),
+                None,
+                completed_at,
+            ).await?;
+
+            // Update transfer object
+            leg.status = TransferStatus::Completed;
+            leg.completed_at = Some(completed_at);
+            leg.updated_at = completed_at;
+        }
+
+        uow.commit().await?;
+
+        Ok(TransferSplitResponse { transfer, splits })
+    }
+
+    // Creates and executes every request inside one unit of work, so either all of them complete or none do.
+    // The inner error names the request that failed and why; the outer error is an infrastructure failure
+    // (e.g. a locked database) that is worth retrying. Each request carries its own Idempotency-Key, so
+    // a retry after a crash replays the transfers that were already committed.
+    pub async fn create_transfers_atomically(&self, requests: Vec<(CreateTransferRequest, String)>) -> Result<Result<Vec<Transfer>, (usize, String)>, String> {
+        // Validate every request before touching the ledger
+        let mut limits = Vec::with_capacity(requests.len());
+        for (index, (request, key)) in requests.iter().enumerate() {
+            if let Err(e) = request.validate().and_then(|_| IdempotencyRecord::validate_key(key)) {
+                return Ok(Err((index, e)));
+            }
+            if request.execute_at.is_some() {
+                return Ok(Err((index, "Scheduled transfers cannot be part of an all-or-nothing batch".to_string())));
+            }
+            let Some(from_user) = self.user_repository.get_user_by_id(request.from_user_id).await? else {
+                return Ok(Err((index, "From user not found".to_string())));
+            };
+            if self.user_repository.get_user_by_id(request.to_user_id).await?.is_none() {
+                return Ok(Err((index, "To user not found".to_string())));
+            }
+            limits.push(self.transfer_limit_repository.get_limits(&from_user.membership_level.to_string()).await?);
+        }
+
+        let user_ids: Vec<u32> = requests.iter()
+            .flat_map(|(request, _)| [request.from_user_id, request.to_user_id])
+            .collect();
+        let _account_lock = self.account_locks.lock(&user_ids).await;
+
+        let uow = self.unit_of_work_factory.begin().await?;
+
+        let mut transfers = Vec::with_capacity(requests.len());
+        for (index, ((request, key), limits)) in requests.into_iter().zip(limits).enumerate() {
+            let fingerprint = serde_json::to_string(&request)
+                .map_err(|e| format!("Failed to fingerprint request: {}", e))?;
+
+            let outcome = match self.find_replayed_transfer(uow.as_ref(), request.from_user_id, &key, &fingerprint).await {
+                Ok(Some(transfer)) => Ok(transfer),
+                Ok(None) => self.create_and_execute(uow.as_ref(), request, limits.as_ref(), &key, &fingerprint).await,
+                Err(e) => Err(e),
+            };
+
+            match outcome {
+                Ok(transfer) => transfers.push(transfer),
+                Err(e) if is_transient_error(&e) => {
+                    uow.rollback().await?;
+                    return Err(e);
+                }
+                Err(e) => {
+                    uow.rollback().await?;
+                    return Ok(Err((index, e)));
+                }
+            }
+        }
+
+        uow.commit().await?;
+
+        Ok(Ok(transfers))
+    }
+
+    // Pending transfers that are due now (unscheduled, or scheduled at or before the clock's now)
+    pub async fn list_pending_transfers(&self, limit: u32) -> Result<Vec<Transfer>, String> {
+        self.transfer_repository.get_pending_transfers(self.clock.now(), limit).await
+    }
+
+    // Runs one pending transfer to completion. Transient database errors are returned so the caller
+    // can retry; business failures (e.g. insufficient points) are recorded on the transfer as failed.
+    pub async fn process_pending_transfer(&self, idem_key: &str) -> Result<Transfer, String> {
+        let transfer = self.transfer_repository.get_transfer_by_idem_key(idem_key).await?
+            .ok_or("Transfer not found".to_string())?;
+        if transfer.status != TransferStatus::Pending || transfer.is_awaiting_acceptance() {
+            return Ok(transfer);
+        }
+
+        let _account_lock = self.account_locks.lock(&[transfer.from_user_id, transfer.to_user_id]).await;
+        let uow = self.unit_of_work_factory.begin().await?;
+
+        // Re-read inside the transaction; another worker may have claimed it meanwhile
+        let mut transfer = uow.transfers().get_transfer_by_idem_key(idem_key).await?
+            .ok_or("Transfer not found".to_string())?;
+        let not_due = transfer.execute_at.is_some_and(|execute_at| execute_at > self.clock.now());
+        if transfer.status != TransferStatus::Pending || transfer.is_awaiting_acceptance() || not_due {
+            uow.rollback().await?;
+            return Ok(transfer);
+        }
+
+        match self.execute_transfer(uow.as_ref(), &mut transfer).await {
+            Ok(_) => {
+                uow.commit().await?;
+                Ok(transfer)
+            }
+            Err(e) if is_transient_error(&e) => {
+                uow.rollback().await?;
+                Err(e)
+            }
+            Err(e) => {
+                uow.rollback().await?;
+                self.fail_pending_transfer(transfer, e).await
+            }
+        }
+    }
+
     pub async fn get_transfer(&self, idem_key: &str) -> Result<TransferGetResponse, String> {
         let transfer = self.transfer_repository.get_transfer_by_idem_key(idem_key).await?
             .ok_or("Transfer not found".to_string())?;
 
-        Ok(TransferGetResponse { transfer })
+        let reversal = match transfer.transfer_id {
+            Some(transfer_id) => self.transfer_repository.get_transfer_reversal(transfer_id).await?,
+            None => None,
+        };
+
+        let splits = match (transfer.transfer_type, transfer.transfer_id) {
+            (TransferType::Split, Some(transfer_id)) => Some(self.transfer_repository.get_child_transfers(transfer_id).await?),
+            _ => None,
+        };
+
+        Ok(TransferGetResponse { transfer, reversal, splits })
+    }
+
+    pub async fn cancel_transfer(&self, idem_key: &str, request: CancelTransferRequest) -> Result<TransferCancelResponse, String> {
+        // Validate request
+        request.validate()?;
+
+        let uow = self.unit_of_work_factory.begin().await?;
+
+        let mut transfer = uow.transfers().get_transfer_by_idem_key(idem_key).await?
+            .ok_or("Transfer not found".to_string())?;
+        if transfer.from_user_id != request.cancelled_by {
+            return Err("Only the sender can cancel this transfer".to_string());
+        }
+        if !matches!(transfer.status, TransferStatus::Pending | TransferStatus::Processing) {
+            return Err(format!("Only pending or processing transfers can be cancelled (current status: {})", transfer.status));
+        }
+
+        // No ledger rows are touched: a cancelled transfer never moved any points
+        let fail_reason = format!("Cancelled by sender: {}", request.reason.trim());
+        let now = self.clock.now();
+        uow.transfers().update_transfer_status(
+            &transfer.idem_key,
+            transfer.status,
+            TransferStatus::Cancelled,
+            None,
+            Some(fail_reason.clone()),
+            now,
+        ).await?;
+
+        uow.commit().await?;
+
+        // Update transfer object
+        transfer.status = TransferStatus::Cancelled;
+        transfer.fail_reason = Some(fail_reason);
+        transfer.updated_at = now;
+
+        Ok(TransferCancelResponse { transfer })
+    }
+
+    // The recipient takes a transfer that was waiting for them; it is executed straight away
+    pub async fn accept_transfer(&self, idem_key: &str, request: AcceptTransferRequest) -> Result<TransferAcceptanceResponse, String> {
+        let transfer = self.transfer_repository.get_transfer_by_idem_key(idem_key).await?
+            .ok_or("Transfer not found".to_string())?;
+
+        let _account_lock = self.account_locks.lock(&[transfer.from_user_id, transfer.to_user_id]).await;
+        let uow = self.unit_of_work_factory.begin().await?;
+
+        // Re-read inside the transaction; the sender may have cancelled it meanwhile
+        let mut transfer = uow.transfers().get_transfer_by_idem_key(idem_key).await?
+            .ok_or("Transfer not found".to_string())?;
+        if transfer.to_user_id != request.accepted_by {
+            return Err("Only the recipient can accept this transfer".to_string());
+        }
+        if !transfer.is_awaiting_acceptance() {
+            return Err(format!("Only transfers awaiting acceptance can be accepted (current status: {})", transfer.status));
+        }
+        if transfer.accept_by.is_some_and(|accept_by| accept_by <= self.clock.now()) {
+            return Err("The acceptance window for this transfer has closed".to_string());
+        }
+
+        match self.execute_transfer(uow.as_ref(), &mut transfer).await {
+            Ok(_) => {
+                uow.commit().await?;
+                Ok(TransferAcceptanceResponse { transfer })
+            }
+            Err(e) if is_transient_error(&e) => {
+                uow.rollback().await?;
+                Err(e)
+            }
+            Err(e) => {
+                uow.rollback().await?;
+                let transfer = self.fail_pending_transfer(transfer, e).await?;
+                Ok(TransferAcceptanceResponse { transfer })
+            }
+        }
+    }
+
+    // The recipient turns a waiting transfer down; no ledger rows are touched and the sender's hold is released
+    pub async fn decline_transfer(&self, idem_key: &str, request: DeclineTransferRequest) -> Result<TransferAcceptanceResponse, String> {
+        // Validate request
+        request.validate()?;
+
+        let uow = self.unit_of_work_factory.begin().await?;
+
+        let mut transfer = uow.transfers().get_transfer_by_idem_key(idem_key).await?
+            .ok_or("Transfer not found".to_string())?;
+        if transfer.to_user_id != request.declined_by {
+            return Err("Only the recipient can decline this transfer".to_string());
+        }
+        if !transfer.is_awaiting_acceptance() {
+            return Err(format!("Only transfers awaiting acceptance can be declined (current status: {})", transfer.status));
+        }
+
+        let fail_reason = match request.reason.as_deref().map(str::trim) {
+            Some(reason) if !reason.is_empty() => format!("Declined by recipient: {}", reason),
+            _ => "Declined by recipient".to_string(),
+        };
+        let now = self.clock.now();
+        uow.transfers().update_transfer_status(
+            &transfer.idem_key,
+            TransferStatus::Pending,
+            TransferStatus::Cancelled,
+            None,
+            Some(fail_reason.clone()),
+            now,
+        ).await?;
+
+        uow.commit().await?;
+
+        // Update transfer object
+        transfer.status = TransferStatus::Cancelled;
+        transfer.fail_reason = Some(fail_reason);
+        transfer.updated_at = now;
+
+        Ok(TransferAcceptanceResponse { transfer })
+    }
+
+    // Cancels transfers whose recipient did not answer before acceptBy and returns how many were picked up
+    pub async fn expire_unaccepted_transfers(&self, limit: u32) -> Result<usize, String> {
+        let expired = self.transfer_repository.get_expired_acceptances(self.clock.now(), limit).await?;
+
+        for transfer in &expired {
+            let fail_reason = match transfer.accept_by {
+                Some(accept_by) => format!("Not accepted by the recipient before {}", accept_by.to_rfc3339()),
+                None => "Not accepted by the recipient".to_string(),
+            };
+
+            let uow = self.unit_of_work_factory.begin().await?;
+            match uow.transfers().update_transfer_status(&transfer.idem_key, TransferStatus::Pending, TransferStatus::Cancelled, None, Some(fail_reason), self.clock.now()).await {
+                Ok(_) => uow.commit().await?,
+                // Accepted, declined or cancelled in the meantime
+                Err(e) if e.contains("Illegal transfer status transition") => uow.rollback().await?,
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(expired.len())
+    }
+
+    // `reversed_by` is the staff member doing it, recorded on the reversal
+    pub async fn reverse_transfer(&self, idem_key: &str, reversed_by: &str, request: ReverseTransferRequest) -> Result<TransferReverseResponse, String> {
+        // Validate request
+        request.validate()?;
+        let force = request.force.unwrap_or(false);
+
+        let transfer = self.transfer_repository.get_transfer_by_idem_key(idem_key).await?
+            .ok_or("Transfer not found".to_string())?;
+
+        let _account_lock = self.account_locks.lock(&[transfer.from_user_id, transfer.to_user_id]).await;
+        let uow = self.unit_of_work_factory.begin().await?;
+
+        // Re-read inside the transaction so two reversals of the same transfer cannot both pass
+        let mut transfer = uow.transfers().get_transfer_by_idem_key(idem_key).await?
+            .ok_or("Transfer not found".to_string())?;
+        if transfer.status != TransferStatus::Completed {
+            return Err(format!("Only completed transfers can be reversed (current status: {})", transfer.status));
+        }
+        if transfer.transfer_type == TransferType::Split {
+            return Err("Split transfers are reversed one recipient at a time; reverse each child transfer".to_string());
+        }
+        let transfer_id = transfer.transfer_id
+            .ok_or("Transfer has no internal id".to_string())?;
+
+        let point_ledger_repository = uow.point_ledger();
+
+        // Refuse if the recipient has spent anything since receiving the points, unless an admin forces it
+        if !force {
+            let ledger_entries = point_ledger_repository.get_ledger_by_transfer_id(transfer_id).await?;
+            let transfer_in = ledger_entries.iter()
+                .find(|entry| entry.user_id == transfer.to_user_id && matches!(entry.event_type, EventType::TransferIn))
+                .ok_or("Transfer has no transfer_in ledger entry".to_string())?;
+
+            if point_ledger_repository.has_debits_after(transfer.to_user_id, transfer_in.id).await? {
+                return Err("Recipient has already spent the transferred points".to_string());
+            }
+        }
+
+        // Even a forced reversal never takes the recipient below zero, or into points they have on hold
+        let to_balance = point_ledger_repository.get_current_balance(transfer.to_user_id).await?;
+        let from_balance = point_ledger_repository.get_current_balance(transfer.from_user_id).await?;
+        if point_ledger_repository.get_available_balance(transfer.to_user_id).await? < transfer.amount {
+            return Err("Insufficient points to reverse transfer".to_string());
+        }
+
+        let metadata = Some(serde_json::json!({
+            "transfer_id": transfer.transfer_id,
+            "idem_key": transfer.idem_key,
+            "reversal": true,
+            "reason": request.reason
+        }).to_string());
+
+        // Compensating entries: take the points back from the recipient, return them to the sender
+        point_ledger_repository.create_ledger_entry(
+            transfer.to_user_id,
+            -(transfer.amount as i32),
+            to_balance - transfer.amount,
+            EventType::TransferOut,
+            transfer.transfer_id,
+            Some(format!("Reversal of transfer to user {}", transfer.to_user_id)),
+            metadata.clone(),
+        ).await?;
+
+        point_ledger_repository.create_ledger_entry(
+            transfer.from_user_id,
+            transfer.amount as i32,
+            from_balance + transfer.amount,
+            EventType::TransferIn,
+            transfer.transfer_id,
+            Some(format!("Reversal of transfer from user {}", transfer.from_user_id)),
+            metadata,
+        ).await?;
+
+        let now = self.clock.now();
+        uow.transfers().update_transfer_status(
+            &transfer.idem_key,
+            TransferStatus::Completed,
+            TransferStatus::Reversed,
+            None,
+            None,
+            now,
+        ).await?;
+
+        let reversal = uow.transfers().create_transfer_reversal(
+            transfer_id,
+            reversed_by,
+            request.reason.trim(),
+            force,
+            now,
+        ).await?;
+
+        uow.commit().await?;
+
+        // Update transfer object
+        transfer.status = TransferStatus::Reversed;
+        transfer.updated_at = reversal.created_at;
+
+        Ok(TransferReverseResponse { transfer, reversal })
     }
 
     pub async fn list_transfers(&self, user_id: u32, page: u32, page_size: u32) -> Result<TransferListResponse, String> {
@@ -111,19 +660,205 @@ impl TransferService {
         })
     }
 
-    async fn process_transfer(&self, transfer: &Transfer) -> Result<(), String> {
+    pub async fn list_transfer_limits(&self) -> Result<Vec<TransferLimits>, String> {
+        self.transfer_limit_repository.list_limits().await
+    }
+
+    pub async fn get_transfer_limits(&self, membership_level: &str) -> Result<TransferLimits, String> {
+        self.transfer_limit_repository.get_limits(membership_level).await?
+            .ok_or(format!("No transfer limits configured for membership level {}", membership_level))
+    }
+
+    pub async fn set_transfer_limits(&self, membership_level: &str, request: UpdateTransferLimitsRequest) -> Result<TransferLimits, String> {
+        // Limits are kept under the tier's canonical name so every spelling finds them
+        let tier = membership_level.trim().parse::<MembershipTier>()?;
+
+        self.transfer_limit_repository.set_limits(&tier.to_string(), request).await
+    }
+
+    // Rejects a transfer that would break the sender's membership-level limits. Runs inside the unit of work
+    // so transfers created earlier in the same transaction are counted too; levels without limits pass.
+    async fn check_transfer_limits(&self, uow: &dyn UnitOfWork, from_user_id: u32, limits: Option<&TransferLimits>, amount: u32) -> Result<(), String> {
+        let Some(limits) = limits else {
+            return Ok(());
+        };
+
+        let now = self.clock.now();
+        let (sent_last_day, _) = uow.transfers().get_outgoing_totals(from_user_id, now - Duration::hours(24)).await?;
+        let (_, transfers_last_hour) = uow.transfers().get_outgoing_totals(from_user_id, now - Duration::hours(1)).await?;
+
+        limits.check(amount, sent_last_day, transfers_last_hour)
+            .map_err(|violation| violation.to_string())
+    }
+
+    async fn find_replayed_transfer(&self, uow: &dyn UnitOfWork, from_user_id: u32, key: &str, fingerprint: &str) -> Result<Option<Transfer>, String> {
+        let now = self.clock.now();
+        uow.idempotency_keys().delete_expired(now).await?;
+
+        let record = match uow.idempotency_keys().get_record(from_user_id, key).await? {
+            Some(record) if !record.is_expired(now) => record,
+            _ => return Ok(None),
+        };
+
+        if record.request_fingerprint != fingerprint {
+            return Err("Idempotency key already used with a different request".to_string());
+        }
+
+        let transfer = uow.transfers().get_transfer_by_idem_key(&record.transfer_idem_key).await?
+            .ok_or("Transfer not found".to_string())?;
+
+        Ok(Some(transfer))
+    }
+
+    async fn remember_idempotency_key(&self, uow: &dyn UnitOfWork, key: Option<&str>, fingerprint: &str, transfer: &Transfer) -> Result<(), String> {
+        let Some(key) = key else {
+            return Ok(());
+        };
+
+        let now = self.clock.now();
+        uow.idempotency_keys().create_record(IdempotencyRecord {
+            from_user_id: transfer.from_user_id,
+            key: key.to_string(),
+            request_fingerprint: fingerprint.to_string(),
+            transfer_idem_key: transfer.idem_key.clone(),
+            created_at: now,
+            expires_at: now + self.idempotency_retention,
+        }).await
+    }
+
+    async fn find_mandate_run_transfer(&self, uow: &dyn UnitOfWork, mandate_run: Option<(u32, u32)>) -> Result<Option<Transfer>, String> {
+        let Some((mandate_id, occurrence)) = mandate_run else {
+            return Ok(None);
+        };
+        let Some(run) = uow.mandates().get_run(mandate_id, occurrence).await? else {
+            return Ok(None);
+        };
+
+        let transfer = uow.transfers().get_transfer_by_idem_key(&run.transfer_idem_key).await?
+            .ok_or("Transfer not found".to_string())?;
+
+        Ok(Some(transfer))
+    }
+
+    async fn remember_mandate_run(&self, uow: &dyn UnitOfWork, mandate_run: Option<(u32, u32)>, transfer: &Transfer) -> Result<(), String> {
+        let Some((mandate_id, occurrence)) = mandate_run else {
+            return Ok(());
+        };
+
+        uow.mandates().create_run(&MandateRun {
+            mandate_id,
+            occurrence,
+            transfer_idem_key: transfer.idem_key.clone(),
+            created_at: self.clock.now(),
+        }).await
+    }
+
+    async fn create_and_execute(&self, uow: &dyn UnitOfWork, request: CreateTransferRequest, limits: Option<&TransferLimits>, key: &str, fingerprint: &str) -> Result<Transfer, String> {
+        self.check_transfer_limits(uow, request.from_user_id, limits, request.amount).await?;
+
+        let mut transfer = uow.transfers().create_transfer(request, self.clock.now()).await?;
+        self.remember_idempotency_key(uow, Some(key), fingerprint, &transfer).await?;
+        self.execute_transfer(uow, &mut transfer).await?;
+
+        Ok(transfer)
+    }
+
+    async fn fail_pending_transfer(&self, mut transfer: Transfer, fail_reason: String) -> Result<Transfer, String> {
+        let uow = self.unit_of_work_factory.begin().await?;
+
+        self.mark_transfer_failed(uow.as_ref(), &mut transfer, fail_reason).await?;
+
+        uow.commit().await?;
+
+        Ok(transfer)
+    }
+
+    async fn record_failed_transfer(&self, request: CreateTransferRequest, fail_reason: String, idempotency_key: Option<&str>, fingerprint: &str, mandate_run: Option<(u32, u32)>) -> Result<Transfer, String> {
+        let uow = self.unit_of_work_factory.begin().await?;
+
+        let mut transfer = uow.transfers().create_transfer(request, self.clock.now()).await?;
+        self.remember_idempotency_key(uow.as_ref(), idempotency_key, fingerprint, &transfer).await?;
+        self.remember_mandate_run(uow.as_ref(), mandate_run, &transfer).await?;
+        self.mark_transfer_failed(uow.as_ref(), &mut transfer, fail_reason).await?;
+
+        uow.commit().await?;
+
+        Ok(transfer)
+    }
+
+    async fn mark_transfer_failed(&self, uow: &dyn UnitOfWork, transfer: &mut Transfer, fail_reason: String) -> Result<(), String> {
+        let now = self.clock.now();
+        uow.transfers().update_transfer_status(
+            &transfer.idem_key,
+            TransferStatus::Pending,
+            TransferStatus::Processing,
+            None,
+            None,
+            now,
+        ).await?;
+        uow.transfers().update_transfer_status(
+            &transfer.idem_key,
+            TransferStatus::Processing,
+            TransferStatus::Failed,
+            None,
+            Some(fail_reason.clone()),
+            now,
+        ).await?;
+
+        // Update transfer object
+        transfer.status = TransferStatus::Failed;
+        transfer.fail_reason = Some(fail_reason);
+        transfer.updated_at = now;
+
+        Ok(())
+    }
+
+    // Claims a pending transfer, posts its ledger entries and completes it inside the caller's unit of work
+    async fn execute_transfer(&self, uow: &dyn UnitOfWork, transfer: &mut Transfer) -> Result<(), String> {
+        uow.transfers().update_transfer_status(
+            &transfer.idem_key,
+            TransferStatus::Pending,
+            TransferStatus::Processing,
+            None,
+            None,
+            self.clock.now(),
+        ).await?;
+
+        self.process_transfer(uow, transfer).await?;
+
+        let completed_at = self.clock.now();
+        uow.transfers().update_transfer_status(
+            &transfer.idem_key,
+            TransferStatus::Processing,
+            TransferStatus::Completed,
+            Some(completed_at.to_rfc3339()),
+            None,
+            completed_at,
+        ).await?;
+
+        // Update transfer object
+        transfer.status = TransferStatus::Completed;
+        transfer.completed_at = Some(completed_at);
+        transfer.updated_at = completed_at;
+
+        Ok(())
+    }
+
+    async fn process_transfer(&self, uow: &dyn UnitOfWork, transfer: &Transfer) -> Result<(), String> {
+        let point_ledger_repository = uow.point_ledger();
+
         // Get current balances
-        let from_balance = self.point_ledger_repository.get_current_balance(transfer.from_user_id).await?;
-        let to_balance = self.point_ledger_repository.get_current_balance(transfer.to_user_id).await?;
+        let from_balance = point_ledger_repository.get_current_balance(transfer.from_user_id).await?;
+        let to_balance = point_ledger_repository.get_current_balance(transfer.to_user_id).await?;
 
-        // Double-check sender has enough points
-        if from_balance < transfer.amount {
+        // Double-check sender has enough points, leaving anything on hold for other transfers untouched
+        if point_ledger_repository.get_available_balance(transfer.from_user_id).await? < transfer.amount {
             return Err("Insufficient points".to_string());
         }
 
         // Create ledger entries (transfer_out for sender)
         let new_from_balance = from_balance - transfer.amount;
-        self.point_ledger_repository.create_ledger_entry(
+        point_ledger_repository.create_ledger_entry(
             transfer.from_user_id,
             -(transfer.amount as i32),
             new_from_balance,
@@ -139,7 +874,7 @@ impl TransferService {
 
         // Create ledger entry (transfer_in for receiver)
         let new_to_balance = to_balance + transfer.amount;
-        self.point_ledger_repository.create_ledger_entry(
+        point_ledger_repository.create_ledger_entry(
             transfer.to_user_id,
             transfer.amount as i32,
             new_to_balance,
@@ -155,4 +890,309 @@ impl TransferService {
 
         Ok(())
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use sqlx::SqlitePool;
+    use crate::domain::{SystemClock, PointLedgerRepository, SplitRecipient};
+    use crate::infrastructure::{SqliteTransferRepository, SqliteUserRepository, SqliteTransferLimitRepository, SqliteUnitOfWorkFactory, SqlitePointLedgerRepository};
+    use crate::infrastructure::test_support::{test_pool, FakeClock};
+
+    fn service(pool: &SqlitePool, clock: Arc<dyn Clock + Send + Sync>) -> TransferService {
+        TransferService::new(
+            Arc::new(SqliteTransferRepository::new(pool.clone())),
+            Arc::new(SqliteUserRepository::new(pool.clone())),
+            Arc::new(SqliteTransferLimitRepository::new(pool.clone())),
+            Arc::new(SqliteUnitOfWorkFactory::new(pool.clone())),
+            clock,
+        )
+    }
+
+    fn transfer_request(from_user_id: u32, to_user_id: u32, amount: u32) -> CreateTransferRequest {
+        CreateTransferRequest { from_user_id, to_user_id, amount, note: None, execute_at: None }
+    }
+
+    async fn ledger_balance(pool: &SqlitePool, user_id: u32) -> i64 {
+        sqlx::query_scalar("SELECT COALESCE(SUM(change), 0) FROM point_ledger WHERE user_id = ?")
+            .bind(user_id as i64)
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn failure_between_ledger_inserts_rolls_back_the_whole_transfer() {
+        let pool = test_pool().await;
+        // The transfer_out row is already written inside the transaction when this fires
+        sqlx::query(
+            "CREATE TRIGGER fail_transfer_in BEFORE INSERT ON point_ledger WHEN NEW.event_type = 'transfer_in' \
+             BEGIN SELECT RAISE(ABORT, 'injected failure'); END",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        let service = service(&pool, Arc::new(SystemClock));
+
+        // The partial posting is rolled back and the transfer is recorded as failed on its own
+        let transfer = service.create_transfer(transfer_request(1, 2, 100), None).await.unwrap().transfer;
+        assert_eq!(transfer.status, TransferStatus::Failed);
+        assert!(transfer.fail_reason.as_deref().unwrap_or_default().contains("injected failure"), "{:?}", transfer.fail_reason);
+
+        let transfer_rows: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM point_ledger WHERE transfer_id IS NOT NULL")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(transfer_rows, 0);
+        assert_eq!(ledger_balance(&pool, 1).await, 1500);
+        assert_eq!(ledger_balance(&pool, 2).await, 750);
+    }
+
+    #[tokio::test]
+    async fn transfer_posts_both_ledger_rows_once_the_failure_is_gone() {
+        let pool = test_pool().await;
+        let service = service(&pool, Arc::new(SystemClock));
+
+        let response = service.create_transfer(transfer_request(1, 2, 100), None).await.unwrap();
+        assert_eq!(response.transfer.status, TransferStatus::Completed);
+
+        let rows: Vec<(String, i64)> = sqlx::query_as("SELECT event_type, change FROM point_ledger WHERE transfer_id = ? ORDER BY id")
+            .bind(response.transfer.transfer_id.map(|id| id as i64))
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+        assert_eq!(rows, vec![("transfer_out".to_string(), -100), ("transfer_in".to_string(), 100)]);
+        assert_eq!(ledger_balance(&pool, 1).await, 1400);
+        assert_eq!(ledger_balance(&pool, 2).await, 850);
+    }
+
+    #[tokio::test]
+    async fn idempotency_keys_belong_to_the_sender() {
+        let pool = test_pool().await;
+        let service = service(&pool, Arc::new(SystemClock));
+        let key = Some("shared-key-123".to_string());
+
+        let first = service.create_transfer(transfer_request(1, 3, 10), key.clone()).await.unwrap().transfer;
+        let second = service.create_transfer(transfer_request(2, 3, 20), key.clone()).await.unwrap().transfer;
+        assert_ne!(first.idem_key, second.idem_key);
+        assert_eq!(second.from_user_id, 2);
+
+        let replay = service.create_transfer(transfer_request(2, 3, 20), key.clone()).await.unwrap().transfer;
+        assert_eq!(replay.idem_key, second.idem_key);
+        let err = service.create_transfer(transfer_request(2, 3, 21), key).await.unwrap_err();
+        assert!(err.contains("different request"), "{}", err);
+    }
+    #[tokio::test]
+    async fn scheduled_transfer_waits_for_its_time_and_checks_the_balance_then() {
+        let pool = test_pool().await;
+        let clock = Arc::new(FakeClock::new(Utc::now()));
+        let service = service(&pool, clock.clone());
+        let execute_at = clock.now() + Duration::hours(1);
+
+        // Bob has 200 points: both 150-point gifts are accepted up front, only the first can be paid
+        let first = CreateTransferRequest { execute_at: Some(execute_at), ..transfer_request(3, 1, 150) };
+        let second = CreateTransferRequest { execute_at: Some(execute_at + Duration::minutes(1)), ..transfer_request(3, 1, 150) };
+        let first = service.create_transfer(first, None).await.unwrap().transfer;
+        let second = service.create_transfer(second, None).await.unwrap().transfer;
+        assert_eq!(first.status, TransferStatus::Pending);
+        assert_eq!(second.status, TransferStatus::Pending);
+        assert_eq!(ledger_balance(&pool, 3).await, 200);
+
+        // Not due yet: the worker query does not see them and processing leaves them alone
+        assert!(service.list_pending_transfers(50).await.unwrap().is_empty());
+        let untouched = service.process_pending_transfer(&first.idem_key).await.unwrap();
+        assert_eq!(untouched.status, TransferStatus::Pending);
+
+        clock.advance(Duration::hours(2));
+        assert_eq!(service.list_pending_transfers(50).await.unwrap().len(), 2);
+        let first = service.process_pending_transfer(&first.idem_key).await.unwrap();
+        let second = service.process_pending_transfer(&second.idem_key).await.unwrap();
+        assert_eq!(first.status, TransferStatus::Completed);
+        assert_eq!(second.status, TransferStatus::Failed);
+        assert_eq!(second.fail_reason.as_deref(), Some("Insufficient points"));
+        assert_eq!(ledger_balance(&pool, 3).await, 50);
+        assert_eq!(ledger_balance(&pool, 1).await, 1650);
+    }
+
+    #[tokio::test]
+    async fn execute_at_must_be_in_the_future() {
+        let pool = test_pool().await;
+        let clock = Arc::new(FakeClock::new(Utc::now()));
+        let service = service(&pool, clock.clone());
+
+        for execute_at in [clock.now(), clock.now() - Duration::seconds(1)] {
+            let request = CreateTransferRequest { execute_at: Some(execute_at), ..transfer_request(1, 2, 10) };
+            let err = service.create_transfer(request, None).await.unwrap_err();
+            assert_eq!(err, "executeAt must be in the future");
+        }
+    }
+
+    #[tokio::test]
+    async fn transferred_points_keep_the_age_they_were_earned_at() {
+        let pool = test_pool().await;
+        let service = service(&pool, Arc::new(SystemClock));
+        let ledger = SqlitePointLedgerRepository::new(pool.clone());
+        let earned_at = "2025-01-01T00:00:00+00:00";
+        sqlx::query("UPDATE point_lots SET earned_at = ? WHERE user_id = 1")
+            .bind(earned_at)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        // Bouncing the points back and forth must not restart their lifetime
+        service.create_transfer(transfer_request(1, 2, 1500), None).await.unwrap();
+        service.create_transfer(transfer_request(2, 1, 1500), None).await.unwrap();
+
+        let lots = ledger.get_open_lots(1).await.unwrap();
+        assert_eq!(lots.iter().map(|lot| lot.remaining).sum::<u32>(), 1500);
+        assert!(lots.iter().all(|lot| lot.earned_at.to_rfc3339() == earned_at), "{:?}", lots);
+
+        // The points user 2 received were older than their own, so those went back first and user 2's own points are what is left
+        let jane = ledger.get_open_lots(2).await.unwrap();
+        assert_eq!(jane.iter().map(|lot| lot.remaining).sum::<u32>(), 750);
+        assert!(jane.iter().all(|lot| lot.earned_at.to_rfc3339() != earned_at), "{:?}", jane);
+    }
+
+    #[tokio::test]
+    async fn limit_windows_follow_the_injected_clock() {
+        let pool = test_pool().await;
+        // Well away from the wall clock, so rows stamped with Utc::now() would land outside every window
+        let clock = Arc::new(FakeClock::new(Utc::now() - Duration::days(30)));
+        let service = service(&pool, clock.clone());
+        service.set_transfer_limits("Bronze", UpdateTransferLimitsRequest {
+            min_amount: None,
+            max_amount: None,
+            daily_cap: Some(100),
+            max_transfers_per_hour: None,
+        }).await.unwrap();
+
+        let first = service.create_transfer(transfer_request(3, 1, 60), None).await.unwrap().transfer;
+        assert_eq!(first.created_at, clock.now());
+        let err = service.create_transfer(transfer_request(3, 1, 60), None).await.unwrap_err();
+        assert!(err.contains("dailyCap"), "{}", err);
+
+        clock.advance(Duration::hours(25));
+        let second = service.create_transfer(transfer_request(3, 1, 60), None).await.unwrap().transfer;
+        assert_eq!(second.status, TransferStatus::Completed);
+        assert_eq!(second.completed_at, Some(clock.now()));
+    }
+
+    #[tokio::test]
+    async fn split_debits_exactly_what_its_legs_add_up_to() {
+        let pool = test_pool().await;
+        let service = service(&pool, Arc::new(SystemClock));
+        // A bill of 100 split unevenly by the client: no leg is rounded, and nothing is lost or created on the way
+        let request = CreateSplitTransferRequest {
+            from_user_id: 3,
+            recipients: vec![
+                SplitRecipient { to_user_id: 1, amount: 33 },
+                SplitRecipient { to_user_id: 2, amount: 67 },
+            ],
+            note: None,
+        };
+
+        let response = service.create_split_transfer(request, None).await.unwrap();
+        assert_eq!(response.transfer.amount, 100);
+        assert_eq!(response.splits.iter().map(|split| split.amount).collect::<Vec<_>>(), [33, 67]);
+        assert!(response.splits.iter().all(|split| split.status == TransferStatus::Completed));
+
+        let rows: Vec<(i64, String, i64)> = sqlx::query_as("SELECT user_id, event_type, change FROM point_ledger WHERE transfer_id IS NOT NULL ORDER BY id")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+        assert_eq!(rows, [(3, "transfer_out".to_string(), -100), (1, "transfer_in".to_string(), 33), (2, "transfer_in".to_string(), 67)]);
+        assert_eq!(ledger_balance(&pool, 3).await, 100);
+
+        // Over the balance as a whole, even though each leg alone would fit
+        let request = CreateSplitTransferRequest {
+            from_user_id: 3,
+            recipients: vec![SplitRecipient { to_user_id: 1, amount: 60 }, SplitRecipient { to_user_id: 2, amount: 60 }],
+            note: None,
+        };
+        assert_eq!(service.create_split_transfer(request, None).await.unwrap_err(), "Insufficient points");
+    }
+
+    #[tokio::test]
+    async fn a_first_transfer_waits_for_the_recipient_until_accept_by() {
+        let pool = test_pool().await;
+        let clock = Arc::new(FakeClock::new(Utc::now()));
+        let service = service(&pool, clock.clone()).with_recipient_acceptance(Some(Duration::hours(48)));
+        let ledger = SqlitePointLedgerRepository::new(pool.clone());
+        let transfers = SqliteTransferRepository::new(pool.clone());
+
+        let waiting = service.create_transfer(transfer_request(1, 2, 100), None).await.unwrap().transfer;
+        assert_eq!(waiting.status, TransferStatus::Pending);
+        assert_eq!(waiting.accept_by, Some(clock.now() + Duration::hours(48)));
+        // Held for the recipient: out of John's available balance, still in his ledger balance
+        assert_eq!(ledger.get_available_balance(1).await.unwrap(), 1400);
+        assert_eq!(ledger_balance(&pool, 1).await, 1500);
+
+        let err = service.accept_transfer(&waiting.idem_key, AcceptTransferRequest { accepted_by: 3 }).await.unwrap_err();
+        assert_eq!(err, "Only the recipient can accept this transfer");
+        let accepted = service.accept_transfer(&waiting.idem_key, AcceptTransferRequest { accepted_by: 2 }).await.unwrap().transfer;
+        assert_eq!(accepted.status, TransferStatus::Completed);
+        assert_eq!(ledger_balance(&pool, 2).await, 850);
+
+        // Jane has taken points from John before, so the next one goes straight through
+        let next = service.create_transfer(transfer_request(1, 2, 100), None).await.unwrap().transfer;
+        assert_eq!(next.status, TransferStatus::Completed);
+
+        let declined = service.create_transfer(transfer_request(1, 3, 100), None).await.unwrap().transfer;
+        let decline = DeclineTransferRequest { declined_by: 3, reason: Some("Not mine".to_string()) };
+        let declined = service.decline_transfer(&declined.idem_key, decline).await.unwrap().transfer;
+        assert_eq!(declined.status, TransferStatus::Cancelled);
+        assert_eq!(declined.fail_reason.as_deref(), Some("Declined by recipient: Not mine"));
+        assert_eq!(ledger.get_available_balance(1).await.unwrap(), 1300);
+
+        // Nobody answers this one: after acceptBy it can no longer be accepted, and the sweep cancels it
+        let unanswered = service.create_transfer(transfer_request(2, 3, 50), None).await.unwrap().transfer;
+        clock.advance(Duration::hours(47));
+        assert_eq!(service.expire_unaccepted_transfers(10).await.unwrap(), 0);
+        clock.advance(Duration::hours(1));
+        let err = service.accept_transfer(&unanswered.idem_key, AcceptTransferRequest { accepted_by: 3 }).await.unwrap_err();
+        assert_eq!(err, "The acceptance window for this transfer has closed");
+        assert_eq!(service.expire_unaccepted_transfers(10).await.unwrap(), 1);
+        let expired = transfers.get_transfer_by_idem_key(&unanswered.idem_key).await.unwrap().unwrap();
+        assert_eq!(expired.status, TransferStatus::Cancelled);
+        assert!(expired.fail_reason.as_deref().unwrap_or_default().starts_with("Not accepted by the recipient"), "{:?}", expired.fail_reason);
+        assert_eq!(ledger.get_available_balance(2).await.unwrap(), 950);
+        assert_eq!(ledger_balance(&pool, 3).await, 200);
+    }
+
+    #[tokio::test]
+    async fn only_a_forced_reversal_takes_back_points_the_recipient_spent() {
+        let pool = test_pool().await;
+        let clock = Arc::new(FakeClock::new(Utc::now()));
+        let service = service(&pool, clock.clone());
+        let ledger = SqlitePointLedgerRepository::new(pool.clone());
+        let reverse = |force| ReverseTransferRequest { reason: "Fraud".to_string(), force };
+
+        let transfer = service.create_transfer(transfer_request(1, 2, 100), None).await.unwrap().transfer;
+        ledger.create_ledger_entry(2, -800, 50, EventType::Redeem, None, None, None).await.unwrap();
+
+        // What an ordinary staff member can do; only admins get to send force
+        let err = service.reverse_transfer(&transfer.idem_key, "staff@example.com", reverse(None)).await.unwrap_err();
+        assert_eq!(err, "Recipient has already spent the transferred points");
+        // Forcing it still never takes Jane below zero
+        let err = service.reverse_transfer(&transfer.idem_key, "admin@example.com", reverse(Some(true))).await.unwrap_err();
+        assert_eq!(err, "Insufficient points to reverse transfer");
+
+        ledger.create_ledger_entry(2, 50, 100, EventType::Earn, None, None, None).await.unwrap();
+        clock.advance(Duration::hours(1));
+        let err = service.reverse_transfer(&transfer.idem_key, "staff@example.com", reverse(Some(false))).await.unwrap_err();
+        assert_eq!(err, "Recipient has already spent the transferred points");
+        let response = service.reverse_transfer(&transfer.idem_key, "admin@example.com", reverse(Some(true))).await.unwrap();
+        assert_eq!(response.transfer.status, TransferStatus::Reversed);
+        assert_eq!(response.transfer.updated_at, clock.now());
+        assert_eq!(response.reversal.reversed_by, "admin@example.com");
+        assert!(response.reversal.forced);
+        assert_eq!(response.reversal.created_at, clock.now());
+        assert_eq!(ledger_balance(&pool, 1).await, 1500);
+        assert_eq!(ledger_balance(&pool, 2).await, 0);
+
+        let err = service.reverse_transfer(&transfer.idem_key, "admin@example.com", reverse(Some(true))).await.unwrap_err();
+        assert!(err.contains("Only completed transfers"), "{}", err);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,121 @@
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use super::TransferService;
+
+// SQLite reports lock contention as "database is locked" / SQLITE_BUSY; those are worth retrying
+pub fn is_transient_error(error: &str) -> bool {
+    let error = error.to_lowercase();
+    error.contains("database is locked") || error.contains("busy")
+}
+
+// Background loop that picks up pending transfers when TransferService runs in async mode, and cancels
+// transfers whose recipient did not accept them in time
+#[derive(Clone)]
+pub struct TransferWorker {
+    transfer_service: TransferService,
+    poll_interval: Duration,
+    batch_size: u32,
+    max_retries: u32,
+    retry_backoff: Duration,
+}
+
+impl TransferWorker {
+    pub fn new(transfer_service: TransferService) -> Self {
+        Self {
+            transfer_service,
+            poll_interval: Duration::from_millis(500),
+            batch_size: 50,
+            max_retries: 5,
+            retry_backoff: Duration::from_millis(100),
+        }
+    }
+
+    pub fn spawn(self) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            loop {
+                match self.run_once().await {
+                    // Something moved on, so more may be waiting: go again straight away. A poll where nothing
+                    // moved (everything hit a transient error) waits, so the same page is not retried in a tight loop
+                    Ok(moved) if moved > 0 => continue,
+                    Ok(_) => {}
+                    Err(e) => eprintln!("⚠️  Transfer worker failed to poll pending transfers: {}", e),
+                }
+                tokio::time::sleep(self.poll_interval).await;
+            }
+        })
+    }
+
+    // Processes one batch of pending transfers and one of expired acceptances, and returns how many transfers
+    // left pending (completed, failed or cancelled)
+    pub async fn run_once(&self) -> Result<usize, String> {
+        let pending = self.transfer_service.list_pending_transfers(self.batch_size).await?;
+
+        let mut moved = 0;
+        for transfer in &pending {
+            if self.process_with_retry(&transfer.idem_key).await {
+                moved += 1;
+            }
+        }
+
+        let expired = self.transfer_service.expire_unaccepted_transfers(self.batch_size).await?;
+
+        Ok(moved + expired)
+    }
+
+    async fn process_with_retry(&self, idem_key: &str) -> bool {
+        let mut attempt = 0;
+        loop {
+            match self.transfer_service.process_pending_transfer(idem_key).await {
+                Ok(_) => return true,
+                Err(e) if is_transient_error(&e) && attempt < self.max_retries => {
+                    // Exponential backoff: 100ms, 200ms, 400ms, ...
+                    tokio::time::sleep(self.retry_backoff * 2u32.pow(attempt)).await;
+                    attempt += 1;
+                }
+                Err(e) => {
+                    // Left pending; the next poll tries again
+                    eprintln!("⚠️  Transfer {} could not be processed: {}", idem_key, e);
+                    return false;
+                }
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use chrono::Utc;
+    use crate::domain::{Clock, CreateTransferRequest, TransferStatus};
+    use crate::infrastructure::{SqliteTransferRepository, SqliteUserRepository, SqliteTransferLimitRepository, SqliteUnitOfWorkFactory};
+    use crate::infrastructure::test_support::{test_pool, FakeClock};
+    use super::*;
+
+    #[tokio::test]
+    async fn scheduled_transfers_run_once_the_clock_reaches_them() {
+        let pool = test_pool().await;
+        let clock = Arc::new(FakeClock::new(Utc::now()));
+        let service = TransferService::new(
+            Arc::new(SqliteTransferRepository::new(pool.clone())),
+            Arc::new(SqliteUserRepository::new(pool.clone())),
+            Arc::new(SqliteTransferLimitRepository::new(pool.clone())),
+            Arc::new(SqliteUnitOfWorkFactory::new(pool.clone())),
+            clock.clone(),
+        );
+        let worker = TransferWorker::new(service.clone());
+        let execute_at = clock.now() + chrono::Duration::days(1);
+        let request = CreateTransferRequest { from_user_id: 1, to_user_id: 2, amount: 100, note: None, execute_at: Some(execute_at) };
+        let transfer = service.create_transfer(request, None).await.unwrap().transfer;
+
+        assert_eq!(worker.run_once().await.unwrap(), 0);
+        clock.advance(chrono::Duration::hours(23));
+        assert_eq!(worker.run_once().await.unwrap(), 0);
+        assert_eq!(service.get_transfer(&transfer.idem_key).await.unwrap().transfer.status, TransferStatus::Pending);
+
+        clock.advance(chrono::Duration::hours(1));
+        assert_eq!(worker.run_once().await.unwrap(), 1);
+        assert_eq!(service.get_transfer(&transfer.idem_key).await.unwrap().transfer.status, TransferStatus::Completed);
+        assert_eq!(worker.run_once().await.unwrap(), 0);
+    }
+}
\ No newline at end of file
@@ -15,10 +15,6 @@ impl UserService {
         self.repository.get_user_by_id(id).await
     }
 
-    pub async fn get_user_by_email(&self, email: &str) -> Result<Option<User>, String> {
-        self.repository.get_user_by_email(email).await
-    }
-
     pub async fn create_user(&self, user_request: CreateUserRequest) -> Result<User, String> {
         self.repository.create_user(user_request).await
     }
@@ -0,0 +1,238 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum AdjustmentStatus {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+impl std::fmt::Display for AdjustmentStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AdjustmentStatus::Pending => write!(f, "pending"),
+            AdjustmentStatus::Approved => write!(f, "approved"),
+            AdjustmentStatus::Rejected => write!(f, "rejected"),
+        }
+    }
+}
+
+impl std::str::FromStr for AdjustmentStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "pending" => Ok(AdjustmentStatus::Pending),
+            "approved" => Ok(AdjustmentStatus::Approved),
+            "rejected" => Ok(AdjustmentStatus::Rejected),
+            _ => Err(format!("Invalid adjustment status: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum AdjustmentAction {
+    Requested,
+    Approved,
+    Rejected,
+}
+
+impl std::fmt::Display for AdjustmentAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AdjustmentAction::Requested => write!(f, "requested"),
+            AdjustmentAction::Approved => write!(f, "approved"),
+            AdjustmentAction::Rejected => write!(f, "rejected"),
+        }
+    }
+}
+
+impl std::str::FromStr for AdjustmentAction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "requested" => Ok(AdjustmentAction::Requested),
+            "approved" => Ok(AdjustmentAction::Approved),
+            "rejected" => Ok(AdjustmentAction::Rejected),
+            _ => Err(format!("Invalid adjustment action: {}", s)),
+        }
+    }
+}
+
+// A proposed balance correction; the adjust ledger entry is only posted once a second person approves it
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BalanceAdjustment {
+    pub id: u32,
+    #[serde(rename = "userId")]
+    pub user_id: u32,
+    // Positive to credit the user, negative to debit them
+    pub amount: i32,
+    pub justification: String,
+    pub status: AdjustmentStatus,
+    // Emails of the signed-in staff members who requested and reviewed it
+    #[serde(rename = "requestedBy")]
+    pub requested_by: String,
+    #[serde(rename = "reviewedBy")]
+    pub reviewed_by: Option<String>,
+    #[serde(rename = "reviewNote")]
+    pub review_note: Option<String>,
+    // The adjust entry posted on approval
+    #[serde(rename = "ledgerEntryId")]
+    pub ledger_entry_id: Option<u32>,
+    #[serde(rename = "createdAt")]
+    #[schema(value_type = String, format = "date-time")]
+    pub created_at: DateTime<Utc>,
+    #[serde(rename = "reviewedAt")]
+    #[schema(value_type = Option<String>, format = "date-time")]
+    pub reviewed_at: Option<DateTime<Utc>>,
+}
+
+// One row per step of an adjustment's life, never updated or deleted
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AdjustmentAuditEntry {
+    pub id: u32,
+    #[serde(rename = "adjustmentId")]
+    pub adjustment_id: u32,
+    pub action: AdjustmentAction,
+    pub actor: String,
+    pub note: Option<String>,
+    #[serde(rename = "createdAt")]
+    #[schema(value_type = String, format = "date-time")]
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CreateAdjustmentRequest {
+    #[serde(rename = "userId")]
+    pub user_id: u32,
+    pub amount: i32,
+    pub justification: String,
+}
+
+impl CreateAdjustmentRequest {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.amount == 0 {
+            return Err("Amount cannot be 0".to_string());
+        }
+
+        if self.justification.trim().is_empty() {
+            return Err("Justification is required".to_string());
+        }
+
+        if self.justification.len() > 1000 {
+            return Err("Justification cannot exceed 1000 characters".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ReviewAdjustmentRequest {
+    pub note: Option<String>,
+}
+
+impl ReviewAdjustmentRequest {
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(note) = &self.note
+            && note.len() > 1000
+        {
+            return Err("Note cannot exceed 1000 characters".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AdjustmentResponse {
+    pub adjustment: BalanceAdjustment,
+    pub audit: Vec<AdjustmentAuditEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AdjustmentListResponse {
+    pub data: Vec<BalanceAdjustment>,
+}
+
+// Database model for internal use
+#[derive(Debug, Clone)]
+pub struct BalanceAdjustmentDb {
+    pub id: u32,
+    pub user_id: u32,
+    pub amount: i32,
+    pub justification: String,
+    pub status: String,
+    pub requested_by: String,
+    pub reviewed_by: Option<String>,
+    pub review_note: Option<String>,
+    pub ledger_entry_id: Option<u32>,
+    pub created_at: String,
+    pub reviewed_at: Option<String>,
+}
+
+impl BalanceAdjustmentDb {
+    pub fn into_domain(self) -> Result<BalanceAdjustment, String> {
+        let status = self.status.parse::<AdjustmentStatus>()?;
+
+        let created_at = DateTime::parse_from_rfc3339(&self.created_at)
+            .map_err(|e| format!("Invalid created_at date: {}", e))?
+            .with_timezone(&Utc);
+
+        let reviewed_at = if let Some(reviewed_str) = self.reviewed_at {
+            Some(DateTime::parse_from_rfc3339(&reviewed_str)
+                .map_err(|e| format!("Invalid reviewed_at date: {}", e))?
+                .with_timezone(&Utc))
+        } else {
+            None
+        };
+
+        Ok(BalanceAdjustment {
+            id: self.id,
+            user_id: self.user_id,
+            amount: self.amount,
+            justification: self.justification,
+            status,
+            requested_by: self.requested_by,
+            reviewed_by: self.reviewed_by,
+            review_note: self.review_note,
+            ledger_entry_id: self.ledger_entry_id,
+            created_at,
+            reviewed_at,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AdjustmentAuditEntryDb {
+    pub id: u32,
+    pub adjustment_id: u32,
+    pub action: String,
+    pub actor: String,
+    pub note: Option<String>,
+    pub created_at: String,
+}
+
+impl AdjustmentAuditEntryDb {
+    pub fn into_domain(self) -> Result<AdjustmentAuditEntry, String> {
+        let action = self.action.parse::<AdjustmentAction>()?;
+
+        let created_at = DateTime::parse_from_rfc3339(&self.created_at)
+            .map_err(|e| format!("Invalid created_at date: {}", e))?
+            .with_timezone(&Utc);
+
+        Ok(AdjustmentAuditEntry {
+            id: self.id,
+            adjustment_id: self.adjustment_id,
+            action,
+            actor: self.actor,
+            note: self.note,
+            created_at,
+        })
+    }
+}
\ No newline at end of file
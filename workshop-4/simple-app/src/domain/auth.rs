@@ -0,0 +1,187 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use super::user::User;
+
+// Creates the user and their password in one call; the membership tier always starts at Bronze
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RegisterRequest {
+    pub first_name: String,
+    pub last_name: String,
+    pub phone: String,
+    pub email: String,
+    pub password: String,
+}
+
+impl RegisterRequest {
+    pub fn validate(&self) -> Result<(), String> {
+        validate_password(&self.password)
+    }
+}
+
+fn validate_password(password: &str) -> Result<(), String> {
+    if password.chars().count() < 8 {
+        return Err("Password must be at least 8 characters".to_string());
+    }
+
+    if password.len() > 128 {
+        return Err("Password cannot exceed 128 characters".to_string());
+    }
+
+    Ok(())
+}
+
+// Sign in with an email address or phone number and either the password or a one-time code from POST /auth/otp
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct LoginRequest {
+    // Email address or phone number
+    pub identifier: String,
+    pub password: Option<String>,
+    pub otp: Option<String>,
+}
+
+impl LoginRequest {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.identifier.trim().is_empty() {
+            return Err("identifier is required".to_string());
+        }
+
+        match (&self.password, &self.otp) {
+            (Some(_), None) | (None, Some(_)) => Ok(()),
+            _ => Err("Provide either password or otp".to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct OtpRequest {
+    // Email address or phone number; the code goes to that address
+    pub identifier: String,
+}
+
+// Sent whether or not the identifier belongs to a user, so the endpoint does not reveal who is registered
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct OtpRequestResponse {
+    #[serde(rename = "expiresInSeconds")]
+    pub expires_in_seconds: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RefreshRequest {
+    #[serde(rename = "refreshToken")]
+    pub refresh_token: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TokenResponse {
+    #[serde(rename = "accessToken")]
+    pub access_token: String,
+    #[serde(rename = "tokenType")]
+    pub token_type: String,
+    #[serde(rename = "expiresIn")]
+    pub expires_in: i64,
+    // Single use: POST /auth/refresh returns a new one with the next access token
+    #[serde(rename = "refreshToken")]
+    pub refresh_token: String,
+    #[serde(rename = "refreshTokenExpiresAt")]
+    #[schema(value_type = String, format = "date-time")]
+    pub refresh_token_expires_at: DateTime<Utc>,
+    pub user: User,
+}
+
+// Payload of the signed access token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccessTokenClaims {
+    // The user ID
+    pub sub: String,
+    pub iat: i64,
+    pub exp: i64,
+}
+
+// Only a SHA-256 hash of the token is stored; a rotated token keeps the ID of the one that replaced it
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct RefreshToken {
+    pub id: u32,
+    pub user_id: u32,
+    pub token_hash: String,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub replaced_by: Option<u32>,
+}
+
+// A one-time login code; only its hash is stored
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct LoginOtp {
+    pub id: u32,
+    pub user_id: u32,
+    pub code_hash: String,
+    pub attempts: u32,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub consumed_at: Option<DateTime<Utc>>,
+}
+
+// Database models for internal use
+#[derive(Debug, Clone)]
+pub struct RefreshTokenDb {
+    pub id: u32,
+    pub user_id: u32,
+    pub token_hash: String,
+    pub expires_at: String,
+    pub created_at: String,
+    pub revoked_at: Option<String>,
+    pub replaced_by: Option<u32>,
+}
+
+impl RefreshTokenDb {
+    pub fn into_domain(self) -> Result<RefreshToken, String> {
+        Ok(RefreshToken {
+            id: self.id,
+            user_id: self.user_id,
+            token_hash: self.token_hash,
+            expires_at: parse_date("expires_at", &self.expires_at)?,
+            created_at: parse_date("created_at", &self.created_at)?,
+            revoked_at: self.revoked_at.as_deref().map(|date| parse_date("revoked_at", date)).transpose()?,
+            replaced_by: self.replaced_by,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LoginOtpDb {
+    pub id: u32,
+    pub user_id: u32,
+    pub code_hash: String,
+    pub attempts: u32,
+    pub expires_at: String,
+    pub created_at: String,
+    pub consumed_at: Option<String>,
+}
+
+impl LoginOtpDb {
+    pub fn into_domain(self) -> Result<LoginOtp, String> {
+        Ok(LoginOtp {
+            id: self.id,
+            user_id: self.user_id,
+            code_hash: self.code_hash,
+            attempts: self.attempts,
+            expires_at: parse_date("expires_at", &self.expires_at)?,
+            created_at: parse_date("created_at", &self.created_at)?,
+            consumed_at: self.consumed_at.as_deref().map(|date| parse_date("consumed_at", date)).transpose()?,
+        })
+    }
+}
+
+fn parse_date(field: &str, value: &str) -> Result<DateTime<Utc>, String> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|date| date.with_timezone(&Utc))
+        .map_err(|e| format!("Invalid {} date: {}", field, e))
+}
+
+// Delivers one-time login codes to the email address or phone number they were requested for
+pub trait OtpSender {
+    fn send(&self, destination: &str, code: &str) -> Result<(), String>;
+}
\ No newline at end of file
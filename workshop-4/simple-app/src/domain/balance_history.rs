@@ -0,0 +1,105 @@
+use chrono::{DateTime, Datelike, Duration, Months, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use super::hold::PointBalance;
+
+// Most periods a single series request may return
+pub const MAX_SERIES_POINTS: usize = 366;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum BalanceInterval {
+    Day,
+    // Monday to Sunday
+    Week,
+    Month,
+}
+
+impl std::fmt::Display for BalanceInterval {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BalanceInterval::Day => write!(f, "day"),
+            BalanceInterval::Week => write!(f, "week"),
+            BalanceInterval::Month => write!(f, "month"),
+        }
+    }
+}
+
+impl std::str::FromStr for BalanceInterval {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "day" => Ok(BalanceInterval::Day),
+            "week" => Ok(BalanceInterval::Week),
+            "month" => Ok(BalanceInterval::Month),
+            _ => Err(format!("Invalid interval: {}", s)),
+        }
+    }
+}
+
+impl BalanceInterval {
+    // First day of the period that contains `date`
+    pub fn period_start(&self, date: NaiveDate) -> NaiveDate {
+        match self {
+            BalanceInterval::Day => date,
+            BalanceInterval::Week => date - Duration::days(date.weekday().num_days_from_monday() as i64),
+            BalanceInterval::Month => date.with_day(1).unwrap_or(date),
+        }
+    }
+
+    pub fn next_period_start(&self, start: NaiveDate) -> Option<NaiveDate> {
+        match self {
+            BalanceInterval::Day => start.checked_add_signed(Duration::days(1)),
+            BalanceInterval::Week => start.checked_add_signed(Duration::days(7)),
+            BalanceInterval::Month => start.checked_add_months(Months::new(1)),
+        }
+    }
+}
+
+// The ledger balance as of a past instant; holds are not kept historically, so only the ledger side is known
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct HistoricalBalance {
+    #[serde(rename = "userId")]
+    pub user_id: u32,
+    #[serde(rename = "asOf")]
+    #[schema(value_type = String, format = "date-time")]
+    pub as_of: DateTime<Utc>,
+    #[serde(rename = "ledgerBalance")]
+    pub ledger_balance: u32,
+    // The last entry counted; empty if the user had no entries yet
+    #[serde(rename = "lastSequence")]
+    pub last_sequence: Option<u32>,
+}
+
+// GET /users/{id}/balance answers with the current balance, or the historical one when `at` is given
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum BalanceResponse {
+    Current(PointBalance),
+    Historical(HistoricalBalance),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BalanceSeriesPoint {
+    #[serde(rename = "periodStart")]
+    #[schema(value_type = String, format = "date")]
+    pub period_start: NaiveDate,
+    // End of the period, or `to` for a period that runs past it
+    #[serde(rename = "asOf")]
+    #[schema(value_type = String, format = "date-time")]
+    pub as_of: DateTime<Utc>,
+    pub balance: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BalanceSeriesResponse {
+    #[serde(rename = "userId")]
+    pub user_id: u32,
+    pub interval: BalanceInterval,
+    #[schema(value_type = String, format = "date-time")]
+    pub from: DateTime<Utc>,
+    #[schema(value_type = String, format = "date-time")]
+    pub to: DateTime<Utc>,
+    pub data: Vec<BalanceSeriesPoint>,
+}
\ No newline at end of file
@@ -0,0 +1,324 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use super::transfer::{TransferStatus, CreateTransferRequest};
+
+pub const MAX_BATCH_ITEMS: usize = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum BatchMode {
+    // Every item is executed in one transaction; one failure rolls all of them back
+    #[default]
+    AllOrNothing,
+    // Items are executed one by one; failures are reported per item
+    BestEffort,
+}
+
+impl std::fmt::Display for BatchMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BatchMode::AllOrNothing => write!(f, "all_or_nothing"),
+            BatchMode::BestEffort => write!(f, "best_effort"),
+        }
+    }
+}
+
+impl std::str::FromStr for BatchMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "all_or_nothing" => Ok(BatchMode::AllOrNothing),
+            "best_effort" => Ok(BatchMode::BestEffort),
+            _ => Err(format!("Invalid batch mode: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum BatchStatus {
+    Pending,
+    Processing,
+    Completed,
+    Failed,
+}
+
+impl std::fmt::Display for BatchStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BatchStatus::Pending => write!(f, "pending"),
+            BatchStatus::Processing => write!(f, "processing"),
+            BatchStatus::Completed => write!(f, "completed"),
+            BatchStatus::Failed => write!(f, "failed"),
+        }
+    }
+}
+
+impl std::str::FromStr for BatchStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "pending" => Ok(BatchStatus::Pending),
+            "processing" => Ok(BatchStatus::Processing),
+            "completed" => Ok(BatchStatus::Completed),
+            "failed" => Ok(BatchStatus::Failed),
+            _ => Err(format!("Invalid batch status: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum BatchItemStatus {
+    Pending,
+    Succeeded,
+    Failed,
+    // Not executed because another item of an all-or-nothing batch failed
+    Skipped,
+}
+
+impl std::fmt::Display for BatchItemStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BatchItemStatus::Pending => write!(f, "pending"),
+            BatchItemStatus::Succeeded => write!(f, "succeeded"),
+            BatchItemStatus::Failed => write!(f, "failed"),
+            BatchItemStatus::Skipped => write!(f, "skipped"),
+        }
+    }
+}
+
+impl std::str::FromStr for BatchItemStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "pending" => Ok(BatchItemStatus::Pending),
+            "succeeded" => Ok(BatchItemStatus::Succeeded),
+            "failed" => Ok(BatchItemStatus::Failed),
+            "skipped" => Ok(BatchItemStatus::Skipped),
+            _ => Err(format!("Invalid batch item status: {}", s)),
+        }
+    }
+}
+
+// One line of a batch: the requested transfer and what became of it
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BatchItem {
+    pub index: u32,
+    #[serde(rename = "fromUserId")]
+    pub from_user_id: u32,
+    #[serde(rename = "toUserId")]
+    pub to_user_id: u32,
+    pub amount: u32,
+    pub note: Option<String>,
+    #[serde(rename = "executeAt", skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>, format = "date-time")]
+    pub execute_at: Option<DateTime<Utc>>,
+    pub status: BatchItemStatus,
+    #[serde(rename = "transferIdemKey")]
+    pub transfer_idem_key: Option<String>,
+    #[serde(rename = "transferStatus")]
+    pub transfer_status: Option<TransferStatus>,
+    #[serde(rename = "errorCode")]
+    pub error_code: Option<String>,
+    #[serde(rename = "errorMessage")]
+    pub error_message: Option<String>,
+}
+
+impl BatchItem {
+    pub fn new(index: u32, request: CreateTransferRequest) -> Self {
+        Self {
+            index,
+            from_user_id: request.from_user_id,
+            to_user_id: request.to_user_id,
+            amount: request.amount,
+            note: request.note,
+            execute_at: request.execute_at,
+            status: BatchItemStatus::Pending,
+            transfer_idem_key: None,
+            transfer_status: None,
+            error_code: None,
+            error_message: None,
+        }
+    }
+
+    pub fn request(&self) -> CreateTransferRequest {
+        CreateTransferRequest {
+            from_user_id: self.from_user_id,
+            to_user_id: self.to_user_id,
+            amount: self.amount,
+            note: self.note.clone(),
+            execute_at: self.execute_at,
+        }
+    }
+
+    pub fn fail(&mut self, error_code: &str, error_message: String) {
+        self.status = BatchItemStatus::Failed;
+        self.error_code = Some(error_code.to_string());
+        self.error_message = Some(error_message);
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TransferBatch {
+    #[serde(rename = "batchId")]
+    pub batch_id: String,
+    #[serde(rename = "idempotencyKey")]
+    pub idempotency_key: String,
+    #[serde(skip)]
+    pub request_fingerprint: String,
+    pub mode: BatchMode,
+    pub status: BatchStatus,
+    #[serde(rename = "totalItems")]
+    pub total_items: u32,
+    #[serde(rename = "succeededItems")]
+    pub succeeded_items: u32,
+    #[serde(rename = "failedItems")]
+    pub failed_items: u32,
+    #[serde(rename = "createdAt")]
+    #[schema(value_type = String, format = "date-time")]
+    pub created_at: DateTime<Utc>,
+    #[serde(rename = "updatedAt")]
+    #[schema(value_type = String, format = "date-time")]
+    pub updated_at: DateTime<Utc>,
+    #[serde(rename = "completedAt")]
+    #[schema(value_type = Option<String>, format = "date-time")]
+    pub completed_at: Option<DateTime<Utc>>,
+    pub items: Vec<BatchItem>,
+}
+
+impl TransferBatch {
+    // Recomputes the progress counters from the item statuses
+    pub fn refresh_counts(&mut self) {
+        self.succeeded_items = self.items.iter().filter(|item| item.status == BatchItemStatus::Succeeded).count() as u32;
+        self.failed_items = self.items.iter().filter(|item| item.status == BatchItemStatus::Failed).count() as u32;
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CreateBatchRequest {
+    #[serde(default)]
+    pub mode: BatchMode,
+    pub items: Vec<CreateTransferRequest>,
+}
+
+impl CreateBatchRequest {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.items.is_empty() {
+            return Err("Batch must contain at least one item".to_string());
+        }
+
+        if self.items.len() > MAX_BATCH_ITEMS {
+            return Err(format!("Batch cannot exceed {} items", MAX_BATCH_ITEMS));
+        }
+
+        Ok(())
+    }
+}
+
+// Database model for internal use
+#[derive(Debug, Clone)]
+pub struct TransferBatchDb {
+    pub batch_id: String,
+    pub idempotency_key: String,
+    pub request_fingerprint: String,
+    pub mode: String,
+    pub status: String,
+    pub total_items: u32,
+    pub succeeded_items: u32,
+    pub failed_items: u32,
+    pub created_at: String,
+    pub updated_at: String,
+    pub completed_at: Option<String>,
+}
+
+// Database model for internal use
+#[derive(Debug, Clone)]
+pub struct BatchItemDb {
+    pub item_index: u32,
+    pub from_user_id: u32,
+    pub to_user_id: u32,
+    pub amount: u32,
+    pub note: Option<String>,
+    pub execute_at: Option<String>,
+    pub status: String,
+    pub transfer_idem_key: Option<String>,
+    pub transfer_status: Option<String>,
+    pub error_code: Option<String>,
+    pub error_message: Option<String>,
+}
+
+impl BatchItemDb {
+    pub fn into_domain(self) -> Result<BatchItem, String> {
+        let status = self.status.parse::<BatchItemStatus>()?;
+
+        let transfer_status = match self.transfer_status {
+            Some(transfer_status) => Some(transfer_status.parse::<TransferStatus>()?),
+            None => None,
+        };
+
+        let execute_at = if let Some(execute_str) = self.execute_at {
+            Some(DateTime::parse_from_rfc3339(&execute_str)
+                .map_err(|e| format!("Invalid execute_at date: {}", e))?
+                .with_timezone(&Utc))
+        } else {
+            None
+        };
+
+        Ok(BatchItem {
+            index: self.item_index,
+            from_user_id: self.from_user_id,
+            to_user_id: self.to_user_id,
+            amount: self.amount,
+            note: self.note,
+            execute_at,
+            status,
+            transfer_idem_key: self.transfer_idem_key,
+            transfer_status,
+            error_code: self.error_code,
+            error_message: self.error_message,
+        })
+    }
+}
+
+impl TransferBatchDb {
+    pub fn into_domain(self, items: Vec<BatchItem>) -> Result<TransferBatch, String> {
+        let mode = self.mode.parse::<BatchMode>()?;
+        let status = self.status.parse::<BatchStatus>()?;
+
+        let created_at = DateTime::parse_from_rfc3339(&self.created_at)
+            .map_err(|e| format!("Invalid created_at date: {}", e))?
+            .with_timezone(&Utc);
+
+        let updated_at = DateTime::parse_from_rfc3339(&self.updated_at)
+            .map_err(|e| format!("Invalid updated_at date: {}", e))?
+            .with_timezone(&Utc);
+
+        let completed_at = if let Some(completed_str) = self.completed_at {
+            Some(DateTime::parse_from_rfc3339(&completed_str)
+                .map_err(|e| format!("Invalid completed_at date: {}", e))?
+                .with_timezone(&Utc))
+        } else {
+            None
+        };
+
+        Ok(TransferBatch {
+            batch_id: self.batch_id,
+            idempotency_key: self.idempotency_key,
+            request_fingerprint: self.request_fingerprint,
+            mode,
+            status,
+            total_items: self.total_items,
+            succeeded_items: self.succeeded_items,
+            failed_items: self.failed_items,
+            created_at,
+            updated_at,
+            completed_at,
+            items,
+        })
+    }
+}
\ No newline at end of file
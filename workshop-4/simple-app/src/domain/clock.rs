@@ -0,0 +1,15 @@
+use chrono::{DateTime, Utc};
+
+// Source of "now" for anything time-dependent (schedules, expiries) so it can be driven by a fake clock
+pub trait Clock {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
@@ -0,0 +1,170 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum HoldStatus {
+    Active,
+    Captured,
+    Released,
+    Expired,
+}
+
+impl std::fmt::Display for HoldStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HoldStatus::Active => write!(f, "active"),
+            HoldStatus::Captured => write!(f, "captured"),
+            HoldStatus::Released => write!(f, "released"),
+            HoldStatus::Expired => write!(f, "expired"),
+        }
+    }
+}
+
+impl std::str::FromStr for HoldStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "active" => Ok(HoldStatus::Active),
+            "captured" => Ok(HoldStatus::Captured),
+            "released" => Ok(HoldStatus::Released),
+            "expired" => Ok(HoldStatus::Expired),
+            _ => Err(format!("Invalid hold status: {}", s)),
+        }
+    }
+}
+
+// Points reserved for a merchant or checkout; they leave the available balance until captured, released or expired
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PointHold {
+    #[serde(rename = "holdId")]
+    pub hold_id: String,
+    #[serde(rename = "userId")]
+    pub user_id: u32,
+    pub amount: u32,
+    // Set on capture; the rest of the hold is released
+    #[serde(rename = "capturedAmount")]
+    pub captured_amount: u32,
+    pub status: HoldStatus,
+    // Merchant's own reference, e.g. an order number
+    pub reference: Option<String>,
+    // The redeem entry written on capture
+    #[serde(rename = "ledgerEntryId")]
+    pub ledger_entry_id: Option<u32>,
+    #[serde(rename = "expiresAt")]
+    #[schema(value_type = String, format = "date-time")]
+    pub expires_at: DateTime<Utc>,
+    #[serde(rename = "createdAt")]
+    #[schema(value_type = String, format = "date-time")]
+    pub created_at: DateTime<Utc>,
+    #[serde(rename = "updatedAt")]
+    #[schema(value_type = String, format = "date-time")]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl PointHold {
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at <= now
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CreateHoldRequest {
+    // Taken from the access token; when sent it must name the signed-in user
+    #[serde(rename = "userId", default)]
+    pub user_id: u32,
+    pub amount: u32,
+    pub reference: Option<String>,
+    // Defaults to 24 hours from now
+    #[serde(rename = "expiresAt")]
+    #[schema(value_type = Option<String>, format = "date-time")]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl CreateHoldRequest {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.amount == 0 {
+            return Err("Amount must be greater than 0".to_string());
+        }
+
+        if let Some(reference) = &self.reference
+            && reference.len() > 128
+        {
+            return Err("Reference cannot exceed 128 characters".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CaptureHoldRequest {
+    // Defaults to the full held amount; anything less releases the remainder
+    pub amount: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct HoldListResponse {
+    pub data: Vec<PointHold>,
+}
+
+// Ledger balance is what the ledger says; available balance is what the user can still spend
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PointBalance {
+    #[serde(rename = "userId")]
+    pub user_id: u32,
+    #[serde(rename = "ledgerBalance")]
+    pub ledger_balance: u32,
+    #[serde(rename = "heldPoints")]
+    pub held_points: u32,
+    #[serde(rename = "availableBalance")]
+    pub available_balance: u32,
+}
+
+// Database model for internal use
+#[derive(Debug, Clone)]
+pub struct PointHoldDb {
+    pub hold_id: String,
+    pub user_id: u32,
+    pub amount: u32,
+    pub captured_amount: u32,
+    pub status: String,
+    pub reference: Option<String>,
+    pub ledger_entry_id: Option<u32>,
+    pub expires_at: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl PointHoldDb {
+    pub fn into_domain(self) -> Result<PointHold, String> {
+        let status = self.status.parse::<HoldStatus>()?;
+
+        let expires_at = DateTime::parse_from_rfc3339(&self.expires_at)
+            .map_err(|e| format!("Invalid expires_at date: {}", e))?
+            .with_timezone(&Utc);
+
+        let created_at = DateTime::parse_from_rfc3339(&self.created_at)
+            .map_err(|e| format!("Invalid created_at date: {}", e))?
+            .with_timezone(&Utc);
+
+        let updated_at = DateTime::parse_from_rfc3339(&self.updated_at)
+            .map_err(|e| format!("Invalid updated_at date: {}", e))?
+            .with_timezone(&Utc);
+
+        Ok(PointHold {
+            hold_id: self.hold_id,
+            user_id: self.user_id,
+            amount: self.amount,
+            captured_amount: self.captured_amount,
+            status,
+            reference: self.reference,
+            ledger_entry_id: self.ledger_entry_id,
+            expires_at,
+            created_at,
+            updated_at,
+        })
+    }
+}
\ No newline at end of file
@@ -0,0 +1,59 @@
+use chrono::{DateTime, Utc};
+
+// Client-supplied Idempotency-Key remembered for a retention window, pointing at the transfer it created.
+// Keys belong to the sender, so two senders may pick the same one.
+#[derive(Debug, Clone)]
+pub struct IdempotencyRecord {
+    pub from_user_id: u32,
+    pub key: String,
+    pub request_fingerprint: String,
+    pub transfer_idem_key: String,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl IdempotencyRecord {
+    pub fn validate_key(key: &str) -> Result<(), String> {
+        if key.len() < 8 || key.len() > 128 {
+            return Err("Idempotency key must be between 8 and 128 characters".to_string());
+        }
+
+        Ok(())
+    }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at <= now
+    }
+}
+
+// Database model for internal use
+#[derive(Debug, Clone)]
+pub struct IdempotencyRecordDb {
+    pub from_user_id: u32,
+    pub key: String,
+    pub request_fingerprint: String,
+    pub transfer_idem_key: String,
+    pub created_at: String,
+    pub expires_at: String,
+}
+
+impl IdempotencyRecordDb {
+    pub fn into_domain(self) -> Result<IdempotencyRecord, String> {
+        let created_at = DateTime::parse_from_rfc3339(&self.created_at)
+            .map_err(|e| format!("Invalid created_at date: {}", e))?
+            .with_timezone(&Utc);
+
+        let expires_at = DateTime::parse_from_rfc3339(&self.expires_at)
+            .map_err(|e| format!("Invalid expires_at date: {}", e))?
+            .with_timezone(&Utc);
+
+        Ok(IdempotencyRecord {
+            from_user_id: self.from_user_id,
+            key: self.key,
+            request_fingerprint: self.request_fingerprint,
+            transfer_idem_key: self.transfer_idem_key,
+            created_at,
+            expires_at,
+        })
+    }
+}
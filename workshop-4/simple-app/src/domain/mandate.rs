@@ -0,0 +1,355 @@
+use chrono::{DateTime, Duration, Months, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum MandateFrequency {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+impl MandateFrequency {
+    // Occurrences are always counted from the start so monthly runs on the 31st don't drift to the 28th
+    pub fn occurrence(&self, start_at: DateTime<Utc>, index: u32) -> Option<DateTime<Utc>> {
+        match self {
+            MandateFrequency::Daily => start_at.checked_add_signed(Duration::days(index as i64)),
+            MandateFrequency::Weekly => start_at.checked_add_signed(Duration::weeks(index as i64)),
+            MandateFrequency::Monthly => start_at.checked_add_months(Months::new(index)),
+        }
+    }
+}
+
+impl std::fmt::Display for MandateFrequency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MandateFrequency::Daily => write!(f, "daily"),
+            MandateFrequency::Weekly => write!(f, "weekly"),
+            MandateFrequency::Monthly => write!(f, "monthly"),
+        }
+    }
+}
+
+impl std::str::FromStr for MandateFrequency {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "daily" => Ok(MandateFrequency::Daily),
+            "weekly" => Ok(MandateFrequency::Weekly),
+            "monthly" => Ok(MandateFrequency::Monthly),
+            _ => Err(format!("Invalid mandate frequency: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum MandateStatus {
+    Active,
+    Paused,
+    Completed,
+    Cancelled,
+}
+
+impl std::fmt::Display for MandateStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MandateStatus::Active => write!(f, "active"),
+            MandateStatus::Paused => write!(f, "paused"),
+            MandateStatus::Completed => write!(f, "completed"),
+            MandateStatus::Cancelled => write!(f, "cancelled"),
+        }
+    }
+}
+
+impl std::str::FromStr for MandateStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "active" => Ok(MandateStatus::Active),
+            "paused" => Ok(MandateStatus::Paused),
+            "completed" => Ok(MandateStatus::Completed),
+            "cancelled" => Ok(MandateStatus::Cancelled),
+            _ => Err(format!("Invalid mandate status: {}", s)),
+        }
+    }
+}
+
+// Standing instruction to send `amount` points from one user to another on a schedule
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TransferMandate {
+    pub id: u32,
+    #[serde(rename = "fromUserId")]
+    pub from_user_id: u32,
+    #[serde(rename = "toUserId")]
+    pub to_user_id: u32,
+    pub amount: u32,
+    pub note: Option<String>,
+    pub frequency: MandateFrequency,
+    #[serde(rename = "startAt")]
+    #[schema(value_type = String, format = "date-time")]
+    pub start_at: DateTime<Utc>,
+    #[serde(rename = "endAt")]
+    #[schema(value_type = Option<String>, format = "date-time")]
+    pub end_at: Option<DateTime<Utc>>,
+    #[serde(rename = "maxRuns")]
+    pub max_runs: Option<u32>,
+    #[serde(rename = "runCount")]
+    pub run_count: u32,
+    // Index of the next occurrence counted from startAt; also part of each run's idempotency key
+    #[serde(skip)]
+    pub next_occurrence: u32,
+    #[serde(rename = "nextRunAt")]
+    #[schema(value_type = Option<String>, format = "date-time")]
+    pub next_run_at: Option<DateTime<Utc>>,
+    pub status: MandateStatus,
+    #[serde(rename = "lastTransferIdemKey")]
+    pub last_transfer_idem_key: Option<String>,
+    #[serde(rename = "lastError")]
+    pub last_error: Option<String>,
+    #[serde(rename = "createdAt")]
+    #[schema(value_type = String, format = "date-time")]
+    pub created_at: DateTime<Utc>,
+    #[serde(rename = "updatedAt")]
+    #[schema(value_type = String, format = "date-time")]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl TransferMandate {
+    // Idempotency-Key for one occurrence, so a run retried after a restart replays the same transfer
+    pub fn run_idempotency_key(&self) -> String {
+        format!("mandate-{}-run-{}", self.id, self.next_occurrence)
+    }
+
+    // Moves to the first occurrence at or after `not_before`, or completes the mandate if none is left
+    pub fn schedule_next(&mut self, mut occurrence: u32, not_before: Option<DateTime<Utc>>) {
+        let max_runs_reached = self.max_runs.is_some_and(|max_runs| self.run_count >= max_runs);
+
+        let mut next_run_at = self.frequency.occurrence(self.start_at, occurrence);
+        if let Some(not_before) = not_before {
+            while let Some(run_at) = next_run_at
+                && run_at < not_before
+            {
+                occurrence += 1;
+                next_run_at = self.frequency.occurrence(self.start_at, occurrence);
+            }
+        }
+
+        let past_end = match (next_run_at, self.end_at) {
+            (Some(run_at), Some(end_at)) => run_at > end_at,
+            (None, _) => true,
+            _ => false,
+        };
+
+        self.next_occurrence = occurrence;
+        if max_runs_reached || past_end {
+            self.status = MandateStatus::Completed;
+            self.next_run_at = None;
+        } else {
+            self.next_run_at = next_run_at;
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CreateMandateRequest {
+    // Taken from the access token; when sent it must name the signed-in user
+    #[serde(rename = "fromUserId", default)]
+    pub from_user_id: u32,
+    #[serde(rename = "toUserId")]
+    pub to_user_id: u32,
+    pub amount: u32,
+    pub note: Option<String>,
+    pub frequency: MandateFrequency,
+    // First run; defaults to now
+    #[serde(rename = "startAt")]
+    #[schema(value_type = Option<String>, format = "date-time")]
+    pub start_at: Option<DateTime<Utc>>,
+    #[serde(rename = "endAt")]
+    #[schema(value_type = Option<String>, format = "date-time")]
+    pub end_at: Option<DateTime<Utc>>,
+    #[serde(rename = "maxRuns")]
+    pub max_runs: Option<u32>,
+}
+
+impl CreateMandateRequest {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.amount == 0 {
+            return Err("Amount must be greater than 0".to_string());
+        }
+
+        if self.from_user_id == self.to_user_id {
+            return Err("Cannot transfer to the same user".to_string());
+        }
+
+        if let Some(note) = &self.note
+            && note.len() > 512
+        {
+            return Err("Note cannot exceed 512 characters".to_string());
+        }
+
+        if self.max_runs == Some(0) {
+            return Err("maxRuns must be greater than 0".to_string());
+        }
+
+        if let (Some(start_at), Some(end_at)) = (self.start_at, self.end_at)
+            && end_at < start_at
+        {
+            return Err("endAt cannot be before startAt".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+// Changes the terms of future runs; runs already made are not touched
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct UpdateMandateRequest {
+    pub amount: Option<u32>,
+    pub note: Option<String>,
+    #[serde(rename = "endAt")]
+    #[schema(value_type = Option<String>, format = "date-time")]
+    pub end_at: Option<DateTime<Utc>>,
+    #[serde(rename = "maxRuns")]
+    pub max_runs: Option<u32>,
+}
+
+impl UpdateMandateRequest {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.amount == Some(0) {
+            return Err("Amount must be greater than 0".to_string());
+        }
+
+        if let Some(note) = &self.note
+            && note.len() > 512
+        {
+            return Err("Note cannot exceed 512 characters".to_string());
+        }
+
+        if self.max_runs == Some(0) {
+            return Err("maxRuns must be greater than 0".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MandateListResponse {
+    pub data: Vec<TransferMandate>,
+    pub total: u32,
+}
+
+// The transfer made for one occurrence of a mandate. There is at most one per (mandate, occurrence),
+// however long after the fact a run is retried
+#[derive(Debug, Clone)]
+pub struct MandateRun {
+    pub mandate_id: u32,
+    pub occurrence: u32,
+    pub transfer_idem_key: String,
+    pub created_at: DateTime<Utc>,
+}
+
+// Database model for internal use
+#[derive(Debug, Clone)]
+pub struct TransferMandateDb {
+    pub id: u32,
+    pub from_user_id: u32,
+    pub to_user_id: u32,
+    pub amount: u32,
+    pub note: Option<String>,
+    pub frequency: String,
+    pub start_at: String,
+    pub end_at: Option<String>,
+    pub max_runs: Option<u32>,
+    pub run_count: u32,
+    pub next_occurrence: u32,
+    pub next_run_at: Option<String>,
+    pub status: String,
+    pub last_transfer_idem_key: Option<String>,
+    pub last_error: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl TransferMandateDb {
+    pub fn into_domain(self) -> Result<TransferMandate, String> {
+        let frequency = self.frequency.parse::<MandateFrequency>()?;
+        let status = self.status.parse::<MandateStatus>()?;
+
+        let start_at = DateTime::parse_from_rfc3339(&self.start_at)
+            .map_err(|e| format!("Invalid start_at date: {}", e))?
+            .with_timezone(&Utc);
+
+        let end_at = if let Some(end_str) = self.end_at {
+            Some(DateTime::parse_from_rfc3339(&end_str)
+                .map_err(|e| format!("Invalid end_at date: {}", e))?
+                .with_timezone(&Utc))
+        } else {
+            None
+        };
+
+        let next_run_at = if let Some(next_str) = self.next_run_at {
+            Some(DateTime::parse_from_rfc3339(&next_str)
+                .map_err(|e| format!("Invalid next_run_at date: {}", e))?
+                .with_timezone(&Utc))
+        } else {
+            None
+        };
+
+        let created_at = DateTime::parse_from_rfc3339(&self.created_at)
+            .map_err(|e| format!("Invalid created_at date: {}", e))?
+            .with_timezone(&Utc);
+
+        let updated_at = DateTime::parse_from_rfc3339(&self.updated_at)
+            .map_err(|e| format!("Invalid updated_at date: {}", e))?
+            .with_timezone(&Utc);
+
+        Ok(TransferMandate {
+            id: self.id,
+            from_user_id: self.from_user_id,
+            to_user_id: self.to_user_id,
+            amount: self.amount,
+            note: self.note,
+            frequency,
+            start_at,
+            end_at,
+            max_runs: self.max_runs,
+            run_count: self.run_count,
+            next_occurrence: self.next_occurrence,
+            next_run_at,
+            status,
+            last_transfer_idem_key: self.last_transfer_idem_key,
+            last_error: self.last_error,
+            created_at,
+            updated_at,
+        })
+    }
+}
+
+// Database model for internal use
+#[derive(Debug, Clone)]
+pub struct MandateRunDb {
+    pub mandate_id: u32,
+    pub occurrence: u32,
+    pub transfer_idem_key: String,
+    pub created_at: String,
+}
+
+impl MandateRunDb {
+    pub fn into_domain(self) -> Result<MandateRun, String> {
+        let created_at = DateTime::parse_from_rfc3339(&self.created_at)
+            .map_err(|e| format!("Invalid created_at date: {}", e))?
+            .with_timezone(&Utc);
+
+        Ok(MandateRun {
+            mandate_id: self.mandate_id,
+            occurrence: self.occurrence,
+            transfer_idem_key: self.transfer_idem_key,
+            created_at,
+        })
+    }
+}
\ No newline at end of file
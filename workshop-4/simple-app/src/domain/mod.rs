@@ -2,8 +2,34 @@ pub mod user;
 pub mod repository;
 pub mod transfer;
 pub mod point_ledger;
+pub mod idempotency;
+pub mod clock;
+pub mod mandate;
+pub mod batch;
+pub mod transfer_limit;
+pub mod hold;
+pub mod points;
+pub mod adjustment;
+pub mod reconciliation;
+pub mod balance_history;
+pub mod point_lot;
+pub mod tier;
+pub mod auth;
 
-pub use user::{User, CreateUserRequest, UpdateUserRequest};
-pub use repository::{UserRepository, TransferRepository, PointLedgerRepository};
-pub use transfer::{Transfer, TransferStatus, CreateTransferRequest, TransferCreateResponse, TransferGetResponse, TransferListResponse, TransferDb};
-pub use point_ledger::{PointLedger, EventType, PointLedgerDb};
\ No newline at end of file
+pub use user::{User, UserRole, CreateUserRequest, UpdateUserRequest};
+pub use repository::{UserRepository, TransferRepository, PointLedgerRepository, IdempotencyRepository, MandateRepository, BatchRepository, TransferLimitRepository, HoldRepository, AdjustmentRepository, TierRepository, AuthRepository, UnitOfWork, UnitOfWorkFactory};
+pub use transfer::{Transfer, TransferStatus, TransferType, SplitRecipient, CreateSplitTransferRequest, TransferSplitResponse, CreateTransferRequest, TransferCreateResponse, TransferGetResponse, TransferListResponse, TransferDb, ReverseTransferRequest, TransferReversal, TransferReverseResponse, CancelTransferRequest, TransferCancelResponse, AcceptTransferRequest, DeclineTransferRequest, TransferAcceptanceResponse};
+pub use point_ledger::{PointLedger, EventType, LedgerFilter, LedgerTotals, LedgerListResponse, LedgerCounterparty, LedgerEntryDetail, PointLedgerDb, GENESIS_HASH};
+pub use idempotency::{IdempotencyRecord, IdempotencyRecordDb};
+pub use clock::{Clock, SystemClock};
+pub use mandate::{TransferMandate, MandateFrequency, MandateStatus, CreateMandateRequest, UpdateMandateRequest, MandateListResponse, TransferMandateDb, MandateRun, MandateRunDb};
+pub use batch::{TransferBatch, BatchItem, BatchMode, BatchStatus, BatchItemStatus, CreateBatchRequest, TransferBatchDb, BatchItemDb};
+pub use transfer_limit::{TransferLimits, UpdateTransferLimitsRequest, TransferLimitsListResponse, TransferLimitsDb, TransferLimitViolation};
+pub use hold::{PointHold, HoldStatus, CreateHoldRequest, CaptureHoldRequest, HoldListResponse, PointBalance, PointHoldDb};
+pub use points::{PointsOperationRequest, PointsOperationResponse};
+pub use adjustment::{BalanceAdjustment, AdjustmentStatus, AdjustmentAction, AdjustmentAuditEntry, CreateAdjustmentRequest, ReviewAdjustmentRequest, AdjustmentResponse, AdjustmentListResponse, BalanceAdjustmentDb, AdjustmentAuditEntryDb};
+pub use reconciliation::{DiscrepancyKind, Discrepancy, ReconciliationReport, BrokenLink, LedgerChainReport};
+pub use balance_history::{BalanceInterval, HistoricalBalance, BalanceResponse, BalanceSeriesPoint, BalanceSeriesResponse, MAX_SERIES_POINTS};
+pub use point_lot::{PointLot, ExpiringPoints, ExpiringPointsResponse, PointLotDb};
+pub use tier::{MembershipTier, TierChangeReason, TierChange, TierBenefits, TierListResponse, UserTierStatus, SetTierRequest, TierHistoryResponse, TierEvaluationReport, TierChangeDb, TIER_QUALIFICATION_MONTHS};
+pub use auth::{RegisterRequest, LoginRequest, OtpRequest, OtpRequestResponse, RefreshRequest, TokenResponse, AccessTokenClaims, RefreshToken, LoginOtp, RefreshTokenDb, LoginOtpDb, OtpSender};
\ No newline at end of file
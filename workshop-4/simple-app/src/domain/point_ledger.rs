@@ -1,8 +1,13 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use utoipa::ToSchema;
+use super::transfer::Transfer;
 
-#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+// prevHash of the first entry in every user's chain
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum EventType {
     TransferOut,
@@ -10,6 +15,8 @@ pub enum EventType {
     Adjust,
     Earn,
     Redeem,
+    // Points that reached the end of their lifetime unspent
+    Expire,
 }
 
 impl std::fmt::Display for EventType {
@@ -20,6 +27,7 @@ impl std::fmt::Display for EventType {
             EventType::Adjust => write!(f, "adjust"),
             EventType::Earn => write!(f, "earn"),
             EventType::Redeem => write!(f, "redeem"),
+            EventType::Expire => write!(f, "expire"),
         }
     }
 }
@@ -34,6 +42,7 @@ impl std::str::FromStr for EventType {
             "adjust" => Ok(EventType::Adjust),
             "earn" => Ok(EventType::Earn),
             "redeem" => Ok(EventType::Redeem),
+            "expire" => Ok(EventType::Expire),
             _ => Err(format!("Invalid event type: {}", s)),
         }
     }
@@ -44,6 +53,8 @@ pub struct PointLedger {
     pub id: u32,
     #[serde(rename = "userId")]
     pub user_id: u32,
+    // Position in the user's ledger, 1, 2, 3, ... with no gaps; balances are built in this order
+    pub sequence: u32,
     pub change: i32,
     #[serde(rename = "balanceAfter")]
     pub balance_after: u32,
@@ -56,6 +67,109 @@ pub struct PointLedger {
     #[serde(rename = "createdAt")]
     #[schema(value_type = String, format = "date-time")]
     pub created_at: DateTime<Utc>,
+    // Client key the entry was posted under (earn and redeem); written with the entry and part of its hash
+    #[serde(rename = "idempotencyKey", skip_serializing_if = "Option::is_none")]
+    pub idempotency_key: Option<String>,
+    // SHA-256 of this entry's content and prevHash; editing any earlier entry breaks every hash after it
+    pub hash: String,
+    // hash of the previous entry in the user's ledger
+    #[serde(rename = "prevHash")]
+    pub prev_hash: String,
+}
+
+impl PointLedger {
+    // Everything but the row id, which is only known after the insert. Entries without an idempotency key
+    // hash exactly as they did before the key was covered
+    pub fn compute_hash(&self, prev_hash: &str) -> String {
+        let mut content = serde_json::json!([
+            self.user_id,
+            self.sequence,
+            self.change,
+            self.balance_after,
+            self.event_type.to_string(),
+            self.transfer_id,
+            self.reference,
+            self.metadata,
+            self.created_at.to_rfc3339(),
+            prev_hash,
+        ]);
+        if let (Some(key), Some(fields)) = (&self.idempotency_key, content.as_array_mut()) {
+            fields.push(serde_json::json!(key));
+        }
+        hex::encode(Sha256::digest(content.to_string().as_bytes()))
+    }
+}
+
+// Narrows a user's ledger history; empty fields match every entry
+#[derive(Debug, Clone, Default)]
+pub struct LedgerFilter {
+    pub event_types: Vec<EventType>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    // Compared with the size of the change, whether it is a credit or a debit
+    pub min_amount: Option<u32>,
+    pub max_amount: Option<u32>,
+}
+
+impl LedgerFilter {
+    pub fn validate(&self) -> Result<(), String> {
+        if let (Some(from), Some(to)) = (self.from, self.to)
+            && from > to
+        {
+            return Err("from cannot be after to".to_string());
+        }
+
+        if let (Some(min_amount), Some(max_amount)) = (self.min_amount, self.max_amount)
+            && min_amount > max_amount
+        {
+            return Err("minAmount cannot be greater than maxAmount".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+// Count and sums over every entry matching a filter, not just the returned page
+#[derive(Debug, Clone, Default)]
+pub struct LedgerTotals {
+    pub total: u32,
+    pub total_in: u64,
+    pub total_out: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct LedgerListResponse {
+    pub data: Vec<PointLedger>,
+    pub page: u32,
+    #[serde(rename = "pageSize")]
+    pub page_size: u32,
+    pub total: u32,
+    // Points credited and debited across all matching entries
+    #[serde(rename = "totalIn")]
+    pub total_in: u64,
+    #[serde(rename = "totalOut")]
+    pub total_out: u64,
+    // Pass as `cursor` to fetch the entries after this page; empty on the last page
+    #[serde(rename = "nextCursor")]
+    pub next_cursor: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct LedgerCounterparty {
+    #[serde(rename = "userId")]
+    pub user_id: u32,
+    #[serde(rename = "firstName")]
+    pub first_name: String,
+    #[serde(rename = "lastName")]
+    pub last_name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct LedgerEntryDetail {
+    pub entry: PointLedger,
+    // The other side of the linked transfer; empty for earn, redeem and adjust entries and for split debits
+    pub counterparty: Option<LedgerCounterparty>,
+    pub transfer: Option<Transfer>,
 }
 
 // Database model for internal use
@@ -63,6 +177,7 @@ pub struct PointLedger {
 pub struct PointLedgerDb {
     pub id: u32,
     pub user_id: u32,
+    pub sequence: u32,
     pub change: i32,
     pub balance_after: u32,
     pub event_type: String,
@@ -70,10 +185,14 @@ pub struct PointLedgerDb {
     pub reference: Option<String>,
     pub metadata: Option<String>,
     pub created_at: String,
+    pub idempotency_key: Option<String>,
+    // Empty only on rows written before the chain existed, or slipped in around the ledger since
+    pub hash: Option<String>,
+    pub prev_hash: Option<String>,
 }
 
 impl PointLedgerDb {
-    pub fn to_domain(self) -> Result<PointLedger, String> {
+    pub fn into_domain(self) -> Result<PointLedger, String> {
         let event_type = self.event_type.parse::<EventType>()
             .map_err(|e| format!("Invalid event type: {}", e))?;
         
@@ -84,6 +203,7 @@ impl PointLedgerDb {
         Ok(PointLedger {
             id: self.id,
             user_id: self.user_id,
+            sequence: self.sequence,
             change: self.change,
             balance_after: self.balance_after,
             event_type,
@@ -91,6 +211,9 @@ impl PointLedgerDb {
             reference: self.reference,
             metadata: self.metadata,
             created_at,
+            idempotency_key: self.idempotency_key,
+            hash: self.hash.unwrap_or_default(),
+            prev_hash: self.prev_hash.unwrap_or_default(),
         })
     }
 }
\ No newline at end of file
@@ -0,0 +1,73 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+// Points from a single credit, spent oldest first; whatever is left when the lot reaches the end of its
+// lifetime is expired
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PointLot {
+    pub id: u32,
+    #[serde(rename = "userId")]
+    pub user_id: u32,
+    // The credit that opened the lot; a transfer's credit opens one for each age of the points it carried
+    #[serde(rename = "ledgerEntryId")]
+    pub ledger_entry_id: u32,
+    pub amount: u32,
+    pub remaining: u32,
+    #[serde(rename = "earnedAt")]
+    #[schema(value_type = String, format = "date-time")]
+    pub earned_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ExpiringPoints {
+    #[serde(rename = "ledgerEntryId")]
+    pub ledger_entry_id: u32,
+    pub points: u32,
+    #[serde(rename = "earnedAt")]
+    #[schema(value_type = String, format = "date-time")]
+    pub earned_at: DateTime<Utc>,
+    #[serde(rename = "expiresAt")]
+    #[schema(value_type = String, format = "date-time")]
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ExpiringPointsResponse {
+    #[serde(rename = "userId")]
+    pub user_id: u32,
+    #[serde(rename = "withinDays")]
+    pub within_days: u32,
+    #[serde(rename = "totalExpiring")]
+    pub total_expiring: u32,
+    // Soonest first
+    pub data: Vec<ExpiringPoints>,
+}
+
+// Database model for internal use
+#[derive(Debug, Clone)]
+pub struct PointLotDb {
+    pub id: u32,
+    pub user_id: u32,
+    pub ledger_entry_id: u32,
+    pub amount: u32,
+    pub remaining: u32,
+    pub earned_at: String,
+}
+
+impl PointLotDb {
+    pub fn into_domain(self) -> Result<PointLot, String> {
+        let earned_at = DateTime::parse_from_rfc3339(&self.earned_at)
+            .map_err(|e| format!("Invalid earned_at date: {}", e))?
+            .with_timezone(&Utc);
+
+        Ok(PointLot {
+            id: self.id,
+            user_id: self.user_id,
+            ledger_entry_id: self.ledger_entry_id,
+            amount: self.amount,
+            remaining: self.remaining,
+            earned_at,
+        })
+    }
+}
\ No newline at end of file
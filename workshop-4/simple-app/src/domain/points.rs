@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use super::point_ledger::PointLedger;
+
+// Points credited (earn) or debited (redeem) outside of a transfer, e.g. for a purchase or a reward
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PointsOperationRequest {
+    pub amount: u32,
+    // Shown as the ledger entry's reference
+    pub reason: String,
+    // The caller's own identifier for the event, e.g. an order or receipt number
+    #[serde(rename = "sourceReference")]
+    pub source_reference: Option<String>,
+    #[schema(value_type = Option<Object>)]
+    pub metadata: Option<serde_json::Value>,
+}
+
+impl PointsOperationRequest {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.amount == 0 {
+            return Err("Amount must be greater than 0".to_string());
+        }
+
+        if self.amount > i32::MAX as u32 {
+            return Err(format!("Amount cannot exceed {}", i32::MAX));
+        }
+
+        if self.reason.trim().is_empty() {
+            return Err("Reason is required".to_string());
+        }
+
+        if self.reason.len() > 255 {
+            return Err("Reason cannot exceed 255 characters".to_string());
+        }
+
+        if let Some(source_reference) = &self.source_reference
+            && source_reference.len() > 128
+        {
+            return Err("Source reference cannot exceed 128 characters".to_string());
+        }
+
+        if let Some(metadata) = &self.metadata
+            && !metadata.is_object()
+        {
+            return Err("Metadata must be a JSON object".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PointsOperationResponse {
+    pub entry: PointLedger,
+    // Extra points from the member's tier earn multiplier, posted as a second earn entry
+    #[serde(rename = "tierBonus", skip_serializing_if = "Option::is_none")]
+    pub tier_bonus: Option<PointLedger>,
+    #[serde(rename = "availableBalance")]
+    pub available_balance: u32,
+}
\ No newline at end of file
@@ -0,0 +1,77 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DiscrepancyKind {
+    // The user's sequence numbers skip or repeat a value
+    SequenceGap,
+    // balance_after is not the previous balance plus change
+    BalanceMismatch,
+    // The replayed balance dropped below zero
+    NegativeBalance,
+    // users.points does not match the user's last balance_after
+    UserPointsMismatch,
+    // The points left in the user's lots do not add up to their balance
+    LotBalanceMismatch,
+    // A transfer's ledger entries are not the out/in legs its status calls for
+    TransferLegMismatch,
+    // A split parent's amount is not the sum of its children
+    SplitTotalMismatch,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Discrepancy {
+    pub kind: DiscrepancyKind,
+    #[serde(rename = "userId", skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<u32>,
+    #[serde(rename = "transferId", skip_serializing_if = "Option::is_none")]
+    pub transfer_id: Option<u32>,
+    #[serde(rename = "entryId", skip_serializing_if = "Option::is_none")]
+    pub entry_id: Option<u32>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ReconciliationReport {
+    #[serde(rename = "generatedAt")]
+    #[schema(value_type = String, format = "date-time")]
+    pub generated_at: DateTime<Utc>,
+    #[serde(rename = "usersChecked")]
+    pub users_checked: u32,
+    #[serde(rename = "entriesChecked")]
+    pub entries_checked: u32,
+    #[serde(rename = "transfersChecked")]
+    pub transfers_checked: u32,
+    // True when no discrepancies were found
+    pub clean: bool,
+    pub discrepancies: Vec<Discrepancy>,
+}
+
+// Where a user's hash chain stops adding up
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BrokenLink {
+    #[serde(rename = "userId")]
+    pub user_id: u32,
+    #[serde(rename = "entryId")]
+    pub entry_id: u32,
+    pub sequence: u32,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct LedgerChainReport {
+    #[serde(rename = "generatedAt")]
+    #[schema(value_type = String, format = "date-time")]
+    pub generated_at: DateTime<Utc>,
+    #[serde(rename = "usersChecked")]
+    pub users_checked: u32,
+    #[serde(rename = "entriesChecked")]
+    pub entries_checked: u32,
+    // True when every user's chain verified end to end
+    pub intact: bool,
+    // The walk stops here; entries after it were not checked
+    #[serde(rename = "firstBrokenLink")]
+    pub first_broken_link: Option<BrokenLink>,
+}
\ No newline at end of file
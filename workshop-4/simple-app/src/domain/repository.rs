@@ -1,29 +1,203 @@
+use std::sync::Arc;
 use async_trait::async_trait;
-use super::user::{User, CreateUserRequest, UpdateUserRequest};
-use super::transfer::{Transfer, CreateTransferRequest, TransferDb};
-use super::point_ledger::{PointLedger, EventType, PointLedgerDb};
+use chrono::{DateTime, Utc};
+use super::user::{User, UserRole, CreateUserRequest, UpdateUserRequest};
+use super::transfer::{Transfer, TransferStatus, CreateTransferRequest, CreateSplitTransferRequest, TransferReversal};
+use super::point_ledger::{PointLedger, EventType, LedgerFilter, LedgerTotals};
+use super::idempotency::IdempotencyRecord;
+use super::mandate::{TransferMandate, CreateMandateRequest, MandateRun};
+use super::batch::{TransferBatch, BatchItem};
+use super::transfer_limit::{TransferLimits, UpdateTransferLimitsRequest};
+use super::hold::PointHold;
+use super::point_lot::PointLot;
+use super::auth::{RefreshToken, LoginOtp};
+use super::tier::{MembershipTier, TierChange, TierChangeReason};
+use super::adjustment::{BalanceAdjustment, AdjustmentStatus, AdjustmentAction, AdjustmentAuditEntry, CreateAdjustmentRequest};
 
 #[async_trait]
 pub trait UserRepository {
     async fn get_user_by_id(&self, id: u32) -> Result<Option<User>, String>;
     async fn get_user_by_email(&self, email: &str) -> Result<Option<User>, String>;
+    // Phone numbers are unique, like emails
+    async fn get_user_by_phone(&self, phone: &str) -> Result<Option<User>, String>;
     async fn create_user(&self, user_request: CreateUserRequest) -> Result<User, String>;
     async fn update_user(&self, id: u32, update_request: UpdateUserRequest) -> Result<User, String>;
     async fn delete_user(&self, id: u32) -> Result<bool, String>;
+    async fn set_role(&self, id: u32, role: UserRole) -> Result<User, String>;
     async fn list_users(&self, limit: Option<i64>, offset: Option<i64>) -> Result<Vec<User>, String>;
 }
 
 #[async_trait]
 pub trait TransferRepository {
-    async fn create_transfer(&self, transfer_request: CreateTransferRequest) -> Result<Transfer, String>;
+    // `now` stamps created_at/updated_at, so rows agree with the service's clock
+    async fn create_transfer(&self, transfer_request: CreateTransferRequest, now: DateTime<Utc>) -> Result<Transfer, String>;
+    // Inserts a pending transfer that waits for the recipient to accept it before `accept_by`
+    async fn create_transfer_awaiting_acceptance(&self, transfer_request: CreateTransferRequest, accept_by: DateTime<Utc>, now: DateTime<Utc>) -> Result<Transfer, String>;
+    // Inserts the pending split parent and one pending child per recipient
+    async fn create_split_transfer(&self, split_request: CreateSplitTransferRequest, now: DateTime<Utc>) -> Result<(Transfer, Vec<Transfer>), String>;
     async fn get_transfer_by_idem_key(&self, idem_key: &str) -> Result<Option<Transfer>, String>;
+    async fn get_transfer_by_id(&self, id: u32) -> Result<Option<Transfer>, String>;
+    async fn get_child_transfers(&self, parent_transfer_id: u32) -> Result<Vec<Transfer>, String>;
     async fn get_transfers_by_user_id(&self, user_id: u32, page: u32, page_size: u32) -> Result<(Vec<Transfer>, u32), String>;
-    async fn update_transfer_status(&self, idem_key: &str, status: &str, completed_at: Option<String>, fail_reason: Option<String>) -> Result<(), String>;
+    // Every transfer in id order, `limit` at a time starting after `after_id`
+    async fn get_transfers_after(&self, after_id: u32, limit: u32) -> Result<Vec<Transfer>, String>;
+    // Pending transfers that are not scheduled, or whose executeAt has arrived by `due_at`; transfers awaiting
+    // the recipient's acceptance are left out
+    async fn get_pending_transfers(&self, due_at: DateTime<Utc>, limit: u32) -> Result<Vec<Transfer>, String>;
+    // Compare-and-set: only moves the transfer if it is still in `from` and `from -> to` is a legal transition
+    async fn update_transfer_status(&self, idem_key: &str, from: TransferStatus, to: TransferStatus, completed_at: Option<String>, fail_reason: Option<String>, now: DateTime<Utc>) -> Result<(), String>;
+    async fn create_transfer_reversal(&self, transfer_id: u32, reversed_by: &str, reason: &str, forced: bool, now: DateTime<Utc>) -> Result<TransferReversal, String>;
+    async fn get_transfer_reversal(&self, transfer_id: u32) -> Result<Option<TransferReversal>, String>;
+    // Whether the recipient has ever been sent points by the sender (a completed or later reversed transfer)
+    async fn has_received_from(&self, from_user_id: u32, to_user_id: u32) -> Result<bool, String>;
+    // Transfers still awaiting acceptance whose acceptBy is at or before `now`
+    async fn get_expired_acceptances(&self, now: DateTime<Utc>, limit: u32) -> Result<Vec<Transfer>, String>;
+    // Points and number of transfers the user has sent since `since` (split legs count once, failed and cancelled transfers not at all)
+    async fn get_outgoing_totals(&self, from_user_id: u32, since: DateTime<Utc>) -> Result<(u64, u32), String>;
 }
 
 #[async_trait]
 pub trait PointLedgerRepository {
+    #[allow(clippy::too_many_arguments)]
     async fn create_ledger_entry(&self, user_id: u32, change: i32, balance_after: u32, event_type: EventType, transfer_id: Option<u32>, reference: Option<String>, metadata: Option<String>) -> Result<PointLedger, String>;
-    async fn get_ledger_by_user_id(&self, user_id: u32, limit: Option<i64>, offset: Option<i64>) -> Result<Vec<PointLedger>, String>;
+    // One page of the user's entries, newest first, with totals over every matching entry. With a cursor the
+    // page starts below that sequence number instead of at `page`
+    async fn get_ledger_by_user_id(&self, user_id: u32, filter: &LedgerFilter, cursor: Option<u32>, page: u32, page_size: u32) -> Result<(Vec<PointLedger>, LedgerTotals), String>;
+    async fn get_ledger_entry(&self, entry_id: u32) -> Result<Option<PointLedger>, String>;
+    // All of the user's entries in sequence order
+    async fn get_full_ledger(&self, user_id: u32) -> Result<Vec<PointLedger>, String>;
     async fn get_current_balance(&self, user_id: u32) -> Result<u32, String>;
+    // balance_after and sequence of the last entry created at or before `at`; none if the user had no entries by then
+    async fn get_balance_at(&self, user_id: u32, at: DateTime<Utc>) -> Result<Option<(u32, u32)>, String>;
+    // Points reserved by active holds and by transfers waiting for their recipient to accept them
+    async fn get_held_amount(&self, user_id: u32) -> Result<u32, String>;
+    // Ledger balance less held points; every debit is checked against this, not the ledger balance
+    async fn get_available_balance(&self, user_id: u32) -> Result<u32, String>;
+    async fn get_ledger_by_transfer_id(&self, transfer_id: u32) -> Result<Vec<PointLedger>, String>;
+    // Earn and redeem entries keep the client's Idempotency-Key, scoped to the user, so a retry finds the original entry
+    async fn get_ledger_entry_by_idempotency_key(&self, user_id: u32, key: &str) -> Result<Option<PointLedger>, String>;
+    // create_ledger_entry for an entry posted under the client's idempotency key; the key is written with the entry
+    #[allow(clippy::too_many_arguments)]
+    async fn create_keyed_ledger_entry(&self, user_id: u32, change: i32, balance_after: u32, event_type: EventType, reference: Option<String>, metadata: Option<String>, idempotency_key: &str) -> Result<PointLedger, String>;
+    async fn has_debits_after(&self, user_id: u32, after_entry_id: u32) -> Result<bool, String>;
+    // Lots with points left, oldest first; every credit opens one and every debit drains them in this order
+    async fn get_open_lots(&self, user_id: u32) -> Result<Vec<PointLot>, String>;
+    // Users past after_user_id, in id order, with points left in a lot earned at or before the cutoff
+    async fn get_users_with_lots_earned_before(&self, cutoff: DateTime<Utc>, after_user_id: u32, limit: u32) -> Result<Vec<u32>, String>;
+    // Points credited by earn entries created at or after `since`; transfers and adjustments do not count
+    async fn get_points_earned(&self, user_id: u32, since: DateTime<Utc>) -> Result<u64, String>;
+}
+
+#[async_trait]
+pub trait IdempotencyRepository {
+    async fn get_record(&self, from_user_id: u32, key: &str) -> Result<Option<IdempotencyRecord>, String>;
+    async fn create_record(&self, record: IdempotencyRecord) -> Result<(), String>;
+    async fn delete_expired(&self, now: DateTime<Utc>) -> Result<u64, String>;
+}
+
+#[async_trait]
+pub trait MandateRepository {
+    async fn create_mandate(&self, request: CreateMandateRequest, start_at: DateTime<Utc>) -> Result<TransferMandate, String>;
+    async fn get_mandate(&self, id: u32) -> Result<Option<TransferMandate>, String>;
+    async fn get_mandates_by_user_id(&self, user_id: u32) -> Result<Vec<TransferMandate>, String>;
+    // Active mandates whose next run is at or before `due_at`, oldest first
+    async fn get_due_mandates(&self, due_at: DateTime<Utc>, limit: u32) -> Result<Vec<TransferMandate>, String>;
+    async fn update_mandate(&self, mandate: &TransferMandate) -> Result<(), String>;
+    async fn get_run(&self, mandate_id: u32, occurrence: u32) -> Result<Option<MandateRun>, String>;
+    // Fails if the occurrence already has a run
+    async fn create_run(&self, run: &MandateRun) -> Result<(), String>;
+}
+
+#[async_trait]
+pub trait BatchRepository {
+    // Stores the batch and all of its items in one transaction
+    async fn create_batch(&self, batch: &TransferBatch) -> Result<(), String>;
+    async fn get_batch(&self, batch_id: &str) -> Result<Option<TransferBatch>, String>;
+    async fn get_batch_by_idempotency_key(&self, idempotency_key: &str) -> Result<Option<TransferBatch>, String>;
+    // Pending or processing batches, oldest first
+    async fn get_unfinished_batches(&self, limit: u32) -> Result<Vec<TransferBatch>, String>;
+    // Saves status, counters and timestamps; items are saved with update_batch_item
+    async fn update_batch(&self, batch: &TransferBatch) -> Result<(), String>;
+    async fn update_batch_item(&self, batch_id: &str, item: &BatchItem) -> Result<(), String>;
+}
+
+#[async_trait]
+pub trait TransferLimitRepository {
+    // Membership levels are matched case-insensitively
+    async fn get_limits(&self, membership_level: &str) -> Result<Option<TransferLimits>, String>;
+    async fn list_limits(&self) -> Result<Vec<TransferLimits>, String>;
+    // Creates or replaces the limits of a membership level
+    async fn set_limits(&self, membership_level: &str, request: UpdateTransferLimitsRequest) -> Result<TransferLimits, String>;
+}
+
+#[async_trait]
+pub trait HoldRepository {
+    async fn create_hold(&self, hold: &PointHold) -> Result<(), String>;
+    async fn get_hold(&self, hold_id: &str) -> Result<Option<PointHold>, String>;
+    async fn get_holds_by_user_id(&self, user_id: u32) -> Result<Vec<PointHold>, String>;
+    // Active holds whose expiresAt is at or before `now`, oldest first
+    async fn get_expired_holds(&self, now: DateTime<Utc>, limit: u32) -> Result<Vec<PointHold>, String>;
+    async fn update_hold(&self, hold: &PointHold) -> Result<(), String>;
+}
+
+#[async_trait]
+pub trait AdjustmentRepository {
+    async fn create_adjustment(&self, request: CreateAdjustmentRequest, requested_by: &str, now: DateTime<Utc>) -> Result<BalanceAdjustment, String>;
+    async fn get_adjustment(&self, id: u32) -> Result<Option<BalanceAdjustment>, String>;
+    // Newest first; either filter may be left out
+    async fn list_adjustments(&self, status: Option<AdjustmentStatus>, user_id: Option<u32>) -> Result<Vec<BalanceAdjustment>, String>;
+    async fn update_adjustment(&self, adjustment: &BalanceAdjustment) -> Result<(), String>;
+    async fn add_audit_entry(&self, adjustment_id: u32, action: AdjustmentAction, actor: &str, note: Option<String>, now: DateTime<Utc>) -> Result<AdjustmentAuditEntry, String>;
+    async fn get_audit_entries(&self, adjustment_id: u32) -> Result<Vec<AdjustmentAuditEntry>, String>;
+}
+
+#[async_trait]
+pub trait TierRepository {
+    async fn get_user_tier(&self, user_id: u32) -> Result<Option<MembershipTier>, String>;
+    // Moves users.membership_level and appends the tier_history row that records it
+    #[allow(clippy::too_many_arguments)]
+    async fn change_tier(&self, user_id: u32, from_tier: MembershipTier, to_tier: MembershipTier, reason: TierChangeReason, points_earned: u64, changed_by: Option<String>, note: Option<String>, now: DateTime<Utc>) -> Result<TierChange, String>;
+    // Newest first
+    async fn get_tier_history(&self, user_id: u32) -> Result<Vec<TierChange>, String>;
+    async fn get_last_tier_change(&self, user_id: u32) -> Result<Option<TierChange>, String>;
+}
+
+#[async_trait]
+pub trait AuthRepository {
+    async fn get_password_hash(&self, user_id: u32) -> Result<Option<String>, String>;
+    async fn set_password_hash(&self, user_id: u32, password_hash: &str, now: DateTime<Utc>) -> Result<(), String>;
+    async fn create_refresh_token(&self, user_id: u32, token_hash: &str, expires_at: DateTime<Utc>, now: DateTime<Utc>) -> Result<RefreshToken, String>;
+    async fn get_refresh_token(&self, token_hash: &str) -> Result<Option<RefreshToken>, String>;
+    // Compare-and-set: false when the token was already revoked
+    async fn revoke_refresh_token(&self, id: u32, replaced_by: Option<u32>, now: DateTime<Utc>) -> Result<bool, String>;
+    async fn revoke_user_refresh_tokens(&self, user_id: u32, now: DateTime<Utc>) -> Result<u64, String>;
+    // Stores a new code for the user and retires any earlier one still open
+    async fn create_login_otp(&self, user_id: u32, code_hash: &str, expires_at: DateTime<Utc>, now: DateTime<Utc>) -> Result<LoginOtp, String>;
+    // How many codes the user has been sent at or after `since`, used or not
+    async fn count_login_otps_since(&self, user_id: u32, since: DateTime<Utc>) -> Result<u32, String>;
+    // The user's unconsumed code, if it has not expired by `now`
+    async fn get_active_login_otp(&self, user_id: u32, now: DateTime<Utc>) -> Result<Option<LoginOtp>, String>;
+    async fn record_login_otp_attempt(&self, id: u32) -> Result<(), String>;
+    // Compare-and-set: false when the code was already used
+    async fn consume_login_otp(&self, id: u32, now: DateTime<Utc>) -> Result<bool, String>;
+}
+
+// Groups transfer, hold, adjustment, tier and ledger writes so they commit or roll back together.
+// Dropping a unit of work without committing rolls it back.
+#[async_trait]
+pub trait UnitOfWork: Send + Sync {
+    fn transfers(&self) -> Arc<dyn TransferRepository + Send + Sync>;
+    fn point_ledger(&self) -> Arc<dyn PointLedgerRepository + Send + Sync>;
+    fn idempotency_keys(&self) -> Arc<dyn IdempotencyRepository + Send + Sync>;
+    fn holds(&self) -> Arc<dyn HoldRepository + Send + Sync>;
+    fn adjustments(&self) -> Arc<dyn AdjustmentRepository + Send + Sync>;
+    fn tiers(&self) -> Arc<dyn TierRepository + Send + Sync>;
+    fn mandates(&self) -> Arc<dyn MandateRepository + Send + Sync>;
+    async fn commit(self: Box<Self>) -> Result<(), String>;
+    async fn rollback(self: Box<Self>) -> Result<(), String>;
+}
+
+#[async_trait]
+pub trait UnitOfWorkFactory {
+    async fn begin(&self) -> Result<Box<dyn UnitOfWork>, String>;
 }
\ No newline at end of file
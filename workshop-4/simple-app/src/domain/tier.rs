@@ -0,0 +1,257 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use super::transfer_limit::TransferLimits;
+
+// Tiers are won on the points a member earned over this many months, and once reached are kept at least this long
+pub const TIER_QUALIFICATION_MONTHS: u32 = 12;
+
+// Ordered lowest to highest, so tiers compare by rank
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, ToSchema)]
+pub enum MembershipTier {
+    Bronze,
+    Silver,
+    Gold,
+    Platinum,
+}
+
+impl std::fmt::Display for MembershipTier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MembershipTier::Bronze => write!(f, "Bronze"),
+            MembershipTier::Silver => write!(f, "Silver"),
+            MembershipTier::Gold => write!(f, "Gold"),
+            MembershipTier::Platinum => write!(f, "Platinum"),
+        }
+    }
+}
+
+impl std::str::FromStr for MembershipTier {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "bronze" => Ok(MembershipTier::Bronze),
+            "silver" => Ok(MembershipTier::Silver),
+            "gold" => Ok(MembershipTier::Gold),
+            "platinum" => Ok(MembershipTier::Platinum),
+            _ => Err(format!("Invalid membership tier: {}", s)),
+        }
+    }
+}
+
+impl MembershipTier {
+    pub const ALL: [MembershipTier; 4] = [
+        MembershipTier::Bronze,
+        MembershipTier::Silver,
+        MembershipTier::Gold,
+        MembershipTier::Platinum,
+    ];
+
+    // Points that must be earned within the qualification window to reach the tier (tier bonuses not included)
+    pub fn min_points_earned(self) -> u64 {
+        match self {
+            MembershipTier::Bronze => 0,
+            MembershipTier::Silver => 1_000,
+            MembershipTier::Gold => 5_000,
+            MembershipTier::Platinum => 20_000,
+        }
+    }
+
+    // Earned points are credited at this percentage; anything over 100 is posted as a separate bonus entry
+    pub fn earn_multiplier_percent(self) -> u32 {
+        match self {
+            MembershipTier::Bronze => 100,
+            MembershipTier::Silver => 125,
+            MembershipTier::Gold => 150,
+            MembershipTier::Platinum => 200,
+        }
+    }
+
+    // Extra points on top of `amount`, rounded down
+    pub fn earn_bonus(self, amount: u32) -> u32 {
+        (amount as u64 * (self.earn_multiplier_percent() as u64 - 100) / 100) as u32
+    }
+
+    // The highest tier the points earned qualify for
+    pub fn for_points_earned(points_earned: u64) -> MembershipTier {
+        MembershipTier::ALL
+            .into_iter()
+            .rev()
+            .find(|tier| points_earned >= tier.min_points_earned())
+            .unwrap_or(MembershipTier::Bronze)
+    }
+
+    pub fn next(self) -> Option<MembershipTier> {
+        MembershipTier::ALL.into_iter().find(|tier| *tier > self)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TierChangeReason {
+    Promotion,
+    Demotion,
+    // Set by staff through PUT /users/{id}/tier
+    Manual,
+}
+
+impl std::fmt::Display for TierChangeReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TierChangeReason::Promotion => write!(f, "promotion"),
+            TierChangeReason::Demotion => write!(f, "demotion"),
+            TierChangeReason::Manual => write!(f, "manual"),
+        }
+    }
+}
+
+impl std::str::FromStr for TierChangeReason {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "promotion" => Ok(TierChangeReason::Promotion),
+            "demotion" => Ok(TierChangeReason::Demotion),
+            "manual" => Ok(TierChangeReason::Manual),
+            _ => Err(format!("Invalid tier change reason: {}", s)),
+        }
+    }
+}
+
+// One row of tier_history; each is also the tier-change event
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TierChange {
+    pub id: u32,
+    #[serde(rename = "userId")]
+    pub user_id: u32,
+    #[serde(rename = "fromTier")]
+    pub from_tier: MembershipTier,
+    #[serde(rename = "toTier")]
+    pub to_tier: MembershipTier,
+    pub reason: TierChangeReason,
+    // Points earned over the qualification window when the change was made
+    #[serde(rename = "pointsEarned")]
+    pub points_earned: u64,
+    // Email of the staff member behind a manual change
+    #[serde(rename = "changedBy")]
+    pub changed_by: Option<String>,
+    pub note: Option<String>,
+    #[serde(rename = "createdAt")]
+    #[schema(value_type = String, format = "date-time")]
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TierBenefits {
+    pub tier: MembershipTier,
+    #[serde(rename = "minPointsEarned")]
+    pub min_points_earned: u64,
+    #[serde(rename = "earnMultiplierPercent")]
+    pub earn_multiplier_percent: u32,
+    // None when no limits are configured for the tier
+    #[serde(rename = "transferLimits")]
+    pub transfer_limits: Option<TransferLimits>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TierListResponse {
+    #[serde(rename = "qualificationMonths")]
+    pub qualification_months: u32,
+    pub data: Vec<TierBenefits>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct UserTierStatus {
+    #[serde(rename = "userId")]
+    pub user_id: u32,
+    pub tier: MembershipTier,
+    #[serde(rename = "tierSince")]
+    #[schema(value_type = String, format = "date-time")]
+    pub tier_since: DateTime<Utc>,
+    // The tier cannot drop before this
+    #[serde(rename = "protectedUntil")]
+    #[schema(value_type = String, format = "date-time")]
+    pub protected_until: DateTime<Utc>,
+    #[serde(rename = "pointsEarned")]
+    pub points_earned: u64,
+    // The tier the points earned qualify for at the next evaluation
+    #[serde(rename = "qualifyingTier")]
+    pub qualifying_tier: MembershipTier,
+    #[serde(rename = "nextTier")]
+    pub next_tier: Option<MembershipTier>,
+    #[serde(rename = "pointsToNextTier")]
+    pub points_to_next_tier: Option<u64>,
+    pub benefits: TierBenefits,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SetTierRequest {
+    pub tier: MembershipTier,
+    pub note: Option<String>,
+}
+
+impl SetTierRequest {
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(note) = &self.note
+            && note.len() > 500
+        {
+            return Err("Note cannot exceed 500 characters".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TierHistoryResponse {
+    #[serde(rename = "userId")]
+    pub user_id: u32,
+    pub data: Vec<TierChange>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TierEvaluationReport {
+    #[serde(rename = "generatedAt")]
+    #[schema(value_type = String, format = "date-time")]
+    pub generated_at: DateTime<Utc>,
+    #[serde(rename = "usersEvaluated")]
+    pub users_evaluated: u32,
+    pub promoted: u32,
+    pub demoted: u32,
+    pub changes: Vec<TierChange>,
+}
+
+// Database model for internal use
+#[derive(Debug, Clone)]
+pub struct TierChangeDb {
+    pub id: u32,
+    pub user_id: u32,
+    pub from_tier: String,
+    pub to_tier: String,
+    pub reason: String,
+    pub points_earned: u64,
+    pub changed_by: Option<String>,
+    pub note: Option<String>,
+    pub created_at: String,
+}
+
+impl TierChangeDb {
+    pub fn into_domain(self) -> Result<TierChange, String> {
+        let created_at = DateTime::parse_from_rfc3339(&self.created_at)
+            .map_err(|e| format!("Invalid created_at date: {}", e))?
+            .with_timezone(&Utc);
+
+        Ok(TierChange {
+            id: self.id,
+            user_id: self.user_id,
+            from_tier: self.from_tier.parse()?,
+            to_tier: self.to_tier.parse()?,
+            reason: self.reason.parse()?,
+            points_earned: self.points_earned,
+            changed_by: self.changed_by,
+            note: self.note,
+            created_at,
+        })
+    }
+}
\ No newline at end of file
@@ -2,7 +2,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
-#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum TransferStatus {
     Pending,
@@ -13,6 +13,32 @@ pub enum TransferStatus {
     Reversed,
 }
 
+// Every status change a transfer may make; anything not listed is rejected
+const TRANSFER_STATUS_TRANSITIONS: &[(TransferStatus, TransferStatus)] = &[
+    (TransferStatus::Pending, TransferStatus::Processing),
+    (TransferStatus::Pending, TransferStatus::Cancelled),
+    (TransferStatus::Processing, TransferStatus::Completed),
+    (TransferStatus::Processing, TransferStatus::Failed),
+    (TransferStatus::Processing, TransferStatus::Cancelled),
+    (TransferStatus::Completed, TransferStatus::Reversed),
+];
+
+impl TransferStatus {
+    pub fn can_transition_to(&self, next: TransferStatus) -> bool {
+        TRANSFER_STATUS_TRANSITIONS
+            .iter()
+            .any(|(from, to)| *from == *self && *to == next)
+    }
+
+    pub fn validate_transition(&self, next: TransferStatus) -> Result<(), String> {
+        if !self.can_transition_to(next) {
+            return Err(format!("Illegal transfer status transition: {} -> {}", self, next));
+        }
+
+        Ok(())
+    }
+}
+
 impl std::fmt::Display for TransferStatus {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -42,6 +68,35 @@ impl std::str::FromStr for TransferStatus {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TransferType {
+    Standard,
+    // Parent of a multi-recipient transfer: one debit from the sender, one child transfer per recipient
+    Split,
+}
+
+impl std::fmt::Display for TransferType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TransferType::Standard => write!(f, "standard"),
+            TransferType::Split => write!(f, "split"),
+        }
+    }
+}
+
+impl std::str::FromStr for TransferType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "standard" => Ok(TransferType::Standard),
+            "split" => Ok(TransferType::Split),
+            _ => Err(format!("Invalid transfer type: {}", s)),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct Transfer {
     #[serde(rename = "idemKey")]
@@ -66,16 +121,39 @@ pub struct Transfer {
     pub completed_at: Option<DateTime<Utc>>,
     #[serde(rename = "failReason")]
     pub fail_reason: Option<String>,
+    #[serde(rename = "executeAt")]
+    #[schema(value_type = Option<String>, format = "date-time")]
+    pub execute_at: Option<DateTime<Utc>>,
+    // A split parent records the sender on both sides; the recipients are on its child transfers
+    #[serde(rename = "transferType")]
+    pub transfer_type: TransferType,
+    #[serde(rename = "parentTransferId", skip_serializing_if = "Option::is_none")]
+    pub parent_transfer_id: Option<u32>,
+    // Set on a transfer to a new recipient: it stays pending until they accept, or is cancelled at this time
+    #[serde(rename = "acceptBy", skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>, format = "date-time")]
+    pub accept_by: Option<DateTime<Utc>>,
+}
+
+impl Transfer {
+    pub fn is_awaiting_acceptance(&self) -> bool {
+        self.status == TransferStatus::Pending && self.accept_by.is_some()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct CreateTransferRequest {
-    #[serde(rename = "fromUserId")]
+    // Taken from the access token; when sent it must name the signed-in user
+    #[serde(rename = "fromUserId", default)]
     pub from_user_id: u32,
     #[serde(rename = "toUserId")]
     pub to_user_id: u32,
     pub amount: u32,
     pub note: Option<String>,
+    // Future-dated transfer: stays pending until the scheduler runs it at this time
+    #[serde(rename = "executeAt", default, skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>, format = "date-time")]
+    pub execute_at: Option<DateTime<Utc>>,
 }
 
 impl CreateTransferRequest {
@@ -88,10 +166,10 @@ impl CreateTransferRequest {
             return Err("Cannot transfer to the same user".to_string());
         }
         
-        if let Some(note) = &self.note {
-            if note.len() > 512 {
-                return Err("Note cannot exceed 512 characters".to_string());
-            }
+        if let Some(note) = &self.note
+            && note.len() > 512
+        {
+            return Err("Note cannot exceed 512 characters".to_string());
         }
         
         Ok(())
@@ -106,6 +184,181 @@ pub struct TransferCreateResponse {
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct TransferGetResponse {
     pub transfer: Transfer,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reversal: Option<TransferReversal>,
+    // Per-recipient child transfers of a split transfer
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub splits: Option<Vec<Transfer>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SplitRecipient {
+    #[serde(rename = "toUserId")]
+    pub to_user_id: u32,
+    pub amount: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CreateSplitTransferRequest {
+    // Taken from the access token; when sent it must name the signed-in user
+    #[serde(rename = "fromUserId", default)]
+    pub from_user_id: u32,
+    pub recipients: Vec<SplitRecipient>,
+    pub note: Option<String>,
+}
+
+impl CreateSplitTransferRequest {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.recipients.len() < 2 {
+            return Err("A split transfer needs at least 2 recipients".to_string());
+        }
+
+        if self.recipients.len() > 50 {
+            return Err("A split transfer cannot exceed 50 recipients".to_string());
+        }
+
+        // Each leg must be a valid transfer on its own
+        for leg in self.legs() {
+            leg.validate()?;
+        }
+
+        let mut recipient_ids: Vec<u32> = self.recipients.iter().map(|recipient| recipient.to_user_id).collect();
+        recipient_ids.sort_unstable();
+        recipient_ids.dedup();
+        if recipient_ids.len() != self.recipients.len() {
+            return Err("Each recipient can only appear once in a split transfer".to_string());
+        }
+
+        if self.total_amount().is_none() {
+            return Err("Total amount is too large".to_string());
+        }
+
+        Ok(())
+    }
+
+    pub fn total_amount(&self) -> Option<u32> {
+        self.recipients.iter().try_fold(0u32, |total, recipient| total.checked_add(recipient.amount))
+    }
+
+    // One ordinary transfer request per recipient
+    pub fn legs(&self) -> Vec<CreateTransferRequest> {
+        self.recipients.iter()
+            .map(|recipient| CreateTransferRequest {
+                from_user_id: self.from_user_id,
+                to_user_id: recipient.to_user_id,
+                amount: recipient.amount,
+                note: self.note.clone(),
+                execute_at: None,
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TransferSplitResponse {
+    pub transfer: Transfer,
+    pub splits: Vec<Transfer>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CancelTransferRequest {
+    // Taken from the access token; when sent it must name the signed-in user
+    #[serde(rename = "cancelledBy", default)]
+    pub cancelled_by: u32,
+    pub reason: String,
+}
+
+impl CancelTransferRequest {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.reason.trim().is_empty() {
+            return Err("Reason cannot be empty".to_string());
+        }
+
+        if self.reason.len() > 512 {
+            return Err("Reason cannot exceed 512 characters".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TransferCancelResponse {
+    pub transfer: Transfer,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AcceptTransferRequest {
+    // Taken from the access token; when sent it must name the signed-in user
+    #[serde(rename = "acceptedBy", default)]
+    pub accepted_by: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DeclineTransferRequest {
+    // Taken from the access token; when sent it must name the signed-in user
+    #[serde(rename = "declinedBy", default)]
+    pub declined_by: u32,
+    pub reason: Option<String>,
+}
+
+impl DeclineTransferRequest {
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(reason) = &self.reason
+            && reason.len() > 512
+        {
+            return Err("Reason cannot exceed 512 characters".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+// Answer to POST /transfers/{id}/accept and /decline
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TransferAcceptanceResponse {
+    pub transfer: Transfer,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ReverseTransferRequest {
+    pub reason: String,
+    // Admin override: reverse even if the recipient has spent points since receiving them
+    pub force: Option<bool>,
+}
+
+impl ReverseTransferRequest {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.reason.trim().is_empty() {
+            return Err("Reason cannot be empty".to_string());
+        }
+
+        if self.reason.len() > 512 {
+            return Err("Reason cannot exceed 512 characters".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TransferReversal {
+    #[serde(rename = "transferId")]
+    pub transfer_id: u32,
+    // Email of the staff member who reversed it
+    #[serde(rename = "reversedBy")]
+    pub reversed_by: String,
+    pub reason: String,
+    pub forced: bool,
+    #[serde(rename = "createdAt")]
+    #[schema(value_type = String, format = "date-time")]
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TransferReverseResponse {
+    pub transfer: Transfer,
+    pub reversal: TransferReversal,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -131,12 +384,17 @@ pub struct TransferDb {
     pub updated_at: String,
     pub completed_at: Option<String>,
     pub fail_reason: Option<String>,
+    pub execute_at: Option<String>,
+    pub transfer_type: String,
+    pub parent_transfer_id: Option<u32>,
+    pub accept_by: Option<String>,
 }
 
 impl TransferDb {
-    pub fn to_domain(self) -> Result<Transfer, String> {
+    pub fn into_domain(self) -> Result<Transfer, String> {
         let status = self.status.parse::<TransferStatus>()
             .map_err(|e| format!("Invalid status: {}", e))?;
+        let transfer_type = self.transfer_type.parse::<TransferType>()?;
         
         let created_at = DateTime::parse_from_rfc3339(&self.created_at)
             .map_err(|e| format!("Invalid created_at date: {}", e))?
@@ -153,6 +411,22 @@ impl TransferDb {
         } else {
             None
         };
+
+        let execute_at = if let Some(execute_str) = self.execute_at {
+            Some(DateTime::parse_from_rfc3339(&execute_str)
+                .map_err(|e| format!("Invalid execute_at date: {}", e))?
+                .with_timezone(&Utc))
+        } else {
+            None
+        };
+
+        let accept_by = if let Some(accept_by_str) = self.accept_by {
+            Some(DateTime::parse_from_rfc3339(&accept_by_str)
+                .map_err(|e| format!("Invalid accept_by date: {}", e))?
+                .with_timezone(&Utc))
+        } else {
+            None
+        };
         
         Ok(Transfer {
             idem_key: self.idempotency_key,
@@ -166,6 +440,76 @@ impl TransferDb {
             updated_at,
             completed_at,
             fail_reason: self.fail_reason,
+            execute_at,
+            transfer_type,
+            parent_transfer_id: self.parent_transfer_id,
+            accept_by,
         })
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL_STATUSES: [TransferStatus; 6] = [
+        TransferStatus::Pending,
+        TransferStatus::Processing,
+        TransferStatus::Completed,
+        TransferStatus::Failed,
+        TransferStatus::Cancelled,
+        TransferStatus::Reversed,
+    ];
+
+    fn is_allowed(from: TransferStatus, to: TransferStatus) -> bool {
+        use TransferStatus::*;
+        matches!(
+            (from, to),
+            (Pending, Processing)
+                | (Pending, Cancelled)
+                | (Processing, Completed)
+                | (Processing, Failed)
+                | (Processing, Cancelled)
+                | (Completed, Reversed)
+        )
+    }
+
+    #[test]
+    fn allowed_transitions_pass() {
+        for from in ALL_STATUSES {
+            for to in ALL_STATUSES.into_iter().filter(|to| is_allowed(from, *to)) {
+                assert!(from.validate_transition(to).is_ok(), "{} -> {} should be allowed", from, to);
+            }
+        }
+    }
+
+    #[test]
+    fn every_other_transition_is_rejected() {
+        let mut rejected = 0;
+        for from in ALL_STATUSES {
+            for to in ALL_STATUSES.into_iter().filter(|to| !is_allowed(from, *to)) {
+                let err = from.validate_transition(to).unwrap_err();
+                assert_eq!(err, format!("Illegal transfer status transition: {} -> {}", from, to));
+                rejected += 1;
+            }
+        }
+        assert_eq!(rejected, 36 - 6);
+    }
+
+    fn split(recipient_ids: impl IntoIterator<Item = u32>) -> CreateSplitTransferRequest {
+        CreateSplitTransferRequest {
+            from_user_id: 1,
+            recipients: recipient_ids.into_iter().map(|to_user_id| SplitRecipient { to_user_id, amount: 10 }).collect(),
+            note: None,
+        }
+    }
+
+    #[test]
+    fn split_takes_2_to_50_distinct_recipients() {
+        assert_eq!(split(2..3).validate().unwrap_err(), "A split transfer needs at least 2 recipients");
+        assert!(split(2..4).validate().is_ok());
+        assert!(split(2..52).validate().is_ok());
+        assert_eq!(split(2..53).validate().unwrap_err(), "A split transfer cannot exceed 50 recipients");
+        assert_eq!(split([2, 3, 2]).validate().unwrap_err(), "Each recipient can only appear once in a split transfer");
+    }
 }
\ No newline at end of file
@@ -0,0 +1,188 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+// Outgoing transfer limits for one membership level; a limit left empty is not enforced
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TransferLimits {
+    #[serde(rename = "membershipLevel")]
+    pub membership_level: String,
+    #[serde(rename = "minAmount")]
+    pub min_amount: Option<u32>,
+    #[serde(rename = "maxAmount")]
+    pub max_amount: Option<u32>,
+    // Points sent over the last 24 hours, this transfer included
+    #[serde(rename = "dailyCap")]
+    pub daily_cap: Option<u32>,
+    #[serde(rename = "maxTransfersPerHour")]
+    pub max_transfers_per_hour: Option<u32>,
+    #[serde(rename = "updatedAt")]
+    #[schema(value_type = String, format = "date-time")]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl TransferLimits {
+    // `sent_last_day` and `transfers_last_hour` are the sender's outgoing transfers already in the windows
+    pub fn check(&self, amount: u32, sent_last_day: u64, transfers_last_hour: u32) -> Result<(), TransferLimitViolation> {
+        let daily_allowance = self.daily_cap.map(|daily_cap| (daily_cap as u64).saturating_sub(sent_last_day));
+        let violation = |limit: &str, limit_value: u32, remaining: Option<u64>| TransferLimitViolation {
+            limit: limit.to_string(),
+            membership_level: self.membership_level.clone(),
+            limit_value: limit_value as u64,
+            remaining,
+        };
+
+        if let Some(min_amount) = self.min_amount
+            && amount < min_amount
+        {
+            return Err(violation("minAmount", min_amount, None));
+        }
+
+        // A per-transfer limit, so what is left is the day's allowance (if there is a daily cap)
+        if let Some(max_amount) = self.max_amount
+            && amount > max_amount
+        {
+            return Err(violation("maxAmount", max_amount, daily_allowance));
+        }
+
+        if let Some(max_transfers) = self.max_transfers_per_hour
+            && transfers_last_hour >= max_transfers
+        {
+            return Err(violation("maxTransfersPerHour", max_transfers, Some(0)));
+        }
+
+        if let Some(daily_cap) = self.daily_cap
+            && sent_last_day + amount as u64 > daily_cap as u64
+        {
+            return Err(violation("dailyCap", daily_cap, daily_allowance));
+        }
+
+        Ok(())
+    }
+}
+
+// The limit a transfer would break. The services pass it on as its message, and handlers parse the
+// message back so LIMIT_EXCEEDED responses carry these as fields
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct TransferLimitViolation {
+    // minAmount, maxAmount, dailyCap or maxTransfersPerHour
+    pub limit: String,
+    #[serde(rename = "membershipLevel")]
+    pub membership_level: String,
+    // The configured value of the limit that was hit
+    #[serde(rename = "limitValue")]
+    pub limit_value: u64,
+    // What is left in the window: transfers this hour for maxTransfersPerHour, otherwise points over the
+    // last 24 hours. Empty when there is no window to report (minAmount, or maxAmount without a daily cap)
+    pub remaining: Option<u64>,
+}
+
+impl std::fmt::Display for TransferLimitViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let terms = match self.limit.as_str() {
+            "minAmount" => format!("must send at least {} points per transfer", self.limit_value),
+            "maxAmount" => format!("can send at most {} points per transfer", self.limit_value),
+            "maxTransfersPerHour" => format!("can make {} transfers per hour", self.limit_value),
+            _ => format!("can send {} points per 24 hours", self.limit_value),
+        };
+        write!(f, "Transfer limit exceeded ({}): {} members {}", self.limit, self.membership_level, terms)?;
+
+        if let Some(remaining) = self.remaining {
+            let unit = if self.limit == "maxTransfersPerHour" { "transfers" } else { "points" };
+            write!(f, "; remaining allowance: {} {}", remaining, unit)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::str::FromStr for TransferLimitViolation {
+    type Err = String;
+
+    fn from_str(message: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Not a transfer limit error: {}", message);
+        let first_number = |text: &str| text.split_whitespace().find_map(|word| word.parse::<u64>().ok());
+
+        let rest = message.strip_prefix("Transfer limit exceeded (").ok_or_else(invalid)?;
+        let (limit, rest) = rest.split_once("): ").ok_or_else(invalid)?;
+        let (membership_level, rest) = rest.split_once(" members ").ok_or_else(invalid)?;
+        let (terms, remaining) = match rest.split_once("; remaining allowance: ") {
+            Some((terms, remaining)) => (terms, Some(first_number(remaining).ok_or_else(invalid)?)),
+            None => (rest, None),
+        };
+
+        Ok(TransferLimitViolation {
+            limit: limit.to_string(),
+            membership_level: membership_level.to_string(),
+            limit_value: first_number(terms).ok_or_else(invalid)?,
+            remaining,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct UpdateTransferLimitsRequest {
+    #[serde(rename = "minAmount")]
+    pub min_amount: Option<u32>,
+    #[serde(rename = "maxAmount")]
+    pub max_amount: Option<u32>,
+    #[serde(rename = "dailyCap")]
+    pub daily_cap: Option<u32>,
+    #[serde(rename = "maxTransfersPerHour")]
+    pub max_transfers_per_hour: Option<u32>,
+}
+
+impl UpdateTransferLimitsRequest {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.min_amount == Some(0) || self.max_amount == Some(0) || self.daily_cap == Some(0) || self.max_transfers_per_hour == Some(0) {
+            return Err("Limits must be greater than 0; leave a limit empty to disable it".to_string());
+        }
+
+        if let (Some(min_amount), Some(max_amount)) = (self.min_amount, self.max_amount)
+            && min_amount > max_amount
+        {
+            return Err("minAmount cannot be greater than maxAmount".to_string());
+        }
+
+        if let (Some(max_amount), Some(daily_cap)) = (self.max_amount, self.daily_cap)
+            && max_amount > daily_cap
+        {
+            return Err("maxAmount cannot be greater than dailyCap".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TransferLimitsListResponse {
+    pub data: Vec<TransferLimits>,
+}
+
+// Database model for internal use
+#[derive(Debug, Clone)]
+pub struct TransferLimitsDb {
+    pub membership_level: String,
+    pub min_amount: Option<u32>,
+    pub max_amount: Option<u32>,
+    pub daily_cap: Option<u32>,
+    pub max_transfers_per_hour: Option<u32>,
+    pub updated_at: String,
+}
+
+impl TransferLimitsDb {
+    pub fn into_domain(self) -> Result<TransferLimits, String> {
+        let updated_at = DateTime::parse_from_rfc3339(&self.updated_at)
+            .map_err(|e| format!("Invalid updated_at date: {}", e))?
+            .with_timezone(&Utc);
+
+        Ok(TransferLimits {
+            membership_level: self.membership_level,
+            min_amount: self.min_amount,
+            max_amount: self.max_amount,
+            daily_cap: self.daily_cap,
+            max_transfers_per_hour: self.max_transfers_per_hour,
+            updated_at,
+        })
+    }
+}
\ No newline at end of file
@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use chrono::{DateTime, Utc};
+use super::tier::MembershipTier;
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct User {
@@ -12,7 +13,11 @@ pub struct User {
     #[serde(with = "chrono::serde::ts_seconds")]
     #[schema(value_type = String, format = DateTime)]
     pub member_since: DateTime<Utc>,
-    pub membership_level: String,
+    // Moved by the tier engine (or by staff through PUT /users/{id}/tier), never by a user update
+    pub membership_level: MembershipTier,
+    // Granted with `simple-app set-role`; staff may run the back-office endpoints
+    pub role: UserRole,
+    // Mirrors the latest ledger balance; only ledger writes change it
     pub points: i64,
     #[serde(with = "chrono::serde::ts_seconds")]
     #[schema(value_type = String, format = DateTime)]
@@ -22,13 +27,49 @@ pub struct User {
     pub updated_at: DateTime<Utc>,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum UserRole {
+    Member,
+    Staff,
+    Admin,
+}
+
+impl std::fmt::Display for UserRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UserRole::Member => write!(f, "Member"),
+            UserRole::Staff => write!(f, "Staff"),
+            UserRole::Admin => write!(f, "Admin"),
+        }
+    }
+}
+
+impl std::str::FromStr for UserRole {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "member" => Ok(UserRole::Member),
+            "staff" => Ok(UserRole::Staff),
+            "admin" => Ok(UserRole::Admin),
+            _ => Err(format!("Invalid user role: {}", s)),
+        }
+    }
+}
+
+impl UserRole {
+    // Admins can do everything staff can
+    pub fn is_staff(self) -> bool {
+        matches!(self, UserRole::Staff | UserRole::Admin)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct CreateUserRequest {
     pub first_name: String,
     pub last_name: String,
     pub phone: String,
     pub email: String,
-    pub membership_level: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -37,7 +78,6 @@ pub struct UpdateUserRequest {
     pub last_name: Option<String>,
     pub phone: Option<String>,
     pub email: Option<String>,
-    pub membership_level: Option<String>,
 }
 
 impl User {
@@ -47,7 +87,6 @@ impl User {
         last_name: String,
         phone: String,
         email: String,
-        membership_level: Option<String>,
     ) -> Self {
         let now = Utc::now();
         Self {
@@ -57,7 +96,9 @@ impl User {
             phone,
             email,
             member_since: now,
-            membership_level: membership_level.unwrap_or_else(|| "Bronze".to_string()),
+            // Everyone starts at the bottom; only the tier engine or staff move them up
+            membership_level: MembershipTier::Bronze,
+            role: UserRole::Member,
             points: 0,
             created_at: now,
             updated_at: now,
@@ -96,9 +137,6 @@ impl User {
         if let Some(email) = update_request.email {
             self.email = email;
         }
-        if let Some(membership_level) = update_request.membership_level {
-            self.membership_level = membership_level;
-        }
         self.updated_at = Utc::now();
     }
 }
\ No newline at end of file
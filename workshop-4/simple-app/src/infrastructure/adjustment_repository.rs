@@ -0,0 +1,246 @@
+use async_trait::async_trait;
+use sqlx::{SqlitePool, Row};
+use sqlx::sqlite::SqliteRow;
+use chrono::{DateTime, Utc};
+use super::unit_of_work::SqliteSession;
+use crate::domain::{
+    BalanceAdjustment, BalanceAdjustmentDb, AdjustmentStatus, AdjustmentAction, AdjustmentAuditEntry, AdjustmentAuditEntryDb,
+    CreateAdjustmentRequest, AdjustmentRepository,
+};
+
+const ADJUSTMENT_COLUMNS: &str = "id, user_id, amount, justification, status, requested_by, reviewed_by, review_note, ledger_entry_id, created_at, reviewed_at";
+
+#[derive(Clone)]
+pub struct SqliteAdjustmentRepository {
+    session: SqliteSession,
+}
+
+impl SqliteAdjustmentRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { session: SqliteSession::Pool(pool) }
+    }
+
+    pub fn with_session(session: SqliteSession) -> Self {
+        Self { session }
+    }
+
+    pub async fn init_database(&self) -> Result<(), String> {
+        let mut conn = self.session.acquire().await?;
+
+        // Create balance_adjustments table
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS balance_adjustments (
+              id INTEGER PRIMARY KEY AUTOINCREMENT,
+              user_id INTEGER NOT NULL,
+              amount INTEGER NOT NULL CHECK (amount <> 0),
+              justification TEXT NOT NULL,
+              status TEXT NOT NULL CHECK (status IN ('pending','approved','rejected')),
+              requested_by TEXT NOT NULL,
+              reviewed_by TEXT,
+              review_note TEXT,
+              ledger_entry_id INTEGER,
+              created_at TEXT NOT NULL,
+              reviewed_at TEXT,
+              CHECK (reviewed_by IS NULL OR reviewed_by <> requested_by),
+              FOREIGN KEY (user_id) REFERENCES users(id),
+              FOREIGN KEY (ledger_entry_id) REFERENCES point_ledger(id)
+            )
+            "#,
+        )
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to create balance_adjustments table: {}", e))?;
+
+        // Create adjustment_audit_log table
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS adjustment_audit_log (
+              id INTEGER PRIMARY KEY AUTOINCREMENT,
+              adjustment_id INTEGER NOT NULL,
+              action TEXT NOT NULL CHECK (action IN ('requested','approved','rejected')),
+              actor TEXT NOT NULL,
+              note TEXT,
+              created_at TEXT NOT NULL,
+              FOREIGN KEY (adjustment_id) REFERENCES balance_adjustments(id)
+            )
+            "#,
+        )
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to create adjustment_audit_log table: {}", e))?;
+
+        // Create indexes
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_adjustments_status ON balance_adjustments(status, created_at)")
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| format!("Failed to create index: {}", e))?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_adjustments_user ON balance_adjustments(user_id)")
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| format!("Failed to create index: {}", e))?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_adjustment_audit ON adjustment_audit_log(adjustment_id)")
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| format!("Failed to create index: {}", e))?;
+
+        Ok(())
+    }
+}
+
+fn adjustment_from_row(row: &SqliteRow) -> Result<BalanceAdjustment, String> {
+    let adjustment_db = BalanceAdjustmentDb {
+        id: row.get::<i64, _>("id") as u32,
+        user_id: row.get::<i64, _>("user_id") as u32,
+        amount: row.get::<i64, _>("amount") as i32,
+        justification: row.get("justification"),
+        status: row.get("status"),
+        requested_by: row.get("requested_by"),
+        reviewed_by: row.get("reviewed_by"),
+        review_note: row.get("review_note"),
+        ledger_entry_id: row.get::<Option<i64>, _>("ledger_entry_id").map(|id| id as u32),
+        created_at: row.get("created_at"),
+        reviewed_at: row.get("reviewed_at"),
+    };
+    adjustment_db.into_domain()
+}
+
+#[async_trait]
+impl AdjustmentRepository for SqliteAdjustmentRepository {
+    async fn create_adjustment(&self, request: CreateAdjustmentRequest, requested_by: &str, now: DateTime<Utc>) -> Result<BalanceAdjustment, String> {
+        let mut conn = self.session.acquire().await?;
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO balance_adjustments (user_id, amount, justification, status, requested_by, created_at)
+            VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(request.user_id as i64)
+        .bind(request.amount as i64)
+        .bind(&request.justification)
+        .bind(AdjustmentStatus::Pending.to_string())
+        .bind(requested_by)
+        .bind(now.to_rfc3339())
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to create adjustment: {}", e))?;
+
+        Ok(BalanceAdjustment {
+            id: result.last_insert_rowid() as u32,
+            user_id: request.user_id,
+            amount: request.amount,
+            justification: request.justification,
+            status: AdjustmentStatus::Pending,
+            requested_by: requested_by.to_string(),
+            reviewed_by: None,
+            review_note: None,
+            ledger_entry_id: None,
+            created_at: now,
+            reviewed_at: None,
+        })
+    }
+
+    async fn get_adjustment(&self, id: u32) -> Result<Option<BalanceAdjustment>, String> {
+        let mut conn = self.session.acquire().await?;
+
+        let row = sqlx::query(&format!("SELECT {} FROM balance_adjustments WHERE id = ?", ADJUSTMENT_COLUMNS))
+            .bind(id as i64)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| format!("Database error: {}", e))?;
+
+        row.as_ref().map(adjustment_from_row).transpose()
+    }
+
+    async fn list_adjustments(&self, status: Option<AdjustmentStatus>, user_id: Option<u32>) -> Result<Vec<BalanceAdjustment>, String> {
+        let mut conn = self.session.acquire().await?;
+
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM balance_adjustments WHERE (? IS NULL OR status = ?) AND (? IS NULL OR user_id = ?) ORDER BY created_at DESC, id DESC",
+            ADJUSTMENT_COLUMNS
+        ))
+        .bind(status.map(|s| s.to_string()))
+        .bind(status.map(|s| s.to_string()))
+        .bind(user_id.map(|id| id as i64))
+        .bind(user_id.map(|id| id as i64))
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| format!("Database error: {}", e))?;
+
+        rows.iter().map(adjustment_from_row).collect()
+    }
+
+    async fn update_adjustment(&self, adjustment: &BalanceAdjustment) -> Result<(), String> {
+        let mut conn = self.session.acquire().await?;
+
+        sqlx::query(
+            r#"
+            UPDATE balance_adjustments
+            SET status = ?, reviewed_by = ?, review_note = ?, ledger_entry_id = ?, reviewed_at = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(adjustment.status.to_string())
+        .bind(&adjustment.reviewed_by)
+        .bind(&adjustment.review_note)
+        .bind(adjustment.ledger_entry_id.map(|id| id as i64))
+        .bind(adjustment.reviewed_at.map(|date| date.to_rfc3339()))
+        .bind(adjustment.id as i64)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to update adjustment: {}", e))?;
+
+        Ok(())
+    }
+
+    async fn add_audit_entry(&self, adjustment_id: u32, action: AdjustmentAction, actor: &str, note: Option<String>, now: DateTime<Utc>) -> Result<AdjustmentAuditEntry, String> {
+        let mut conn = self.session.acquire().await?;
+
+        let result = sqlx::query(
+            "INSERT INTO adjustment_audit_log (adjustment_id, action, actor, note, created_at) VALUES (?, ?, ?, ?, ?)"
+        )
+        .bind(adjustment_id as i64)
+        .bind(action.to_string())
+        .bind(actor)
+        .bind(&note)
+        .bind(now.to_rfc3339())
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to write adjustment audit entry: {}", e))?;
+
+        Ok(AdjustmentAuditEntry {
+            id: result.last_insert_rowid() as u32,
+            adjustment_id,
+            action,
+            actor: actor.to_string(),
+            note,
+            created_at: now,
+        })
+    }
+
+    async fn get_audit_entries(&self, adjustment_id: u32) -> Result<Vec<AdjustmentAuditEntry>, String> {
+        let mut conn = self.session.acquire().await?;
+
+        let rows = sqlx::query(
+            "SELECT id, adjustment_id, action, actor, note, created_at FROM adjustment_audit_log WHERE adjustment_id = ? ORDER BY id"
+        )
+        .bind(adjustment_id as i64)
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| format!("Database error: {}", e))?;
+
+        rows.iter()
+            .map(|row| AdjustmentAuditEntryDb {
+                id: row.get::<i64, _>("id") as u32,
+                adjustment_id: row.get::<i64, _>("adjustment_id") as u32,
+                action: row.get("action"),
+                actor: row.get("actor"),
+                note: row.get("note"),
+                created_at: row.get("created_at"),
+            }.into_domain())
+            .collect()
+    }
+}
\ No newline at end of file
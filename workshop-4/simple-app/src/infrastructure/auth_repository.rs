@@ -0,0 +1,299 @@
+use async_trait::async_trait;
+use sqlx::{SqlitePool, Row};
+use sqlx::sqlite::SqliteRow;
+use chrono::{DateTime, Utc};
+use super::unit_of_work::SqliteSession;
+use crate::domain::{RefreshToken, RefreshTokenDb, LoginOtp, LoginOtpDb, AuthRepository};
+
+const REFRESH_TOKEN_COLUMNS: &str = "id, user_id, token_hash, expires_at, created_at, revoked_at, replaced_by";
+const LOGIN_OTP_COLUMNS: &str = "id, user_id, code_hash, attempts, expires_at, created_at, consumed_at";
+
+#[derive(Clone)]
+pub struct SqliteAuthRepository {
+    session: SqliteSession,
+}
+
+impl SqliteAuthRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { session: SqliteSession::Pool(pool) }
+    }
+
+    pub async fn init_database(&self) -> Result<(), String> {
+        let mut conn = self.session.acquire().await?;
+
+        // Create user_credentials table (users created through POST /users have none and sign in with a one-time code)
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS user_credentials (
+              user_id INTEGER PRIMARY KEY,
+              password_hash TEXT NOT NULL,
+              created_at TEXT NOT NULL,
+              updated_at TEXT NOT NULL,
+              FOREIGN KEY (user_id) REFERENCES users(id)
+            )
+            "#,
+        )
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to create user_credentials table: {}", e))?;
+
+        // Create refresh_tokens table
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS refresh_tokens (
+              id INTEGER PRIMARY KEY AUTOINCREMENT,
+              user_id INTEGER NOT NULL,
+              token_hash TEXT NOT NULL UNIQUE,
+              expires_at TEXT NOT NULL,
+              created_at TEXT NOT NULL,
+              revoked_at TEXT,
+              replaced_by INTEGER,
+              FOREIGN KEY (user_id) REFERENCES users(id),
+              FOREIGN KEY (replaced_by) REFERENCES refresh_tokens(id)
+            )
+            "#,
+        )
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to create refresh_tokens table: {}", e))?;
+
+        // Create login_otps table
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS login_otps (
+              id INTEGER PRIMARY KEY AUTOINCREMENT,
+              user_id INTEGER NOT NULL,
+              code_hash TEXT NOT NULL,
+              attempts INTEGER NOT NULL DEFAULT 0,
+              expires_at TEXT NOT NULL,
+              created_at TEXT NOT NULL,
+              consumed_at TEXT,
+              FOREIGN KEY (user_id) REFERENCES users(id)
+            )
+            "#,
+        )
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to create login_otps table: {}", e))?;
+
+        // Create indexes
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_refresh_tokens_user ON refresh_tokens(user_id)")
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| format!("Failed to create index: {}", e))?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_login_otps_user ON login_otps(user_id, consumed_at)")
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| format!("Failed to create index: {}", e))?;
+
+        Ok(())
+    }
+}
+
+fn refresh_token_from_row(row: &SqliteRow) -> Result<RefreshToken, String> {
+    let token_db = RefreshTokenDb {
+        id: row.get::<i64, _>("id") as u32,
+        user_id: row.get::<i64, _>("user_id") as u32,
+        token_hash: row.get("token_hash"),
+        expires_at: row.get("expires_at"),
+        created_at: row.get("created_at"),
+        revoked_at: row.get("revoked_at"),
+        replaced_by: row.get::<Option<i64>, _>("replaced_by").map(|id| id as u32),
+    };
+    token_db.into_domain()
+}
+
+fn login_otp_from_row(row: &SqliteRow) -> Result<LoginOtp, String> {
+    let otp_db = LoginOtpDb {
+        id: row.get::<i64, _>("id") as u32,
+        user_id: row.get::<i64, _>("user_id") as u32,
+        code_hash: row.get("code_hash"),
+        attempts: row.get::<i64, _>("attempts") as u32,
+        expires_at: row.get("expires_at"),
+        created_at: row.get("created_at"),
+        consumed_at: row.get("consumed_at"),
+    };
+    otp_db.into_domain()
+}
+
+#[async_trait]
+impl AuthRepository for SqliteAuthRepository {
+    async fn get_password_hash(&self, user_id: u32) -> Result<Option<String>, String> {
+        let mut conn = self.session.acquire().await?;
+
+        sqlx::query_scalar("SELECT password_hash FROM user_credentials WHERE user_id = ?")
+            .bind(user_id as i64)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| format!("Database error: {}", e))
+    }
+
+    async fn set_password_hash(&self, user_id: u32, password_hash: &str, now: DateTime<Utc>) -> Result<(), String> {
+        let mut conn = self.session.acquire().await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO user_credentials (user_id, password_hash, created_at, updated_at)
+            VALUES (?, ?, ?, ?)
+            ON CONFLICT(user_id) DO UPDATE SET password_hash = excluded.password_hash, updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(user_id as i64)
+        .bind(password_hash)
+        .bind(now.to_rfc3339())
+        .bind(now.to_rfc3339())
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to save password: {}", e))?;
+
+        Ok(())
+    }
+
+    async fn create_refresh_token(&self, user_id: u32, token_hash: &str, expires_at: DateTime<Utc>, now: DateTime<Utc>) -> Result<RefreshToken, String> {
+        let mut conn = self.session.acquire().await?;
+
+        let result = sqlx::query("INSERT INTO refresh_tokens (user_id, token_hash, expires_at, created_at) VALUES (?, ?, ?, ?)")
+            .bind(user_id as i64)
+            .bind(token_hash)
+            .bind(expires_at.to_rfc3339())
+            .bind(now.to_rfc3339())
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| format!("Failed to create refresh token: {}", e))?;
+
+        Ok(RefreshToken {
+            id: result.last_insert_rowid() as u32,
+            user_id,
+            token_hash: token_hash.to_string(),
+            expires_at,
+            created_at: now,
+            revoked_at: None,
+            replaced_by: None,
+        })
+    }
+
+    async fn get_refresh_token(&self, token_hash: &str) -> Result<Option<RefreshToken>, String> {
+        let mut conn = self.session.acquire().await?;
+
+        let row = sqlx::query(&format!("SELECT {} FROM refresh_tokens WHERE token_hash = ?", REFRESH_TOKEN_COLUMNS))
+            .bind(token_hash)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| format!("Database error: {}", e))?;
+
+        row.as_ref().map(refresh_token_from_row).transpose()
+    }
+
+    async fn revoke_refresh_token(&self, id: u32, replaced_by: Option<u32>, now: DateTime<Utc>) -> Result<bool, String> {
+        let mut conn = self.session.acquire().await?;
+
+        let result = sqlx::query("UPDATE refresh_tokens SET revoked_at = ?, replaced_by = ? WHERE id = ? AND revoked_at IS NULL")
+            .bind(now.to_rfc3339())
+            .bind(replaced_by.map(|id| id as i64))
+            .bind(id as i64)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| format!("Failed to revoke refresh token: {}", e))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn revoke_user_refresh_tokens(&self, user_id: u32, now: DateTime<Utc>) -> Result<u64, String> {
+        let mut conn = self.session.acquire().await?;
+
+        let result = sqlx::query("UPDATE refresh_tokens SET revoked_at = ? WHERE user_id = ? AND revoked_at IS NULL")
+            .bind(now.to_rfc3339())
+            .bind(user_id as i64)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| format!("Failed to revoke refresh tokens: {}", e))?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn create_login_otp(&self, user_id: u32, code_hash: &str, expires_at: DateTime<Utc>, now: DateTime<Utc>) -> Result<LoginOtp, String> {
+        let mut conn = self.session.acquire().await?;
+
+        // Only the latest code works; marking the older ones consumed keeps them from being guessed meanwhile
+        sqlx::query("UPDATE login_otps SET consumed_at = ? WHERE user_id = ? AND consumed_at IS NULL")
+            .bind(now.to_rfc3339())
+            .bind(user_id as i64)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| format!("Failed to retire login codes: {}", e))?;
+
+        let result = sqlx::query("INSERT INTO login_otps (user_id, code_hash, expires_at, created_at) VALUES (?, ?, ?, ?)")
+            .bind(user_id as i64)
+            .bind(code_hash)
+            .bind(expires_at.to_rfc3339())
+            .bind(now.to_rfc3339())
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| format!("Failed to create login code: {}", e))?;
+
+        Ok(LoginOtp {
+            id: result.last_insert_rowid() as u32,
+            user_id,
+            code_hash: code_hash.to_string(),
+            attempts: 0,
+            expires_at,
+            created_at: now,
+            consumed_at: None,
+        })
+    }
+
+    async fn count_login_otps_since(&self, user_id: u32, since: DateTime<Utc>) -> Result<u32, String> {
+        let mut conn = self.session.acquire().await?;
+
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM login_otps WHERE user_id = ? AND created_at >= ?")
+            .bind(user_id as i64)
+            .bind(since.to_rfc3339())
+            .fetch_one(&mut *conn)
+            .await
+            .map_err(|e| format!("Database error: {}", e))?;
+
+        Ok(count as u32)
+    }
+
+    async fn get_active_login_otp(&self, user_id: u32, now: DateTime<Utc>) -> Result<Option<LoginOtp>, String> {
+        let mut conn = self.session.acquire().await?;
+
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM login_otps WHERE user_id = ? AND consumed_at IS NULL AND expires_at > ? ORDER BY id DESC LIMIT 1",
+            LOGIN_OTP_COLUMNS
+        ))
+        .bind(user_id as i64)
+        .bind(now.to_rfc3339())
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(|e| format!("Database error: {}", e))?;
+
+        row.as_ref().map(login_otp_from_row).transpose()
+    }
+
+    async fn record_login_otp_attempt(&self, id: u32) -> Result<(), String> {
+        let mut conn = self.session.acquire().await?;
+
+        sqlx::query("UPDATE login_otps SET attempts = attempts + 1 WHERE id = ?")
+            .bind(id as i64)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| format!("Failed to record login code attempt: {}", e))?;
+
+        Ok(())
+    }
+
+    async fn consume_login_otp(&self, id: u32, now: DateTime<Utc>) -> Result<bool, String> {
+        let mut conn = self.session.acquire().await?;
+
+        let result = sqlx::query("UPDATE login_otps SET consumed_at = ? WHERE id = ? AND consumed_at IS NULL")
+            .bind(now.to_rfc3339())
+            .bind(id as i64)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| format!("Failed to consume login code: {}", e))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
\ No newline at end of file
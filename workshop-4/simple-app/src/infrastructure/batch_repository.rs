@@ -0,0 +1,277 @@
+use async_trait::async_trait;
+use sqlx::{Connection, SqliteConnection, SqlitePool, Row};
+use sqlx::sqlite::SqliteRow;
+use super::unit_of_work::SqliteSession;
+use crate::domain::{TransferBatch, TransferBatchDb, BatchItem, BatchItemDb, BatchRepository};
+
+const BATCH_COLUMNS: &str = "batch_id, idempotency_key, request_fingerprint, mode, status, total_items, succeeded_items, failed_items, created_at, updated_at, completed_at";
+
+#[derive(Clone)]
+pub struct SqliteBatchRepository {
+    session: SqliteSession,
+}
+
+impl SqliteBatchRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { session: SqliteSession::Pool(pool) }
+    }
+
+    pub async fn init_database(&self) -> Result<(), String> {
+        let mut conn = self.session.acquire().await?;
+
+        // Create transfer_batches table
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS transfer_batches (
+              batch_id TEXT PRIMARY KEY,
+              idempotency_key TEXT NOT NULL UNIQUE,
+              request_fingerprint TEXT NOT NULL,
+              mode TEXT NOT NULL CHECK (mode IN ('all_or_nothing','best_effort')),
+              status TEXT NOT NULL CHECK (status IN ('pending','processing','completed','failed')),
+              total_items INTEGER NOT NULL,
+              succeeded_items INTEGER NOT NULL DEFAULT 0,
+              failed_items INTEGER NOT NULL DEFAULT 0,
+              created_at TEXT NOT NULL,
+              updated_at TEXT NOT NULL,
+              completed_at TEXT
+            )
+            "#,
+        )
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to create transfer_batches table: {}", e))?;
+
+        // Create transfer_batch_items table (one row per requested transfer)
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS transfer_batch_items (
+              batch_id TEXT NOT NULL,
+              item_index INTEGER NOT NULL,
+              from_user_id INTEGER NOT NULL,
+              to_user_id INTEGER NOT NULL,
+              amount INTEGER NOT NULL,
+              note TEXT,
+              execute_at TEXT,
+              status TEXT NOT NULL CHECK (status IN ('pending','succeeded','failed','skipped')),
+              transfer_idem_key TEXT,
+              transfer_status TEXT,
+              error_code TEXT,
+              error_message TEXT,
+              PRIMARY KEY (batch_id, item_index),
+              FOREIGN KEY (batch_id) REFERENCES transfer_batches(batch_id)
+            )
+            "#,
+        )
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to create transfer_batch_items table: {}", e))?;
+
+        // Create indexes
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_batches_status ON transfer_batches(status, created_at)")
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| format!("Failed to create index: {}", e))?;
+
+        Ok(())
+    }
+}
+
+async fn load_batch(conn: &mut SqliteConnection, row: &SqliteRow) -> Result<TransferBatch, String> {
+    let batch_db = TransferBatchDb {
+        batch_id: row.get("batch_id"),
+        idempotency_key: row.get("idempotency_key"),
+        request_fingerprint: row.get("request_fingerprint"),
+        mode: row.get("mode"),
+        status: row.get("status"),
+        total_items: row.get::<i64, _>("total_items") as u32,
+        succeeded_items: row.get::<i64, _>("succeeded_items") as u32,
+        failed_items: row.get::<i64, _>("failed_items") as u32,
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+        completed_at: row.get("completed_at"),
+    };
+
+    let item_rows = sqlx::query(
+        "SELECT item_index, from_user_id, to_user_id, amount, note, execute_at, status, transfer_idem_key, transfer_status, error_code, error_message FROM transfer_batch_items WHERE batch_id = ? ORDER BY item_index"
+    )
+    .bind(&batch_db.batch_id)
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| format!("Database error: {}", e))?;
+
+    let mut items = Vec::new();
+    for row in item_rows {
+        let item_db = BatchItemDb {
+            item_index: row.get::<i64, _>("item_index") as u32,
+            from_user_id: row.get::<i64, _>("from_user_id") as u32,
+            to_user_id: row.get::<i64, _>("to_user_id") as u32,
+            amount: row.get::<i64, _>("amount") as u32,
+            note: row.get("note"),
+            execute_at: row.get("execute_at"),
+            status: row.get("status"),
+            transfer_idem_key: row.get("transfer_idem_key"),
+            transfer_status: row.get("transfer_status"),
+            error_code: row.get("error_code"),
+            error_message: row.get("error_message"),
+        };
+        items.push(item_db.into_domain()?);
+    }
+
+    batch_db.into_domain(items)
+}
+
+#[async_trait]
+impl BatchRepository for SqliteBatchRepository {
+    async fn create_batch(&self, batch: &TransferBatch) -> Result<(), String> {
+        let mut conn = self.session.acquire().await?;
+        let mut tx = conn.begin()
+            .await
+            .map_err(|e| format!("Failed to begin transaction: {}", e))?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO transfer_batches (batch_id, idempotency_key, request_fingerprint, mode, status, total_items, succeeded_items, failed_items, created_at, updated_at, completed_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&batch.batch_id)
+        .bind(&batch.idempotency_key)
+        .bind(&batch.request_fingerprint)
+        .bind(batch.mode.to_string())
+        .bind(batch.status.to_string())
+        .bind(batch.total_items as i64)
+        .bind(batch.succeeded_items as i64)
+        .bind(batch.failed_items as i64)
+        .bind(batch.created_at.to_rfc3339())
+        .bind(batch.updated_at.to_rfc3339())
+        .bind(batch.completed_at.map(|completed_at| completed_at.to_rfc3339()))
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to create batch: {}", e))?;
+
+        for item in &batch.items {
+            sqlx::query(
+                r#"
+                INSERT INTO transfer_batch_items (batch_id, item_index, from_user_id, to_user_id, amount, note, execute_at, status, transfer_idem_key, transfer_status, error_code, error_message)
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(&batch.batch_id)
+            .bind(item.index as i64)
+            .bind(item.from_user_id as i64)
+            .bind(item.to_user_id as i64)
+            .bind(item.amount as i64)
+            .bind(&item.note)
+            .bind(item.execute_at.map(|execute_at| execute_at.to_rfc3339()))
+            .bind(item.status.to_string())
+            .bind(&item.transfer_idem_key)
+            .bind(item.transfer_status.map(|status| status.to_string()))
+            .bind(&item.error_code)
+            .bind(&item.error_message)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to create batch item: {}", e))?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit transaction: {}", e))
+    }
+
+    async fn get_batch(&self, batch_id: &str) -> Result<Option<TransferBatch>, String> {
+        let mut conn = self.session.acquire().await?;
+
+        let row = sqlx::query(&format!("SELECT {} FROM transfer_batches WHERE batch_id = ?", BATCH_COLUMNS))
+            .bind(batch_id)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| format!("Database error: {}", e))?;
+
+        match row {
+            Some(row) => Ok(Some(load_batch(&mut conn, &row).await?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn get_batch_by_idempotency_key(&self, idempotency_key: &str) -> Result<Option<TransferBatch>, String> {
+        let mut conn = self.session.acquire().await?;
+
+        let row = sqlx::query(&format!("SELECT {} FROM transfer_batches WHERE idempotency_key = ?", BATCH_COLUMNS))
+            .bind(idempotency_key)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| format!("Database error: {}", e))?;
+
+        match row {
+            Some(row) => Ok(Some(load_batch(&mut conn, &row).await?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn get_unfinished_batches(&self, limit: u32) -> Result<Vec<TransferBatch>, String> {
+        let mut conn = self.session.acquire().await?;
+
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM transfer_batches WHERE status IN ('pending', 'processing') ORDER BY created_at, batch_id LIMIT ?",
+            BATCH_COLUMNS
+        ))
+        .bind(limit as i64)
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| format!("Database error: {}", e))?;
+
+        let mut batches = Vec::new();
+        for row in rows {
+            batches.push(load_batch(&mut conn, &row).await?);
+        }
+
+        Ok(batches)
+    }
+
+    async fn update_batch(&self, batch: &TransferBatch) -> Result<(), String> {
+        let mut conn = self.session.acquire().await?;
+
+        sqlx::query(
+            r#"
+            UPDATE transfer_batches
+            SET status = ?, succeeded_items = ?, failed_items = ?, updated_at = ?, completed_at = ?
+            WHERE batch_id = ?
+            "#,
+        )
+        .bind(batch.status.to_string())
+        .bind(batch.succeeded_items as i64)
+        .bind(batch.failed_items as i64)
+        .bind(batch.updated_at.to_rfc3339())
+        .bind(batch.completed_at.map(|completed_at| completed_at.to_rfc3339()))
+        .bind(&batch.batch_id)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to update batch: {}", e))?;
+
+        Ok(())
+    }
+
+    async fn update_batch_item(&self, batch_id: &str, item: &BatchItem) -> Result<(), String> {
+        let mut conn = self.session.acquire().await?;
+
+        sqlx::query(
+            r#"
+            UPDATE transfer_batch_items
+            SET status = ?, transfer_idem_key = ?, transfer_status = ?, error_code = ?, error_message = ?
+            WHERE batch_id = ? AND item_index = ?
+            "#,
+        )
+        .bind(item.status.to_string())
+        .bind(&item.transfer_idem_key)
+        .bind(item.transfer_status.map(|status| status.to_string()))
+        .bind(&item.error_code)
+        .bind(&item.error_message)
+        .bind(batch_id)
+        .bind(item.index as i64)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to update batch item: {}", e))?;
+
+        Ok(())
+    }
+}
\ No newline at end of file
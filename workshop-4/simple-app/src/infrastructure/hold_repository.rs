@@ -0,0 +1,170 @@
+use async_trait::async_trait;
+use sqlx::{SqlitePool, Row};
+use sqlx::sqlite::SqliteRow;
+use chrono::{DateTime, Utc};
+use super::unit_of_work::SqliteSession;
+use crate::domain::{PointHold, PointHoldDb, HoldRepository};
+
+const HOLD_COLUMNS: &str = "hold_id, user_id, amount, captured_amount, status, reference, ledger_entry_id, expires_at, created_at, updated_at";
+
+#[derive(Clone)]
+pub struct SqliteHoldRepository {
+    session: SqliteSession,
+}
+
+impl SqliteHoldRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { session: SqliteSession::Pool(pool) }
+    }
+
+    pub fn with_session(session: SqliteSession) -> Self {
+        Self { session }
+    }
+
+    pub async fn init_database(&self) -> Result<(), String> {
+        let mut conn = self.session.acquire().await?;
+
+        // Create point_holds table
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS point_holds (
+              hold_id TEXT PRIMARY KEY,
+              user_id INTEGER NOT NULL,
+              amount INTEGER NOT NULL CHECK (amount > 0),
+              captured_amount INTEGER NOT NULL DEFAULT 0 CHECK (captured_amount >= 0 AND captured_amount <= amount),
+              status TEXT NOT NULL CHECK (status IN ('active','captured','released','expired')),
+              reference TEXT,
+              ledger_entry_id INTEGER,
+              expires_at TEXT NOT NULL,
+              created_at TEXT NOT NULL,
+              updated_at TEXT NOT NULL,
+              FOREIGN KEY (user_id) REFERENCES users(id),
+              FOREIGN KEY (ledger_entry_id) REFERENCES point_ledger(id)
+            )
+            "#,
+        )
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to create point_holds table: {}", e))?;
+
+        // Create indexes
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_holds_user ON point_holds(user_id, status)")
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| format!("Failed to create index: {}", e))?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_holds_expiry ON point_holds(status, expires_at)")
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| format!("Failed to create index: {}", e))?;
+
+        Ok(())
+    }
+}
+
+fn hold_from_row(row: &SqliteRow) -> Result<PointHold, String> {
+    let hold_db = PointHoldDb {
+        hold_id: row.get("hold_id"),
+        user_id: row.get::<i64, _>("user_id") as u32,
+        amount: row.get::<i64, _>("amount") as u32,
+        captured_amount: row.get::<i64, _>("captured_amount") as u32,
+        status: row.get("status"),
+        reference: row.get("reference"),
+        ledger_entry_id: row.get::<Option<i64>, _>("ledger_entry_id").map(|id| id as u32),
+        expires_at: row.get("expires_at"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    };
+    hold_db.into_domain()
+}
+
+#[async_trait]
+impl HoldRepository for SqliteHoldRepository {
+    async fn create_hold(&self, hold: &PointHold) -> Result<(), String> {
+        let mut conn = self.session.acquire().await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO point_holds (hold_id, user_id, amount, captured_amount, status, reference, ledger_entry_id, expires_at, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&hold.hold_id)
+        .bind(hold.user_id as i64)
+        .bind(hold.amount as i64)
+        .bind(hold.captured_amount as i64)
+        .bind(hold.status.to_string())
+        .bind(&hold.reference)
+        .bind(hold.ledger_entry_id.map(|id| id as i64))
+        .bind(hold.expires_at.to_rfc3339())
+        .bind(hold.created_at.to_rfc3339())
+        .bind(hold.updated_at.to_rfc3339())
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to create hold: {}", e))?;
+
+        Ok(())
+    }
+
+    async fn get_hold(&self, hold_id: &str) -> Result<Option<PointHold>, String> {
+        let mut conn = self.session.acquire().await?;
+
+        let row = sqlx::query(&format!("SELECT {} FROM point_holds WHERE hold_id = ?", HOLD_COLUMNS))
+            .bind(hold_id)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| format!("Database error: {}", e))?;
+
+        row.as_ref().map(hold_from_row).transpose()
+    }
+
+    async fn get_holds_by_user_id(&self, user_id: u32) -> Result<Vec<PointHold>, String> {
+        let mut conn = self.session.acquire().await?;
+
+        let rows = sqlx::query(&format!("SELECT {} FROM point_holds WHERE user_id = ? ORDER BY created_at DESC", HOLD_COLUMNS))
+            .bind(user_id as i64)
+            .fetch_all(&mut *conn)
+            .await
+            .map_err(|e| format!("Database error: {}", e))?;
+
+        rows.iter().map(hold_from_row).collect()
+    }
+
+    async fn get_expired_holds(&self, now: DateTime<Utc>, limit: u32) -> Result<Vec<PointHold>, String> {
+        let mut conn = self.session.acquire().await?;
+
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM point_holds WHERE status = 'active' AND expires_at <= ? ORDER BY expires_at, hold_id LIMIT ?",
+            HOLD_COLUMNS
+        ))
+        .bind(now.to_rfc3339())
+        .bind(limit as i64)
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| format!("Database error: {}", e))?;
+
+        rows.iter().map(hold_from_row).collect()
+    }
+
+    async fn update_hold(&self, hold: &PointHold) -> Result<(), String> {
+        let mut conn = self.session.acquire().await?;
+
+        sqlx::query(
+            r#"
+            UPDATE point_holds
+            SET captured_amount = ?, status = ?, ledger_entry_id = ?, updated_at = ?
+            WHERE hold_id = ?
+            "#,
+        )
+        .bind(hold.captured_amount as i64)
+        .bind(hold.status.to_string())
+        .bind(hold.ledger_entry_id.map(|id| id as i64))
+        .bind(hold.updated_at.to_rfc3339())
+        .bind(&hold.hold_id)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to update hold: {}", e))?;
+
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,171 @@
+use async_trait::async_trait;
+use sqlx::{SqlitePool, SqliteConnection, Row};
+use chrono::{DateTime, Utc};
+use super::unit_of_work::SqliteSession;
+use crate::domain::{IdempotencyRecord, IdempotencyRecordDb, IdempotencyRepository};
+
+#[derive(Clone)]
+pub struct SqliteIdempotencyRepository {
+    session: SqliteSession,
+}
+
+impl SqliteIdempotencyRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { session: SqliteSession::Pool(pool) }
+    }
+
+    pub fn with_session(session: SqliteSession) -> Self {
+        Self { session }
+    }
+
+    pub async fn init_database(&self) -> Result<(), String> {
+        let mut conn = self.session.acquire().await?;
+
+        // Create idempotency_keys table
+        sqlx::query(&idempotency_keys_table_sql("idempotency_keys"))
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| format!("Failed to create idempotency_keys table: {}", e))?;
+
+        migrate_key_scope(&mut conn).await?;
+
+        // Create indexes
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_idempotency_expires ON idempotency_keys(expires_at)")
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| format!("Failed to create index: {}", e))?;
+
+        Ok(())
+    }
+}
+
+fn idempotency_keys_table_sql(table: &str) -> String {
+    format!(
+        r#"
+        CREATE TABLE IF NOT EXISTS {} (
+          from_user_id INTEGER NOT NULL,
+          key TEXT NOT NULL,
+          request_fingerprint TEXT NOT NULL,
+          transfer_idem_key TEXT NOT NULL,
+          created_at TEXT NOT NULL,
+          expires_at TEXT NOT NULL,
+          PRIMARY KEY (from_user_id, key),
+          FOREIGN KEY (from_user_id) REFERENCES users(id),
+          FOREIGN KEY (transfer_idem_key) REFERENCES transfers(idempotency_key)
+        )
+        "#,
+        table
+    )
+}
+
+// Keys used to be global. SQLite cannot change a primary key in place, so an older table is copied into the
+// new shape, each key going to the sender of the transfer it points at.
+async fn migrate_key_scope(conn: &mut SqliteConnection) -> Result<(), String> {
+    let scoped: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM pragma_table_info('idempotency_keys') WHERE name = 'from_user_id'")
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to inspect idempotency_keys table: {}", e))?;
+    if scoped > 0 {
+        return Ok(());
+    }
+
+    let mut tx = sqlx::Connection::begin(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to begin transaction: {}", e))?;
+
+    sqlx::query(&idempotency_keys_table_sql("idempotency_keys_new"))
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to create idempotency_keys table: {}", e))?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO idempotency_keys_new (from_user_id, key, request_fingerprint, transfer_idem_key, created_at, expires_at)
+        SELECT t.from_user_id, k.key, k.request_fingerprint, k.transfer_idem_key, k.created_at, k.expires_at
+        FROM idempotency_keys k
+        JOIN transfers t ON t.idempotency_key = k.transfer_idem_key
+        "#,
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| format!("Failed to copy idempotency_keys: {}", e))?;
+
+    sqlx::query("DROP TABLE idempotency_keys")
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to drop old idempotency_keys: {}", e))?;
+
+    sqlx::query("ALTER TABLE idempotency_keys_new RENAME TO idempotency_keys")
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to rename idempotency_keys: {}", e))?;
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit transaction: {}", e))
+}
+
+#[async_trait]
+impl IdempotencyRepository for SqliteIdempotencyRepository {
+    async fn get_record(&self, from_user_id: u32, key: &str) -> Result<Option<IdempotencyRecord>, String> {
+        let mut conn = self.session.acquire().await?;
+
+        let row = sqlx::query(
+            "SELECT from_user_id, key, request_fingerprint, transfer_idem_key, created_at, expires_at FROM idempotency_keys WHERE from_user_id = ? AND key = ?"
+        )
+        .bind(from_user_id as i64)
+        .bind(key)
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(|e| format!("Database error: {}", e))?;
+
+        match row {
+            Some(row) => {
+                let record_db = IdempotencyRecordDb {
+                    from_user_id: row.get::<i64, _>("from_user_id") as u32,
+                    key: row.get("key"),
+                    request_fingerprint: row.get("request_fingerprint"),
+                    transfer_idem_key: row.get("transfer_idem_key"),
+                    created_at: row.get("created_at"),
+                    expires_at: row.get("expires_at"),
+                };
+                Ok(Some(record_db.into_domain()?))
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn create_record(&self, record: IdempotencyRecord) -> Result<(), String> {
+        let mut conn = self.session.acquire().await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO idempotency_keys (from_user_id, key, request_fingerprint, transfer_idem_key, created_at, expires_at)
+            VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(record.from_user_id as i64)
+        .bind(&record.key)
+        .bind(&record.request_fingerprint)
+        .bind(&record.transfer_idem_key)
+        .bind(record.created_at.to_rfc3339())
+        .bind(record.expires_at.to_rfc3339())
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to create idempotency key: {}", e))?;
+
+        Ok(())
+    }
+
+    async fn delete_expired(&self, now: DateTime<Utc>) -> Result<u64, String> {
+        let mut conn = self.session.acquire().await?;
+
+        let result = sqlx::query("DELETE FROM idempotency_keys WHERE expires_at <= ?")
+            .bind(now.to_rfc3339())
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| format!("Failed to delete expired idempotency keys: {}", e))?;
+
+        Ok(result.rows_affected())
+    }
+}
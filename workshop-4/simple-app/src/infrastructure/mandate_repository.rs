@@ -0,0 +1,288 @@
+use async_trait::async_trait;
+use sqlx::{SqlitePool, Row};
+use sqlx::sqlite::SqliteRow;
+use chrono::{DateTime, Utc};
+use super::unit_of_work::SqliteSession;
+use crate::domain::{TransferMandate, TransferMandateDb, MandateStatus, CreateMandateRequest, MandateRun, MandateRunDb, MandateRepository};
+
+const MANDATE_COLUMNS: &str = "id, from_user_id, to_user_id, amount, note, frequency, start_at, end_at, max_runs, run_count, next_occurrence, next_run_at, status, last_transfer_idem_key, last_error, created_at, updated_at";
+
+#[derive(Clone)]
+pub struct SqliteMandateRepository {
+    session: SqliteSession,
+}
+
+impl SqliteMandateRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { session: SqliteSession::Pool(pool) }
+    }
+
+    pub fn with_session(session: SqliteSession) -> Self {
+        Self { session }
+    }
+
+    pub async fn init_database(&self) -> Result<(), String> {
+        let mut conn = self.session.acquire().await?;
+
+        // Create transfer_mandates table
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS transfer_mandates (
+              id INTEGER PRIMARY KEY AUTOINCREMENT,
+              from_user_id INTEGER NOT NULL,
+              to_user_id INTEGER NOT NULL,
+              amount INTEGER NOT NULL CHECK (amount > 0),
+              note TEXT,
+              frequency TEXT NOT NULL CHECK (frequency IN ('daily','weekly','monthly')),
+              start_at TEXT NOT NULL,
+              end_at TEXT,
+              max_runs INTEGER CHECK (max_runs IS NULL OR max_runs > 0),
+              run_count INTEGER NOT NULL DEFAULT 0,
+              next_occurrence INTEGER NOT NULL DEFAULT 0,
+              next_run_at TEXT,
+              status TEXT NOT NULL CHECK (status IN ('active','paused','completed','cancelled')),
+              last_transfer_idem_key TEXT,
+              last_error TEXT,
+              created_at TEXT NOT NULL,
+              updated_at TEXT NOT NULL,
+              FOREIGN KEY (from_user_id) REFERENCES users(id),
+              FOREIGN KEY (to_user_id) REFERENCES users(id)
+            )
+            "#,
+        )
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to create transfer_mandates table: {}", e))?;
+
+        // Create indexes
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_mandates_due ON transfer_mandates(status, next_run_at)")
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| format!("Failed to create index: {}", e))?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_mandates_from ON transfer_mandates(from_user_id)")
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| format!("Failed to create index: {}", e))?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_mandates_to ON transfer_mandates(to_user_id)")
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| format!("Failed to create index: {}", e))?;
+
+        // Create mandate_runs table; the primary key stops an occurrence from ever paying out twice
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS mandate_runs (
+              mandate_id INTEGER NOT NULL,
+              occurrence INTEGER NOT NULL,
+              transfer_idem_key TEXT NOT NULL,
+              created_at TEXT NOT NULL,
+              PRIMARY KEY (mandate_id, occurrence),
+              FOREIGN KEY (mandate_id) REFERENCES transfer_mandates(id),
+              FOREIGN KEY (transfer_idem_key) REFERENCES transfers(idempotency_key)
+            )
+            "#,
+        )
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to create mandate_runs table: {}", e))?;
+
+        Ok(())
+    }
+}
+
+fn mandate_from_row(row: &SqliteRow) -> Result<TransferMandate, String> {
+    let mandate_db = TransferMandateDb {
+        id: row.get::<i64, _>("id") as u32,
+        from_user_id: row.get::<i64, _>("from_user_id") as u32,
+        to_user_id: row.get::<i64, _>("to_user_id") as u32,
+        amount: row.get::<i64, _>("amount") as u32,
+        note: row.get("note"),
+        frequency: row.get("frequency"),
+        start_at: row.get("start_at"),
+        end_at: row.get("end_at"),
+        max_runs: row.get::<Option<i64>, _>("max_runs").map(|max_runs| max_runs as u32),
+        run_count: row.get::<i64, _>("run_count") as u32,
+        next_occurrence: row.get::<i64, _>("next_occurrence") as u32,
+        next_run_at: row.get("next_run_at"),
+        status: row.get("status"),
+        last_transfer_idem_key: row.get("last_transfer_idem_key"),
+        last_error: row.get("last_error"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    };
+    mandate_db.into_domain()
+}
+
+#[async_trait]
+impl MandateRepository for SqliteMandateRepository {
+    async fn create_mandate(&self, request: CreateMandateRequest, start_at: DateTime<Utc>) -> Result<TransferMandate, String> {
+        request.validate()?;
+
+        let mut conn = self.session.acquire().await?;
+        let now = Utc::now();
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO transfer_mandates (from_user_id, to_user_id, amount, note, frequency, start_at, end_at, max_runs, run_count, next_occurrence, next_run_at, status, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, 0, 0, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(request.from_user_id as i64)
+        .bind(request.to_user_id as i64)
+        .bind(request.amount as i64)
+        .bind(&request.note)
+        .bind(request.frequency.to_string())
+        .bind(start_at.to_rfc3339())
+        .bind(request.end_at.map(|end_at| end_at.to_rfc3339()))
+        .bind(request.max_runs.map(|max_runs| max_runs as i64))
+        .bind(start_at.to_rfc3339())
+        .bind(MandateStatus::Active.to_string())
+        .bind(now.to_rfc3339())
+        .bind(now.to_rfc3339())
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to create mandate: {}", e))?;
+
+        Ok(TransferMandate {
+            id: result.last_insert_rowid() as u32,
+            from_user_id: request.from_user_id,
+            to_user_id: request.to_user_id,
+            amount: request.amount,
+            note: request.note,
+            frequency: request.frequency,
+            start_at,
+            end_at: request.end_at,
+            max_runs: request.max_runs,
+            run_count: 0,
+            next_occurrence: 0,
+            next_run_at: Some(start_at),
+            status: MandateStatus::Active,
+            last_transfer_idem_key: None,
+            last_error: None,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    async fn get_mandate(&self, id: u32) -> Result<Option<TransferMandate>, String> {
+        let mut conn = self.session.acquire().await?;
+
+        let row = sqlx::query(&format!("SELECT {} FROM transfer_mandates WHERE id = ?", MANDATE_COLUMNS))
+            .bind(id as i64)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| format!("Database error: {}", e))?;
+
+        match row {
+            Some(row) => Ok(Some(mandate_from_row(&row)?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn get_mandates_by_user_id(&self, user_id: u32) -> Result<Vec<TransferMandate>, String> {
+        let mut conn = self.session.acquire().await?;
+
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM transfer_mandates WHERE from_user_id = ? OR to_user_id = ? ORDER BY id DESC",
+            MANDATE_COLUMNS
+        ))
+        .bind(user_id as i64)
+        .bind(user_id as i64)
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| format!("Database error: {}", e))?;
+
+        rows.iter().map(mandate_from_row).collect()
+    }
+
+    async fn get_due_mandates(&self, due_at: DateTime<Utc>, limit: u32) -> Result<Vec<TransferMandate>, String> {
+        let mut conn = self.session.acquire().await?;
+
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM transfer_mandates WHERE status = 'active' AND next_run_at <= ? ORDER BY next_run_at, id LIMIT ?",
+            MANDATE_COLUMNS
+        ))
+        .bind(due_at.to_rfc3339())
+        .bind(limit as i64)
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| format!("Database error: {}", e))?;
+
+        rows.iter().map(mandate_from_row).collect()
+    }
+
+    async fn update_mandate(&self, mandate: &TransferMandate) -> Result<(), String> {
+        let mut conn = self.session.acquire().await?;
+
+        let result = sqlx::query(
+            r#"
+            UPDATE transfer_mandates
+            SET amount = ?, note = ?, end_at = ?, max_runs = ?, run_count = ?, next_occurrence = ?, next_run_at = ?,
+                status = ?, last_transfer_idem_key = ?, last_error = ?, updated_at = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(mandate.amount as i64)
+        .bind(&mandate.note)
+        .bind(mandate.end_at.map(|end_at| end_at.to_rfc3339()))
+        .bind(mandate.max_runs.map(|max_runs| max_runs as i64))
+        .bind(mandate.run_count as i64)
+        .bind(mandate.next_occurrence as i64)
+        .bind(mandate.next_run_at.map(|next_run_at| next_run_at.to_rfc3339()))
+        .bind(mandate.status.to_string())
+        .bind(&mandate.last_transfer_idem_key)
+        .bind(&mandate.last_error)
+        .bind(mandate.updated_at.to_rfc3339())
+        .bind(mandate.id as i64)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to update mandate: {}", e))?;
+
+        if result.rows_affected() == 0 {
+            return Err("Mandate not found".to_string());
+        }
+
+        Ok(())
+    }
+
+    async fn get_run(&self, mandate_id: u32, occurrence: u32) -> Result<Option<MandateRun>, String> {
+        let mut conn = self.session.acquire().await?;
+
+        let row = sqlx::query("SELECT mandate_id, occurrence, transfer_idem_key, created_at FROM mandate_runs WHERE mandate_id = ? AND occurrence = ?")
+            .bind(mandate_id as i64)
+            .bind(occurrence as i64)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| format!("Database error: {}", e))?;
+
+        match row {
+            Some(row) => {
+                let run_db = MandateRunDb {
+                    mandate_id: row.get::<i64, _>("mandate_id") as u32,
+                    occurrence: row.get::<i64, _>("occurrence") as u32,
+                    transfer_idem_key: row.get("transfer_idem_key"),
+                    created_at: row.get("created_at"),
+                };
+                Ok(Some(run_db.into_domain()?))
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn create_run(&self, run: &MandateRun) -> Result<(), String> {
+        let mut conn = self.session.acquire().await?;
+
+        sqlx::query("INSERT INTO mandate_runs (mandate_id, occurrence, transfer_idem_key, created_at) VALUES (?, ?, ?, ?)")
+            .bind(run.mandate_id as i64)
+            .bind(run.occurrence as i64)
+            .bind(&run.transfer_idem_key)
+            .bind(run.created_at.to_rfc3339())
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| format!("Failed to record mandate run: {}", e))?;
+
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,21 @@
+use sqlx::SqliteConnection;
+
+// Adds a column to a table created by an older version of the schema (CREATE TABLE IF NOT EXISTS won't);
+// true when the column had to be added
+pub async fn add_column_if_missing(conn: &mut SqliteConnection, table: &str, column: &str, definition: &str) -> Result<bool, String> {
+    let exists: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM pragma_table_info(?) WHERE name = ?")
+        .bind(table)
+        .bind(column)
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to inspect {} table: {}", table, e))?;
+
+    if exists == 0 {
+        sqlx::query(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition))
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| format!("Failed to add {}.{} column: {}", table, column, e))?;
+    }
+
+    Ok(exists == 0)
+}
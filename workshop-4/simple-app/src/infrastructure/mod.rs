@@ -1,5 +1,28 @@
 pub mod repository;
 pub mod transfer_repository;
+pub mod unit_of_work;
+pub mod idempotency_repository;
+pub mod migrations;
+pub mod mandate_repository;
+pub mod batch_repository;
+pub mod transfer_limit_repository;
+pub mod hold_repository;
+pub mod adjustment_repository;
+pub mod tier_repository;
+pub mod auth_repository;
+pub mod otp_sender;
+#[cfg(test)]
+pub mod test_support;
 
 pub use repository::SqliteUserRepository;
-pub use transfer_repository::{SqliteTransferRepository, SqlitePointLedgerRepository};
\ No newline at end of file
+pub use transfer_repository::{SqliteTransferRepository, SqlitePointLedgerRepository};
+pub use unit_of_work::SqliteUnitOfWorkFactory;
+pub use idempotency_repository::SqliteIdempotencyRepository;
+pub use mandate_repository::SqliteMandateRepository;
+pub use batch_repository::SqliteBatchRepository;
+pub use transfer_limit_repository::SqliteTransferLimitRepository;
+pub use hold_repository::SqliteHoldRepository;
+pub use adjustment_repository::SqliteAdjustmentRepository;
+pub use tier_repository::SqliteTierRepository;
+pub use auth_repository::SqliteAuthRepository;
+pub use otp_sender::ConsoleOtpSender;
\ No newline at end of file
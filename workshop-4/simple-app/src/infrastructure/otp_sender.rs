@@ -0,0 +1,12 @@
+use crate::domain::OtpSender;
+
+// Stand-in for an email/SMS gateway: prints the code to the server log
+#[derive(Debug, Clone, Default)]
+pub struct ConsoleOtpSender;
+
+impl OtpSender for ConsoleOtpSender {
+    fn send(&self, destination: &str, code: &str) -> Result<(), String> {
+        println!("📨 Login code for {}: {}", destination, code);
+        Ok(())
+    }
+}
\ No newline at end of file
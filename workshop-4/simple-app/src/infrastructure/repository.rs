@@ -1,7 +1,11 @@
 use async_trait::async_trait;
 use sqlx::{SqlitePool, Row};
+use sqlx::sqlite::SqliteRow;
 use chrono::{DateTime, Utc};
-use crate::domain::{User, UserRepository, CreateUserRequest, UpdateUserRequest};
+use crate::domain::{User, UserRole, UserRepository, CreateUserRequest, UpdateUserRequest};
+use super::migrations::add_column_if_missing;
+
+const USER_COLUMNS: &str = "id, first_name, last_name, phone, email, member_since, membership_level, role, points, created_at, updated_at";
 
 #[derive(Clone)]
 pub struct SqliteUserRepository {
@@ -34,6 +38,34 @@ impl SqliteUserRepository {
         .await
         .map_err(|e| format!("Failed to create users table: {}", e))?;
 
+        let mut conn = self.pool.acquire()
+            .await
+            .map_err(|e| format!("Database error: {}", e))?;
+        add_column_if_missing(&mut conn, "users", "role", "TEXT NOT NULL DEFAULT 'Member'").await?;
+        drop(conn);
+
+        // Phone numbers sign users in, so like emails they belong to one account
+        sqlx::query("CREATE UNIQUE INDEX IF NOT EXISTS idx_users_phone ON users(phone)")
+            .execute(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to create users phone index (do two users share a phone number?): {}", e))?;
+
+        // membership_level used to be free text; anything that is not a tier name starts over at Bronze
+        sqlx::query(
+            r#"
+            UPDATE users SET membership_level = CASE lower(trim(membership_level))
+                WHEN 'silver' THEN 'Silver'
+                WHEN 'gold' THEN 'Gold'
+                WHEN 'platinum' THEN 'Platinum'
+                ELSE 'Bronze'
+            END
+            WHERE membership_level NOT IN ('Bronze', 'Silver', 'Gold', 'Platinum')
+            "#,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to normalize membership levels: {}", e))?;
+
         // Insert sample data if table is empty
         let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM users")
             .fetch_one(&self.pool)
@@ -101,83 +133,68 @@ impl SqliteUserRepository {
     }
 }
 
+fn row_to_user(row: &SqliteRow) -> Result<User, String> {
+    Ok(User {
+        id: row.get::<i64, _>("id") as u32,
+        first_name: row.get("first_name"),
+        last_name: row.get("last_name"),
+        phone: row.get("phone"),
+        email: row.get("email"),
+        member_since: DateTime::parse_from_rfc3339(row.get("member_since"))
+            .map_err(|e| format!("Invalid datetime: {}", e))?
+            .with_timezone(&Utc),
+        membership_level: row.get::<String, _>("membership_level").parse()?,
+        role: row.get::<String, _>("role").parse()?,
+        points: row.get("points"),
+        created_at: DateTime::parse_from_rfc3339(row.get("created_at"))
+            .map_err(|e| format!("Invalid datetime: {}", e))?
+            .with_timezone(&Utc),
+        updated_at: DateTime::parse_from_rfc3339(row.get("updated_at"))
+            .map_err(|e| format!("Invalid datetime: {}", e))?
+            .with_timezone(&Utc),
+    })
+}
+
 #[async_trait]
 impl UserRepository for SqliteUserRepository {
     async fn get_user_by_id(&self, id: u32) -> Result<Option<User>, String> {
-        let row = sqlx::query(
-            "SELECT id, first_name, last_name, phone, email, member_since, membership_level, points, created_at, updated_at FROM users WHERE id = ?"
-        )
-        .bind(id as i64)
-        .fetch_optional(&self.pool)
-        .await
-        .map_err(|e| format!("Database error: {}", e))?;
-
-        match row {
-            Some(row) => {
-                let user = User {
-                    id: row.get::<i64, _>("id") as u32,
-                    first_name: row.get("first_name"),
-                    last_name: row.get("last_name"),
-                    phone: row.get("phone"),
-                    email: row.get("email"),
-                    member_since: DateTime::parse_from_rfc3339(row.get("member_since"))
-                        .map_err(|e| format!("Invalid datetime: {}", e))?
-                        .with_timezone(&Utc),
-                    membership_level: row.get("membership_level"),
-                    points: row.get("points"),
-                    created_at: DateTime::parse_from_rfc3339(row.get("created_at"))
-                        .map_err(|e| format!("Invalid datetime: {}", e))?
-                        .with_timezone(&Utc),
-                    updated_at: DateTime::parse_from_rfc3339(row.get("updated_at"))
-                        .map_err(|e| format!("Invalid datetime: {}", e))?
-                        .with_timezone(&Utc),
-                };
-                Ok(Some(user))
-            }
-            None => Ok(None),
-        }
+        let row = sqlx::query(&format!("SELECT {} FROM users WHERE id = ?", USER_COLUMNS))
+            .bind(id as i64)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| format!("Database error: {}", e))?;
+
+        row.as_ref().map(row_to_user).transpose()
     }
 
     async fn get_user_by_email(&self, email: &str) -> Result<Option<User>, String> {
-        let row = sqlx::query(
-            "SELECT id, first_name, last_name, phone, email, member_since, membership_level, points, created_at, updated_at FROM users WHERE email = ?"
-        )
-        .bind(email)
-        .fetch_optional(&self.pool)
-        .await
-        .map_err(|e| format!("Database error: {}", e))?;
-
-        match row {
-            Some(row) => {
-                let user = User {
-                    id: row.get::<i64, _>("id") as u32,
-                    first_name: row.get("first_name"),
-                    last_name: row.get("last_name"),
-                    phone: row.get("phone"),
-                    email: row.get("email"),
-                    member_since: DateTime::parse_from_rfc3339(row.get("member_since"))
-                        .map_err(|e| format!("Invalid datetime: {}", e))?
-                        .with_timezone(&Utc),
-                    membership_level: row.get("membership_level"),
-                    points: row.get("points"),
-                    created_at: DateTime::parse_from_rfc3339(row.get("created_at"))
-                        .map_err(|e| format!("Invalid datetime: {}", e))?
-                        .with_timezone(&Utc),
-                    updated_at: DateTime::parse_from_rfc3339(row.get("updated_at"))
-                        .map_err(|e| format!("Invalid datetime: {}", e))?
-                        .with_timezone(&Utc),
-                };
-                Ok(Some(user))
-            }
-            None => Ok(None),
-        }
+        let row = sqlx::query(&format!("SELECT {} FROM users WHERE email = ?", USER_COLUMNS))
+            .bind(email)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| format!("Database error: {}", e))?;
+
+        row.as_ref().map(row_to_user).transpose()
+    }
+
+    async fn get_user_by_phone(&self, phone: &str) -> Result<Option<User>, String> {
+        let row = sqlx::query(&format!("SELECT {} FROM users WHERE phone = ?", USER_COLUMNS))
+            .bind(phone)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| format!("Database error: {}", e))?;
+
+        row.as_ref().map(row_to_user).transpose()
     }
 
     async fn create_user(&self, user_request: CreateUserRequest) -> Result<User, String> {
-        // Check if email already exists
-        if let Some(_) = self.get_user_by_email(&user_request.email).await? {
+        // Check if email or phone already exists
+        if self.get_user_by_email(&user_request.email).await?.is_some() {
             return Err("Email already exists".to_string());
         }
+        if self.get_user_by_phone(&user_request.phone).await?.is_some() {
+            return Err("Phone already exists".to_string());
+        }
 
         let user = User::new(
             0, // Will be replaced by auto-increment
@@ -185,7 +202,6 @@ impl UserRepository for SqliteUserRepository {
             user_request.last_name,
             user_request.phone,
             user_request.email,
-            user_request.membership_level,
         );
 
         user.validate()?;
@@ -201,7 +217,7 @@ impl UserRepository for SqliteUserRepository {
         .bind(&user.phone)
         .bind(&user.email)
         .bind(user.member_since.to_rfc3339())
-        .bind(&user.membership_level)
+        .bind(user.membership_level.to_string())
         .bind(user.points)
         .bind(user.created_at.to_rfc3339())
         .bind(user.updated_at.to_rfc3339())
@@ -224,12 +240,19 @@ impl UserRepository for SqliteUserRepository {
             .ok_or("User not found".to_string())?;
 
         // Check if email is being updated and if it already exists
-        if let Some(ref new_email) = update_request.email {
-            if new_email != &user.email {
-                if let Some(_) = self.get_user_by_email(new_email).await? {
-                    return Err("Email already exists".to_string());
-                }
-            }
+        if let Some(ref new_email) = update_request.email
+            && new_email != &user.email
+            && self.get_user_by_email(new_email).await?.is_some()
+        {
+            return Err("Email already exists".to_string());
+        }
+
+        // Same for the phone
+        if let Some(ref new_phone) = update_request.phone
+            && new_phone != &user.phone
+            && self.get_user_by_phone(new_phone).await?.is_some()
+        {
+            return Err("Phone already exists".to_string());
         }
 
         user.update_fields(update_request);
@@ -238,7 +261,7 @@ impl UserRepository for SqliteUserRepository {
         sqlx::query(
             r#"
             UPDATE users 
-            SET first_name = ?, last_name = ?, phone = ?, email = ?, membership_level = ?, updated_at = ?
+            SET first_name = ?, last_name = ?, phone = ?, email = ?, updated_at = ?
             WHERE id = ?
             "#,
         )
@@ -246,7 +269,6 @@ impl UserRepository for SqliteUserRepository {
         .bind(&user.last_name)
         .bind(&user.phone)
         .bind(&user.email)
-        .bind(&user.membership_level)
         .bind(user.updated_at.to_rfc3339())
         .bind(id as i64)
         .execute(&self.pool)
@@ -266,42 +288,33 @@ impl UserRepository for SqliteUserRepository {
         Ok(result.rows_affected() > 0)
     }
 
+    async fn set_role(&self, id: u32, role: UserRole) -> Result<User, String> {
+        let result = sqlx::query("UPDATE users SET role = ?, updated_at = ? WHERE id = ?")
+            .bind(role.to_string())
+            .bind(Utc::now().to_rfc3339())
+            .bind(id as i64)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to set user role: {}", e))?;
+        if result.rows_affected() == 0 {
+            return Err("User not found".to_string());
+        }
+
+        self.get_user_by_id(id).await?
+            .ok_or("User not found".to_string())
+    }
+
     async fn list_users(&self, limit: Option<i64>, offset: Option<i64>) -> Result<Vec<User>, String> {
         let limit = limit.unwrap_or(100);
         let offset = offset.unwrap_or(0);
 
-        let rows = sqlx::query(
-            "SELECT id, first_name, last_name, phone, email, member_since, membership_level, points, created_at, updated_at FROM users ORDER BY created_at DESC LIMIT ? OFFSET ?"
-        )
-        .bind(limit)
-        .bind(offset)
-        .fetch_all(&self.pool)
-        .await
-        .map_err(|e| format!("Database error: {}", e))?;
-
-        let mut users = Vec::new();
-        for row in rows {
-            let user = User {
-                id: row.get::<i64, _>("id") as u32,
-                first_name: row.get("first_name"),
-                last_name: row.get("last_name"),
-                phone: row.get("phone"),
-                email: row.get("email"),
-                member_since: DateTime::parse_from_rfc3339(row.get("member_since"))
-                    .map_err(|e| format!("Invalid datetime: {}", e))?
-                    .with_timezone(&Utc),
-                membership_level: row.get("membership_level"),
-                points: row.get("points"),
-                created_at: DateTime::parse_from_rfc3339(row.get("created_at"))
-                    .map_err(|e| format!("Invalid datetime: {}", e))?
-                    .with_timezone(&Utc),
-                updated_at: DateTime::parse_from_rfc3339(row.get("updated_at"))
-                    .map_err(|e| format!("Invalid datetime: {}", e))?
-                    .with_timezone(&Utc),
-            };
-            users.push(user);
-        }
+        let rows = sqlx::query(&format!("SELECT {} FROM users ORDER BY created_at DESC LIMIT ? OFFSET ?", USER_COLUMNS))
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| format!("Database error: {}", e))?;
 
-        Ok(users)
+        rows.iter().map(row_to_user).collect()
     }
 }
\ No newline at end of file
@@ -0,0 +1,53 @@
+use std::sync::Mutex;
+use chrono::{DateTime, Duration, Utc};
+use sqlx::SqlitePool;
+use sqlx::sqlite::SqliteConnectOptions;
+use super::{
+    SqliteUserRepository, SqliteTransferRepository, SqlitePointLedgerRepository, SqliteIdempotencyRepository,
+    SqliteMandateRepository, SqliteBatchRepository, SqliteTransferLimitRepository, SqliteHoldRepository,
+    SqliteAdjustmentRepository, SqliteTierRepository, SqliteAuthRepository,
+};
+use crate::domain::Clock;
+
+// A fresh database file with every table created and the seed users (1500, 750 and 200 points) loaded,
+// the same way main() sets it up. The default transfer limits are removed so tests are not capped by them.
+pub async fn test_pool() -> SqlitePool {
+    let path = std::env::temp_dir().join(format!("simple-app-test-{}.db", uuid::Uuid::new_v4()));
+    let options = SqliteConnectOptions::new().filename(&path).create_if_missing(true);
+    let pool = SqlitePool::connect_with(options).await.expect("open test database");
+
+    SqliteUserRepository::new(pool.clone()).init_database().await.unwrap();
+    SqliteTransferRepository::new(pool.clone()).init_database().await.unwrap();
+    SqlitePointLedgerRepository::new(pool.clone()).init_database().await.unwrap();
+    SqliteIdempotencyRepository::new(pool.clone()).init_database().await.unwrap();
+    SqliteMandateRepository::new(pool.clone()).init_database().await.unwrap();
+    SqliteBatchRepository::new(pool.clone()).init_database().await.unwrap();
+    SqliteTransferLimitRepository::new(pool.clone()).init_database().await.unwrap();
+    SqliteHoldRepository::new(pool.clone()).init_database().await.unwrap();
+    SqliteAdjustmentRepository::new(pool.clone()).init_database().await.unwrap();
+    SqliteTierRepository::new(pool.clone()).init_database().await.unwrap();
+    SqliteAuthRepository::new(pool.clone()).init_database().await.unwrap();
+
+    sqlx::query("DELETE FROM transfer_limits").execute(&pool).await.unwrap();
+
+    pool
+}
+
+// A clock that only moves when the test advances it
+pub struct FakeClock(Mutex<DateTime<Utc>>);
+
+impl FakeClock {
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self(Mutex::new(now))
+    }
+
+    pub fn advance(&self, by: Duration) {
+        *self.0.lock().unwrap() += by;
+    }
+}
+
+impl Clock for FakeClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.0.lock().unwrap()
+    }
+}
\ No newline at end of file
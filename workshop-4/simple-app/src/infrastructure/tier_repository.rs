@@ -0,0 +1,168 @@
+use async_trait::async_trait;
+use sqlx::{SqlitePool, Row};
+use sqlx::sqlite::SqliteRow;
+use chrono::{DateTime, Utc};
+use super::unit_of_work::SqliteSession;
+use crate::domain::{MembershipTier, TierChange, TierChangeDb, TierChangeReason, TierRepository};
+
+const TIER_CHANGE_COLUMNS: &str = "id, user_id, from_tier, to_tier, reason, points_earned, changed_by, note, created_at";
+
+#[derive(Clone)]
+pub struct SqliteTierRepository {
+    session: SqliteSession,
+}
+
+impl SqliteTierRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { session: SqliteSession::Pool(pool) }
+    }
+
+    pub fn with_session(session: SqliteSession) -> Self {
+        Self { session }
+    }
+
+    pub async fn init_database(&self) -> Result<(), String> {
+        let mut conn = self.session.acquire().await?;
+
+        // Create tier_history table
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS tier_history (
+              id INTEGER PRIMARY KEY AUTOINCREMENT,
+              user_id INTEGER NOT NULL,
+              from_tier TEXT NOT NULL CHECK (from_tier IN ('Bronze','Silver','Gold','Platinum')),
+              to_tier TEXT NOT NULL CHECK (to_tier IN ('Bronze','Silver','Gold','Platinum')),
+              reason TEXT NOT NULL CHECK (reason IN ('promotion','demotion','manual')),
+              points_earned INTEGER NOT NULL,
+              changed_by TEXT,
+              note TEXT,
+              created_at TEXT NOT NULL,
+              CHECK (from_tier <> to_tier),
+              FOREIGN KEY (user_id) REFERENCES users(id)
+            )
+            "#,
+        )
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to create tier_history table: {}", e))?;
+
+        // Create indexes
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_tier_history_user ON tier_history(user_id, id)")
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| format!("Failed to create index: {}", e))?;
+
+        Ok(())
+    }
+}
+
+fn tier_change_from_row(row: &SqliteRow) -> Result<TierChange, String> {
+    let change_db = TierChangeDb {
+        id: row.get::<i64, _>("id") as u32,
+        user_id: row.get::<i64, _>("user_id") as u32,
+        from_tier: row.get("from_tier"),
+        to_tier: row.get("to_tier"),
+        reason: row.get("reason"),
+        points_earned: row.get::<i64, _>("points_earned") as u64,
+        changed_by: row.get("changed_by"),
+        note: row.get("note"),
+        created_at: row.get("created_at"),
+    };
+    change_db.into_domain()
+}
+
+#[async_trait]
+impl TierRepository for SqliteTierRepository {
+    async fn get_user_tier(&self, user_id: u32) -> Result<Option<MembershipTier>, String> {
+        let mut conn = self.session.acquire().await?;
+
+        let tier: Option<String> = sqlx::query_scalar("SELECT membership_level FROM users WHERE id = ?")
+            .bind(user_id as i64)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| format!("Database error: {}", e))?;
+
+        tier.map(|tier| tier.parse()).transpose()
+    }
+
+    async fn change_tier(
+        &self,
+        user_id: u32,
+        from_tier: MembershipTier,
+        to_tier: MembershipTier,
+        reason: TierChangeReason,
+        points_earned: u64,
+        changed_by: Option<String>,
+        note: Option<String>,
+        now: DateTime<Utc>,
+    ) -> Result<TierChange, String> {
+        let mut conn = self.session.acquire().await?;
+
+        // Compare-and-set on the tier the caller read, so a concurrent change is not silently overwritten
+        let result = sqlx::query("UPDATE users SET membership_level = ?, updated_at = ? WHERE id = ? AND membership_level = ?")
+            .bind(to_tier.to_string())
+            .bind(now.to_rfc3339())
+            .bind(user_id as i64)
+            .bind(from_tier.to_string())
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| format!("Failed to update membership tier: {}", e))?;
+        if result.rows_affected() == 0 {
+            return Err(format!("Membership tier of user {} is no longer {}", user_id, from_tier));
+        }
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO tier_history (user_id, from_tier, to_tier, reason, points_earned, changed_by, note, created_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(user_id as i64)
+        .bind(from_tier.to_string())
+        .bind(to_tier.to_string())
+        .bind(reason.to_string())
+        .bind(points_earned as i64)
+        .bind(&changed_by)
+        .bind(&note)
+        .bind(now.to_rfc3339())
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to record tier change: {}", e))?;
+
+        Ok(TierChange {
+            id: result.last_insert_rowid() as u32,
+            user_id,
+            from_tier,
+            to_tier,
+            reason,
+            points_earned,
+            changed_by,
+            note,
+            created_at: now,
+        })
+    }
+
+    async fn get_tier_history(&self, user_id: u32) -> Result<Vec<TierChange>, String> {
+        let mut conn = self.session.acquire().await?;
+
+        let rows = sqlx::query(&format!("SELECT {} FROM tier_history WHERE user_id = ? ORDER BY id DESC", TIER_CHANGE_COLUMNS))
+            .bind(user_id as i64)
+            .fetch_all(&mut *conn)
+            .await
+            .map_err(|e| format!("Database error: {}", e))?;
+
+        rows.iter().map(tier_change_from_row).collect()
+    }
+
+    async fn get_last_tier_change(&self, user_id: u32) -> Result<Option<TierChange>, String> {
+        let mut conn = self.session.acquire().await?;
+
+        let row = sqlx::query(&format!("SELECT {} FROM tier_history WHERE user_id = ? ORDER BY id DESC LIMIT 1", TIER_CHANGE_COLUMNS))
+            .bind(user_id as i64)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| format!("Database error: {}", e))?;
+
+        row.as_ref().map(tier_change_from_row).transpose()
+    }
+}
\ No newline at end of file
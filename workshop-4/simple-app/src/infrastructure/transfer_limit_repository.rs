@@ -0,0 +1,144 @@
+use async_trait::async_trait;
+use sqlx::{SqlitePool, Row};
+use sqlx::sqlite::SqliteRow;
+use chrono::Utc;
+use super::unit_of_work::SqliteSession;
+use crate::domain::{TransferLimits, TransferLimitsDb, UpdateTransferLimitsRequest, TransferLimitRepository};
+
+const LIMIT_COLUMNS: &str = "membership_level, min_amount, max_amount, daily_cap, max_transfers_per_hour, updated_at";
+
+#[derive(Clone)]
+pub struct SqliteTransferLimitRepository {
+    session: SqliteSession,
+}
+
+impl SqliteTransferLimitRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { session: SqliteSession::Pool(pool) }
+    }
+
+    pub async fn init_database(&self) -> Result<(), String> {
+        let mut conn = self.session.acquire().await?;
+
+        // Create transfer_limits table (one row per membership level)
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS transfer_limits (
+              membership_level TEXT PRIMARY KEY COLLATE NOCASE,
+              min_amount INTEGER CHECK (min_amount IS NULL OR min_amount > 0),
+              max_amount INTEGER CHECK (max_amount IS NULL OR max_amount > 0),
+              daily_cap INTEGER CHECK (daily_cap IS NULL OR daily_cap > 0),
+              max_transfers_per_hour INTEGER CHECK (max_transfers_per_hour IS NULL OR max_transfers_per_hour > 0),
+              updated_at TEXT NOT NULL
+            )
+            "#,
+        )
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to create transfer_limits table: {}", e))?;
+
+        // Insert default limits if table is empty
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM transfer_limits")
+            .fetch_one(&mut *conn)
+            .await
+            .map_err(|e| format!("Failed to count transfer limits: {}", e))?;
+
+        if count == 0 {
+            self.seed_data().await?;
+        }
+
+        Ok(())
+    }
+
+    async fn seed_data(&self) -> Result<(), String> {
+        // (level, min, max, 24h cap, transfers per hour)
+        let limits = vec![
+            ("Bronze", 1, 1000, 2000, 10),
+            ("Silver", 1, 5000, 10000, 20),
+            ("Gold", 1, 20000, 50000, 50),
+            ("Platinum", 1, 100000, 250000, 100),
+        ];
+
+        for (membership_level, min_amount, max_amount, daily_cap, max_transfers_per_hour) in limits {
+            self.set_limits(membership_level, UpdateTransferLimitsRequest {
+                min_amount: Some(min_amount),
+                max_amount: Some(max_amount),
+                daily_cap: Some(daily_cap),
+                max_transfers_per_hour: Some(max_transfers_per_hour),
+            }).await?;
+        }
+
+        Ok(())
+    }
+}
+
+fn limits_from_row(row: &SqliteRow) -> Result<TransferLimits, String> {
+    let limits_db = TransferLimitsDb {
+        membership_level: row.get("membership_level"),
+        min_amount: row.get::<Option<i64>, _>("min_amount").map(|amount| amount as u32),
+        max_amount: row.get::<Option<i64>, _>("max_amount").map(|amount| amount as u32),
+        daily_cap: row.get::<Option<i64>, _>("daily_cap").map(|amount| amount as u32),
+        max_transfers_per_hour: row.get::<Option<i64>, _>("max_transfers_per_hour").map(|count| count as u32),
+        updated_at: row.get("updated_at"),
+    };
+    limits_db.into_domain()
+}
+
+#[async_trait]
+impl TransferLimitRepository for SqliteTransferLimitRepository {
+    async fn get_limits(&self, membership_level: &str) -> Result<Option<TransferLimits>, String> {
+        let mut conn = self.session.acquire().await?;
+
+        let row = sqlx::query(&format!("SELECT {} FROM transfer_limits WHERE membership_level = ?", LIMIT_COLUMNS))
+            .bind(membership_level)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| format!("Database error: {}", e))?;
+
+        row.as_ref().map(limits_from_row).transpose()
+    }
+
+    async fn list_limits(&self) -> Result<Vec<TransferLimits>, String> {
+        let mut conn = self.session.acquire().await?;
+
+        let rows = sqlx::query(&format!("SELECT {} FROM transfer_limits ORDER BY membership_level", LIMIT_COLUMNS))
+            .fetch_all(&mut *conn)
+            .await
+            .map_err(|e| format!("Database error: {}", e))?;
+
+        rows.iter().map(limits_from_row).collect()
+    }
+
+    async fn set_limits(&self, membership_level: &str, request: UpdateTransferLimitsRequest) -> Result<TransferLimits, String> {
+        request.validate()?;
+
+        let mut conn = self.session.acquire().await?;
+        let now = Utc::now();
+
+        sqlx::query(
+            r#"
+            INSERT INTO transfer_limits (membership_level, min_amount, max_amount, daily_cap, max_transfers_per_hour, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?)
+            ON CONFLICT(membership_level) DO UPDATE SET
+              min_amount = excluded.min_amount,
+              max_amount = excluded.max_amount,
+              daily_cap = excluded.daily_cap,
+              max_transfers_per_hour = excluded.max_transfers_per_hour,
+              updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(membership_level)
+        .bind(request.min_amount.map(|amount| amount as i64))
+        .bind(request.max_amount.map(|amount| amount as i64))
+        .bind(request.daily_cap.map(|amount| amount as i64))
+        .bind(request.max_transfers_per_hour.map(|count| count as i64))
+        .bind(now.to_rfc3339())
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to save transfer limits: {}", e))?;
+
+        drop(conn);
+        self.get_limits(membership_level).await?
+            .ok_or("Transfer limits not found".to_string())
+    }
+}
\ No newline at end of file
@@ -1,23 +1,35 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
 use async_trait::async_trait;
-use sqlx::{SqlitePool, Row};
-use chrono::Utc;
+use sqlx::{SqliteConnection, SqlitePool, Row};
+use sqlx::{Sqlite, query::Query};
+use sqlx::sqlite::{SqliteArguments, SqliteRow};
+use super::unit_of_work::SqliteSession;
+use super::migrations::add_column_if_missing;
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 use crate::domain::{
-    Transfer, TransferRepository, CreateTransferRequest, TransferDb,
-    PointLedger, PointLedgerRepository, EventType, PointLedgerDb,
+    Transfer, TransferStatus, TransferType, TransferRepository, CreateTransferRequest, CreateSplitTransferRequest, TransferDb, TransferReversal,
+    PointLedger, PointLedgerRepository, EventType, PointLedgerDb, LedgerFilter, LedgerTotals, GENESIS_HASH, PointLot, PointLotDb,
 };
 
 #[derive(Clone)]
 pub struct SqliteTransferRepository {
-    pool: SqlitePool,
+    session: SqliteSession,
 }
 
 impl SqliteTransferRepository {
     pub fn new(pool: SqlitePool) -> Self {
-        Self { pool }
+        Self { session: SqliteSession::Pool(pool) }
+    }
+
+    pub fn with_session(session: SqliteSession) -> Self {
+        Self { session }
     }
 
     pub async fn init_database(&self) -> Result<(), String> {
+        let mut conn = self.session.acquire().await?;
+
         // Create transfers table
         sqlx::query(
             r#"
@@ -33,109 +45,249 @@ impl SqliteTransferRepository {
               updated_at TEXT NOT NULL,
               completed_at TEXT,
               fail_reason TEXT,
+              execute_at TEXT,
+              transfer_type TEXT NOT NULL DEFAULT 'standard' CHECK (transfer_type IN ('standard','split')),
+              parent_transfer_id INTEGER,
+              accept_by TEXT,
               FOREIGN KEY (from_user_id) REFERENCES users(id),
-              FOREIGN KEY (to_user_id) REFERENCES users(id)
+              FOREIGN KEY (to_user_id) REFERENCES users(id),
+              FOREIGN KEY (parent_transfer_id) REFERENCES transfers(id)
             )
             "#,
         )
-        .execute(&self.pool)
+        .execute(&mut *conn)
         .await
         .map_err(|e| format!("Failed to create transfers table: {}", e))?;
 
+        add_column_if_missing(&mut conn, "transfers", "execute_at", "TEXT").await?;
+        add_column_if_missing(&mut conn, "transfers", "transfer_type", "TEXT NOT NULL DEFAULT 'standard'").await?;
+        add_column_if_missing(&mut conn, "transfers", "parent_transfer_id", "INTEGER REFERENCES transfers(id)").await?;
+        add_column_if_missing(&mut conn, "transfers", "accept_by", "TEXT").await?;
+
         // Create indexes
         sqlx::query("CREATE INDEX IF NOT EXISTS idx_transfers_from ON transfers(from_user_id)")
-            .execute(&self.pool)
+            .execute(&mut *conn)
             .await
             .map_err(|e| format!("Failed to create index: {}", e))?;
 
         sqlx::query("CREATE INDEX IF NOT EXISTS idx_transfers_to ON transfers(to_user_id)")
-            .execute(&self.pool)
+            .execute(&mut *conn)
             .await
             .map_err(|e| format!("Failed to create index: {}", e))?;
 
         sqlx::query("CREATE INDEX IF NOT EXISTS idx_transfers_created ON transfers(created_at)")
-            .execute(&self.pool)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| format!("Failed to create index: {}", e))?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_transfers_status ON transfers(status)")
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| format!("Failed to create index: {}", e))?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_transfers_parent ON transfers(parent_transfer_id)")
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| format!("Failed to create index: {}", e))?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_transfers_accept_by ON transfers(accept_by) WHERE accept_by IS NOT NULL")
+            .execute(&mut *conn)
             .await
             .map_err(|e| format!("Failed to create index: {}", e))?;
 
+        // Create transfer_reversals table (who reversed a transfer and why)
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS transfer_reversals (
+              id INTEGER PRIMARY KEY AUTOINCREMENT,
+              transfer_id INTEGER NOT NULL UNIQUE,
+              reversed_by TEXT NOT NULL,
+              reason TEXT NOT NULL,
+              forced INTEGER NOT NULL DEFAULT 0,
+              created_at TEXT NOT NULL,
+              FOREIGN KEY (transfer_id) REFERENCES transfers(id)
+            )
+            "#,
+        )
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to create transfer_reversals table: {}", e))?;
+
         Ok(())
     }
 }
 
+const TRANSFER_COLUMNS: &str = "id, from_user_id, to_user_id, amount, status, note, idempotency_key, created_at, updated_at, completed_at, fail_reason, execute_at, transfer_type, parent_transfer_id, accept_by";
+
+fn transfer_from_row(row: &SqliteRow) -> Result<Transfer, String> {
+    let transfer_db = TransferDb {
+        id: row.get::<i64, _>("id") as u32,
+        from_user_id: row.get::<i64, _>("from_user_id") as u32,
+        to_user_id: row.get::<i64, _>("to_user_id") as u32,
+        amount: row.get::<i64, _>("amount") as u32,
+        status: row.get("status"),
+        note: row.get("note"),
+        idempotency_key: row.get("idempotency_key"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+        completed_at: row.get("completed_at"),
+        fail_reason: row.get("fail_reason"),
+        execute_at: row.get("execute_at"),
+        transfer_type: row.get("transfer_type"),
+        parent_transfer_id: row.get::<Option<i64>, _>("parent_transfer_id").map(|id| id as u32),
+        accept_by: row.get("accept_by"),
+    };
+    transfer_db.into_domain()
+}
+
+async fn insert_transfer(
+    conn: &mut SqliteConnection,
+    transfer_request: CreateTransferRequest,
+    transfer_type: TransferType,
+    parent_transfer_id: Option<u32>,
+    accept_by: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+) -> Result<Transfer, String> {
+    let idem_key = Uuid::new_v4().to_string();
+
+    let result = sqlx::query(
+        r#"
+        INSERT INTO transfers (from_user_id, to_user_id, amount, status, note, idempotency_key, created_at, updated_at, execute_at, transfer_type, parent_transfer_id, accept_by)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(transfer_request.from_user_id as i64)
+    .bind(transfer_request.to_user_id as i64)
+    .bind(transfer_request.amount as i64)
+    .bind("pending")
+    .bind(&transfer_request.note)
+    .bind(&idem_key)
+    .bind(now.to_rfc3339())
+    .bind(now.to_rfc3339())
+    .bind(transfer_request.execute_at.map(|execute_at| execute_at.to_rfc3339()))
+    .bind(transfer_type.to_string())
+    .bind(parent_transfer_id.map(|id| id as i64))
+    .bind(accept_by.map(|accept_by| accept_by.to_rfc3339()))
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| format!("Failed to create transfer: {}", e))?;
+
+    let transfer_id = result.last_insert_rowid() as u32;
+
+    Ok(Transfer {
+        idem_key,
+        transfer_id: Some(transfer_id),
+        from_user_id: transfer_request.from_user_id,
+        to_user_id: transfer_request.to_user_id,
+        amount: transfer_request.amount,
+        status: TransferStatus::Pending,
+        note: transfer_request.note,
+        created_at: now,
+        updated_at: now,
+        completed_at: None,
+        fail_reason: None,
+        execute_at: transfer_request.execute_at,
+        transfer_type,
+        parent_transfer_id,
+        accept_by,
+    })
+}
+
 #[async_trait]
 impl TransferRepository for SqliteTransferRepository {
-    async fn create_transfer(&self, transfer_request: CreateTransferRequest) -> Result<Transfer, String> {
+    async fn create_transfer(&self, transfer_request: CreateTransferRequest, now: DateTime<Utc>) -> Result<Transfer, String> {
         transfer_request.validate()?;
         
-        let now = Utc::now();
-        let idem_key = Uuid::new_v4().to_string();
-        
-        let result = sqlx::query(
-            r#"
-            INSERT INTO transfers (from_user_id, to_user_id, amount, status, note, idempotency_key, created_at, updated_at)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
-            "#,
-        )
-        .bind(transfer_request.from_user_id as i64)
-        .bind(transfer_request.to_user_id as i64)
-        .bind(transfer_request.amount as i64)
-        .bind("pending")
-        .bind(&transfer_request.note)
-        .bind(&idem_key)
-        .bind(now.to_rfc3339())
-        .bind(now.to_rfc3339())
-        .execute(&self.pool)
-        .await
-        .map_err(|e| format!("Failed to create transfer: {}", e))?;
+        let mut conn = self.session.acquire().await?;
+        insert_transfer(&mut conn, transfer_request, TransferType::Standard, None, None, now).await
+    }
 
-        let transfer_id = result.last_insert_rowid() as u32;
+    async fn create_transfer_awaiting_acceptance(&self, transfer_request: CreateTransferRequest, accept_by: DateTime<Utc>, now: DateTime<Utc>) -> Result<Transfer, String> {
+        transfer_request.validate()?;
 
-        Ok(Transfer {
-            idem_key,
-            transfer_id: Some(transfer_id),
-            from_user_id: transfer_request.from_user_id,
-            to_user_id: transfer_request.to_user_id,
-            amount: transfer_request.amount,
-            status: crate::domain::TransferStatus::Pending,
-            note: transfer_request.note,
-            created_at: now,
-            updated_at: now,
-            completed_at: None,
-            fail_reason: None,
-        })
+        let mut conn = self.session.acquire().await?;
+        insert_transfer(&mut conn, transfer_request, TransferType::Standard, None, Some(accept_by), now).await
+    }
+
+    async fn create_split_transfer(&self, split_request: CreateSplitTransferRequest, now: DateTime<Utc>) -> Result<(Transfer, Vec<Transfer>), String> {
+        split_request.validate()?;
+        let total_amount = split_request.total_amount()
+            .ok_or("Total amount is too large".to_string())?;
+
+        let mut conn = self.session.acquire().await?;
+
+        // The service looked the recipients up before it took the account locks; check again in this transaction
+        for recipient in &split_request.recipients {
+            let exists: Option<i64> = sqlx::query_scalar("SELECT id FROM users WHERE id = ?")
+                .bind(recipient.to_user_id as i64)
+                .fetch_optional(&mut *conn)
+                .await
+                .map_err(|e| format!("Database error: {}", e))?;
+            if exists.is_none() {
+                return Err(format!("To user {} not found", recipient.to_user_id));
+            }
+        }
+
+        let parent_request = CreateTransferRequest {
+            from_user_id: split_request.from_user_id,
+            to_user_id: split_request.from_user_id,
+            amount: total_amount,
+            note: split_request.note.clone(),
+            execute_at: None,
+        };
+        let parent = insert_transfer(&mut conn, parent_request, TransferType::Split, None, None, now).await?;
+
+        let mut children = Vec::with_capacity(split_request.recipients.len());
+        for leg in split_request.legs() {
+            children.push(insert_transfer(&mut conn, leg, TransferType::Standard, parent.transfer_id, None, now).await?);
+        }
+
+        Ok((parent, children))
     }
 
     async fn get_transfer_by_idem_key(&self, idem_key: &str) -> Result<Option<Transfer>, String> {
-        let row = sqlx::query(
-            "SELECT id, from_user_id, to_user_id, amount, status, note, idempotency_key, created_at, updated_at, completed_at, fail_reason FROM transfers WHERE idempotency_key = ?"
-        )
-        .bind(idem_key)
-        .fetch_optional(&self.pool)
-        .await
-        .map_err(|e| format!("Database error: {}", e))?;
+        let mut conn = self.session.acquire().await?;
+
+        let row = sqlx::query(&format!("SELECT {} FROM transfers WHERE idempotency_key = ?", TRANSFER_COLUMNS))
+            .bind(idem_key)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| format!("Database error: {}", e))?;
 
         match row {
-            Some(row) => {
-                let transfer_db = TransferDb {
-                    id: row.get::<i64, _>("id") as u32,
-                    from_user_id: row.get::<i64, _>("from_user_id") as u32,
-                    to_user_id: row.get::<i64, _>("to_user_id") as u32,
-                    amount: row.get::<i64, _>("amount") as u32,
-                    status: row.get("status"),
-                    note: row.get("note"),
-                    idempotency_key: row.get("idempotency_key"),
-                    created_at: row.get("created_at"),
-                    updated_at: row.get("updated_at"),
-                    completed_at: row.get("completed_at"),
-                    fail_reason: row.get("fail_reason"),
-                };
-                Ok(Some(transfer_db.to_domain()?))
-            }
+            Some(row) => Ok(Some(transfer_from_row(&row)?)),
             None => Ok(None),
         }
     }
 
+    async fn get_transfer_by_id(&self, id: u32) -> Result<Option<Transfer>, String> {
+        let mut conn = self.session.acquire().await?;
+
+        let row = sqlx::query(&format!("SELECT {} FROM transfers WHERE id = ?", TRANSFER_COLUMNS))
+            .bind(id as i64)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| format!("Database error: {}", e))?;
+
+        row.as_ref().map(transfer_from_row).transpose()
+    }
+
+    async fn get_transfers_after(&self, after_id: u32, limit: u32) -> Result<Vec<Transfer>, String> {
+        let mut conn = self.session.acquire().await?;
+
+        let rows = sqlx::query(&format!("SELECT {} FROM transfers WHERE id > ? ORDER BY id LIMIT ?", TRANSFER_COLUMNS))
+            .bind(after_id as i64)
+            .bind(limit as i64)
+            .fetch_all(&mut *conn)
+            .await
+            .map_err(|e| format!("Database error: {}", e))?;
+
+        rows.iter().map(transfer_from_row).collect()
+    }
+
     async fn get_transfers_by_user_id(&self, user_id: u32, page: u32, page_size: u32) -> Result<(Vec<Transfer>, u32), String> {
+        let mut conn = self.session.acquire().await?;
+
         let limit = page_size as i64;
         let offset = ((page - 1) * page_size) as i64;
 
@@ -145,218 +297,1392 @@ impl TransferRepository for SqliteTransferRepository {
         )
         .bind(user_id as i64)
         .bind(user_id as i64)
-        .fetch_one(&self.pool)
+        .fetch_one(&mut *conn)
         .await
         .map_err(|e| format!("Database error: {}", e))?;
 
         // Get transfers
         let rows = sqlx::query(
-            "SELECT id, from_user_id, to_user_id, amount, status, note, idempotency_key, created_at, updated_at, completed_at, fail_reason FROM transfers WHERE from_user_id = ? OR to_user_id = ? ORDER BY created_at DESC LIMIT ? OFFSET ?"
+            &format!("SELECT {} FROM transfers WHERE from_user_id = ? OR to_user_id = ? ORDER BY created_at DESC LIMIT ? OFFSET ?", TRANSFER_COLUMNS)
         )
         .bind(user_id as i64)
         .bind(user_id as i64)
         .bind(limit)
         .bind(offset)
-        .fetch_all(&self.pool)
+        .fetch_all(&mut *conn)
         .await
         .map_err(|e| format!("Database error: {}", e))?;
 
-        let mut transfers = Vec::new();
-        for row in rows {
-            let transfer_db = TransferDb {
-                id: row.get::<i64, _>("id") as u32,
-                from_user_id: row.get::<i64, _>("from_user_id") as u32,
-                to_user_id: row.get::<i64, _>("to_user_id") as u32,
-                amount: row.get::<i64, _>("amount") as u32,
-                status: row.get("status"),
-                note: row.get("note"),
-                idempotency_key: row.get("idempotency_key"),
-                created_at: row.get("created_at"),
-                updated_at: row.get("updated_at"),
-                completed_at: row.get("completed_at"),
-                fail_reason: row.get("fail_reason"),
-            };
-            transfers.push(transfer_db.to_domain()?);
-        }
+        let transfers = rows.iter()
+            .map(transfer_from_row)
+            .collect::<Result<Vec<_>, _>>()?;
 
         Ok((transfers, total as u32))
     }
 
-    async fn update_transfer_status(&self, idem_key: &str, status: &str, completed_at: Option<String>, fail_reason: Option<String>) -> Result<(), String> {
-        let now = Utc::now().to_rfc3339();
-        
-        sqlx::query(
-            "UPDATE transfers SET status = ?, updated_at = ?, completed_at = ?, fail_reason = ? WHERE idempotency_key = ?"
+    async fn get_pending_transfers(&self, due_at: DateTime<Utc>, limit: u32) -> Result<Vec<Transfer>, String> {
+        let mut conn = self.session.acquire().await?;
+
+        // Oldest first so transfers are executed in the order they were accepted
+        let rows = sqlx::query(
+            &format!("SELECT {} FROM transfers WHERE status = 'pending' AND accept_by IS NULL AND (execute_at IS NULL OR execute_at <= ?) ORDER BY id LIMIT ?", TRANSFER_COLUMNS)
+        )
+        .bind(due_at.to_rfc3339())
+        .bind(limit as i64)
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| format!("Database error: {}", e))?;
+
+        let transfers = rows.iter()
+            .map(transfer_from_row)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(transfers)
+    }
+
+    async fn get_child_transfers(&self, parent_transfer_id: u32) -> Result<Vec<Transfer>, String> {
+        let mut conn = self.session.acquire().await?;
+
+        let rows = sqlx::query(&format!("SELECT {} FROM transfers WHERE parent_transfer_id = ? ORDER BY id", TRANSFER_COLUMNS))
+            .bind(parent_transfer_id as i64)
+            .fetch_all(&mut *conn)
+            .await
+            .map_err(|e| format!("Database error: {}", e))?;
+
+        rows.iter().map(transfer_from_row).collect()
+    }
+
+    async fn update_transfer_status(&self, idem_key: &str, from: TransferStatus, to: TransferStatus, completed_at: Option<String>, fail_reason: Option<String>, now: DateTime<Utc>) -> Result<(), String> {
+        from.validate_transition(to)?;
+
+        let mut conn = self.session.acquire().await?;
+
+        let result = sqlx::query(
+            "UPDATE transfers SET status = ?, updated_at = ?, completed_at = COALESCE(?, completed_at), fail_reason = COALESCE(?, fail_reason) WHERE idempotency_key = ? AND status = ?"
         )
-        .bind(status)
-        .bind(now)
+        .bind(to.to_string())
+        .bind(now.to_rfc3339())
         .bind(completed_at)
         .bind(fail_reason)
         .bind(idem_key)
-        .execute(&self.pool)
+        .bind(from.to_string())
+        .execute(&mut *conn)
         .await
         .map_err(|e| format!("Failed to update transfer status: {}", e))?;
 
+        // Nothing matched: either the transfer is gone or someone else moved it out of `from` first
+        if result.rows_affected() == 0 {
+            return Err(format!("Illegal transfer status transition: transfer is no longer {}", from));
+        }
+
         Ok(())
     }
+
+    async fn create_transfer_reversal(&self, transfer_id: u32, reversed_by: &str, reason: &str, forced: bool, now: DateTime<Utc>) -> Result<TransferReversal, String> {
+        let mut conn = self.session.acquire().await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO transfer_reversals (transfer_id, reversed_by, reason, forced, created_at)
+            VALUES (?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(transfer_id as i64)
+        .bind(reversed_by)
+        .bind(reason)
+        .bind(forced)
+        .bind(now.to_rfc3339())
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to create transfer reversal: {}", e))?;
+
+        Ok(TransferReversal {
+            transfer_id,
+            reversed_by: reversed_by.to_string(),
+            reason: reason.to_string(),
+            forced,
+            created_at: now,
+        })
+    }
+
+    async fn get_transfer_reversal(&self, transfer_id: u32) -> Result<Option<TransferReversal>, String> {
+        let mut conn = self.session.acquire().await?;
+
+        let row = sqlx::query(
+            "SELECT transfer_id, reversed_by, reason, forced, created_at FROM transfer_reversals WHERE transfer_id = ?"
+        )
+        .bind(transfer_id as i64)
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(|e| format!("Database error: {}", e))?;
+
+        match row {
+            Some(row) => {
+                let created_at = DateTime::parse_from_rfc3339(row.get("created_at"))
+                    .map_err(|e| format!("Invalid created_at date: {}", e))?
+                    .with_timezone(&Utc);
+
+                Ok(Some(TransferReversal {
+                    transfer_id: row.get::<i64, _>("transfer_id") as u32,
+                    reversed_by: row.get("reversed_by"),
+                    reason: row.get("reason"),
+                    forced: row.get("forced"),
+                    created_at,
+                }))
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn has_received_from(&self, from_user_id: u32, to_user_id: u32) -> Result<bool, String> {
+        let mut conn = self.session.acquire().await?;
+
+        let count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM transfers WHERE from_user_id = ? AND to_user_id = ? AND status IN ('completed', 'reversed')"
+        )
+        .bind(from_user_id as i64)
+        .bind(to_user_id as i64)
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(|e| format!("Database error: {}", e))?;
+
+        Ok(count > 0)
+    }
+
+    async fn get_expired_acceptances(&self, now: DateTime<Utc>, limit: u32) -> Result<Vec<Transfer>, String> {
+        let mut conn = self.session.acquire().await?;
+
+        let rows = sqlx::query(
+            &format!("SELECT {} FROM transfers WHERE status = 'pending' AND accept_by IS NOT NULL AND accept_by <= ? ORDER BY accept_by, id LIMIT ?", TRANSFER_COLUMNS)
+        )
+        .bind(now.to_rfc3339())
+        .bind(limit as i64)
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| format!("Database error: {}", e))?;
+
+        rows.iter().map(transfer_from_row).collect()
+    }
+
+    async fn get_outgoing_totals(&self, from_user_id: u32, since: DateTime<Utc>) -> Result<(u64, u32), String> {
+        let mut conn = self.session.acquire().await?;
+
+        let row = sqlx::query(
+            r#"
+            SELECT COALESCE(SUM(amount), 0) AS total_amount, COUNT(*) AS transfer_count
+            FROM transfers
+            WHERE from_user_id = ? AND created_at >= ? AND parent_transfer_id IS NULL AND status NOT IN ('failed', 'cancelled')
+            "#,
+        )
+        .bind(from_user_id as i64)
+        .bind(since.to_rfc3339())
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(|e| format!("Database error: {}", e))?;
+
+        Ok((row.get::<i64, _>("total_amount") as u64, row.get::<i64, _>("transfer_count") as u32))
+    }
 }
 
+// (entry id, earned_at, amount, remaining)
+type ReplayedLot = (u32, DateTime<Utc>, u32, u32);
+
+// (earned_at, amount) of points that left one account and have not yet arrived in another, by the transfer moving
+// them (see carrier_transfer_id), so a credit can only pick up the points of its own transfer
+type LotsInFlight = Arc<Mutex<HashMap<u32, VecDeque<(DateTime<Utc>, u32)>>>>;
+
 #[derive(Clone)]
 pub struct SqlitePointLedgerRepository {
-    pool: SqlitePool,
+    session: SqliteSession,
+    lots_in_flight: LotsInFlight,
 }
 
 impl SqlitePointLedgerRepository {
     pub fn new(pool: SqlitePool) -> Self {
-        Self { pool }
+        Self { session: SqliteSession::Pool(pool), lots_in_flight: LotsInFlight::default() }
     }
 
-    pub async fn init_database(&self) -> Result<(), String> {
+    pub fn with_session(session: SqliteSession) -> Self {
+        Self { session, lots_in_flight: LotsInFlight::default() }
+    }
+
+    async fn post_ledger_entry(&self, mut entry: PointLedger) -> Result<PointLedger, String> {
+        let mut conn = self.session.acquire().await?;
+
+        insert_sealed_entry(&mut conn, &mut entry).await?;
+
+        // Credits open lots; debits drain the oldest lots first. Moving points keeps their age: a transfer_out leaves
+        // the lots it drained in flight under its transfer, and the transfer_in of that transfer, posted after it in
+        // the same unit of work, opens lots with their earned_at. Only points that were not carried over (earn,
+        // adjust, opening balance) start a new lifetime
+        let carrier = match (entry.event_type, entry.transfer_id) {
+            (EventType::TransferIn | EventType::TransferOut, Some(transfer_id)) => Some(carrier_transfer_id(&mut conn, transfer_id).await?),
+            _ => None,
+        };
+        if entry.change > 0 {
+            let amount = entry.change as u32;
+            let lots = match carrier {
+                Some(carrier) if entry.event_type == EventType::TransferIn => {
+                    let mut lots_in_flight = self.lots_in_flight.lock().unwrap();
+                    let in_flight = lots_in_flight.entry(carrier).or_default();
+                    let lots = carry_lots(in_flight, amount, entry.created_at);
+                    if in_flight.is_empty() {
+                        lots_in_flight.remove(&carrier);
+                    }
+                    lots
+                }
+                _ => vec![(entry.created_at, amount)],
+            };
+            for (earned_at, amount) in lots {
+                sqlx::query("INSERT INTO point_lots (user_id, ledger_entry_id, amount, remaining, earned_at) VALUES (?, ?, ?, ?, ?)")
+                    .bind(entry.user_id as i64)
+                    .bind(entry.id as i64)
+                    .bind(amount as i64)
+                    .bind(amount as i64)
+                    .bind(earned_at.to_rfc3339())
+                    .execute(&mut *conn)
+                    .await
+                    .map_err(|e| format!("Failed to create point lot: {}", e))?;
+            }
+        } else {
+            let drained = consume_lots(&mut conn, entry.user_id, entry.change.unsigned_abs()).await?;
+            if let Some(carrier) = carrier
+                && entry.event_type == EventType::TransferOut
+            {
+                self.lots_in_flight.lock().unwrap().entry(carrier).or_default().extend(drained);
+            }
+        }
+
+        // users.points mirrors the latest balance_after and is written in the same transaction, so it cannot drift
+        sqlx::query("UPDATE users SET points = ? WHERE id = ?")
+            .bind(entry.balance_after as i64)
+            .bind(entry.user_id as i64)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| format!("Failed to update user points: {}", e))?;
+
+        Ok(entry)
+    }
+
+    // Returns how many entries have no hash, for the caller to report
+    pub async fn init_database(&self) -> Result<u64, String> {
+        let mut conn = self.session.acquire().await?;
+
         // Create point_ledger table
-        sqlx::query(
-            r#"
-            CREATE TABLE IF NOT EXISTS point_ledger (
-              id INTEGER PRIMARY KEY AUTOINCREMENT,
-              user_id INTEGER NOT NULL,
-              change INTEGER NOT NULL,
-              balance_after INTEGER NOT NULL,
-              event_type TEXT NOT NULL CHECK (event_type IN ('transfer_out','transfer_in','adjust','earn','redeem')),
-              transfer_id INTEGER,
-              reference TEXT,
-              metadata TEXT,
-              created_at TEXT NOT NULL,
-              FOREIGN KEY (user_id) REFERENCES users(id),
-              FOREIGN KEY (transfer_id) REFERENCES transfers(id)
-            )
-            "#,
-        )
-        .execute(&self.pool)
-        .await
-        .map_err(|e| format!("Failed to create point_ledger table: {}", e))?;
+        sqlx::query(&point_ledger_table_sql("point_ledger"))
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| format!("Failed to create point_ledger table: {}", e))?;
+
+        add_column_if_missing(&mut conn, "point_ledger", "idempotency_key", "TEXT").await?;
+        add_column_if_missing(&mut conn, "point_ledger", "sequence", "INTEGER").await?;
+        // A ledger from before the hash chain; its entries are sealed once below
+        let chain_is_new = add_column_if_missing(&mut conn, "point_ledger", "hash", "TEXT").await?;
+        add_column_if_missing(&mut conn, "point_ledger", "prev_hash", "TEXT").await?;
+        migrate_event_types(&mut conn).await?;
 
         // Create indexes
         sqlx::query("CREATE INDEX IF NOT EXISTS idx_ledger_user ON point_ledger(user_id)")
-            .execute(&self.pool)
+            .execute(&mut *conn)
             .await
             .map_err(|e| format!("Failed to create index: {}", e))?;
 
         sqlx::query("CREATE INDEX IF NOT EXISTS idx_ledger_transfer ON point_ledger(transfer_id)")
-            .execute(&self.pool)
+            .execute(&mut *conn)
             .await
             .map_err(|e| format!("Failed to create index: {}", e))?;
 
         sqlx::query("CREATE INDEX IF NOT EXISTS idx_ledger_created ON point_ledger(created_at)")
-            .execute(&self.pool)
+            .execute(&mut *conn)
             .await
             .map_err(|e| format!("Failed to create index: {}", e))?;
 
-        Ok(())
-    }
-}
+        sqlx::query("CREATE UNIQUE INDEX IF NOT EXISTS idx_ledger_idempotency ON point_ledger(user_id, idempotency_key) WHERE idempotency_key IS NOT NULL")
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| format!("Failed to create index: {}", e))?;
 
-#[async_trait]
-impl PointLedgerRepository for SqlitePointLedgerRepository {
-    async fn create_ledger_entry(
-        &self,
-        user_id: u32,
-        change: i32,
-        balance_after: u32,
-        event_type: EventType,
-        transfer_id: Option<u32>,
-        reference: Option<String>,
-        metadata: Option<String>,
-    ) -> Result<PointLedger, String> {
-        let now = Utc::now();
-        
-        let result = sqlx::query(
+        // Lots used to be one per credit, before a transfer's credit could carry points of several ages. They are
+        // derived from the ledger, so a table of the old shape is dropped and replayed by migrate_point_lots
+        let lots_sql: Option<String> = sqlx::query_scalar("SELECT sql FROM sqlite_master WHERE type = 'table' AND name = 'point_lots'")
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| format!("Failed to inspect point_lots table: {}", e))?;
+        if lots_sql.is_some_and(|sql| sql.contains("ledger_entry_id INTEGER NOT NULL UNIQUE")) {
+            sqlx::query("DROP TABLE point_lots")
+                .execute(&mut *conn)
+                .await
+                .map_err(|e| format!("Failed to drop old point_lots: {}", e))?;
+        }
+
+        // Create point_lots table
+        sqlx::query(
             r#"
-            INSERT INTO point_ledger (user_id, change, balance_after, event_type, transfer_id, reference, metadata, created_at)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            CREATE TABLE IF NOT EXISTS point_lots (
+              id INTEGER PRIMARY KEY AUTOINCREMENT,
+              user_id INTEGER NOT NULL,
+              ledger_entry_id INTEGER NOT NULL,
+              amount INTEGER NOT NULL CHECK (amount > 0),
+              remaining INTEGER NOT NULL CHECK (remaining >= 0 AND remaining <= amount),
+              earned_at TEXT NOT NULL,
+              FOREIGN KEY (user_id) REFERENCES users(id),
+              FOREIGN KEY (ledger_entry_id) REFERENCES point_ledger(id)
+            )
             "#,
         )
-        .bind(user_id as i64)
-        .bind(change as i64)
-        .bind(balance_after as i64)
-        .bind(event_type.to_string())
-        .bind(transfer_id.map(|id| id as i64))
-        .bind(&reference)
-        .bind(&metadata)
-        .bind(now.to_rfc3339())
-        .execute(&self.pool)
+        .execute(&mut *conn)
         .await
-        .map_err(|e| format!("Failed to create ledger entry: {}", e))?;
+        .map_err(|e| format!("Failed to create point_lots table: {}", e))?;
 
-        let id = result.last_insert_rowid() as u32;
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_lots_open ON point_lots(user_id, earned_at) WHERE remaining > 0")
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| format!("Failed to create index: {}", e))?;
 
-        Ok(PointLedger {
-            id,
-            user_id,
-            change,
-            balance_after,
-            event_type,
-            transfer_id,
-            reference,
-            metadata,
-            created_at: now,
-        })
-    }
+        drop(conn);
+        self.migrate_opening_balances(chain_is_new).await?;
+        self.migrate_point_lots().await?;
+
+        // Only once every row has a sequence; the unique index is what stops two writers taking the same slot
+        let mut conn = self.session.acquire().await?;
+        sqlx::query("CREATE UNIQUE INDEX IF NOT EXISTS idx_ledger_sequence ON point_ledger(user_id, sequence)")
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| format!("Failed to create index: {}", e))?;
 
-    async fn get_ledger_by_user_id(&self, user_id: u32, limit: Option<i64>, offset: Option<i64>) -> Result<Vec<PointLedger>, String> {
-        let limit = limit.unwrap_or(100);
-        let offset = offset.unwrap_or(0);
+        // Earlier versions let unsealed entries and idempotency keys be updated
+        for trigger in ["point_ledger_no_update", "point_ledger_idempotency_key_once"] {
+            sqlx::query(&format!("DROP TRIGGER IF EXISTS {}", trigger))
+                .execute(&mut *conn)
+                .await
+                .map_err(|e| format!("Failed to drop trigger: {}", e))?;
+        }
 
-        let rows = sqlx::query(
-            "SELECT id, user_id, change, balance_after, event_type, transfer_id, reference, metadata, created_at FROM point_ledger WHERE user_id = ? ORDER BY created_at DESC LIMIT ? OFFSET ?"
+        // The ledger is append-only: every entry goes in numbered and hashed, and nothing in it changes afterwards
+        sqlx::query(
+            r#"
+            CREATE TRIGGER IF NOT EXISTS point_ledger_sealed_insert
+            BEFORE INSERT ON point_ledger
+            WHEN NEW.sequence IS NULL OR NEW.hash IS NULL OR NEW.prev_hash IS NULL
+            BEGIN
+              SELECT RAISE(ABORT, 'point_ledger entries must be numbered and hashed');
+            END
+            "#,
         )
-        .bind(user_id as i64)
-        .bind(limit)
-        .bind(offset)
-        .fetch_all(&self.pool)
+        .execute(&mut *conn)
         .await
-        .map_err(|e| format!("Database error: {}", e))?;
-
-        let mut ledger_entries = Vec::new();
-        for row in rows {
-            let ledger_db = PointLedgerDb {
-                id: row.get::<i64, _>("id") as u32,
-                user_id: row.get::<i64, _>("user_id") as u32,
-                change: row.get::<i64, _>("change") as i32,
-                balance_after: row.get::<i64, _>("balance_after") as u32,
-                event_type: row.get("event_type"),
-                transfer_id: row.get::<Option<i64>, _>("transfer_id").map(|id| id as u32),
-                reference: row.get("reference"),
-                metadata: row.get("metadata"),
-                created_at: row.get("created_at"),
-            };
-            ledger_entries.push(ledger_db.to_domain()?);
-        }
+        .map_err(|e| format!("Failed to create trigger: {}", e))?;
 
-        Ok(ledger_entries)
-    }
+        sqlx::query(
+            r#"
+            CREATE TRIGGER IF NOT EXISTS point_ledger_immutable
+            BEFORE UPDATE ON point_ledger
+            BEGIN
+              SELECT RAISE(ABORT, 'point_ledger entries are immutable');
+            END
+            "#,
+        )
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to create trigger: {}", e))?;
 
-    async fn get_current_balance(&self, user_id: u32) -> Result<u32, String> {
-        // Try to get the latest balance from point_ledger
-        let balance: Option<i64> = sqlx::query_scalar(
-            "SELECT balance_after FROM point_ledger WHERE user_id = ? ORDER BY created_at DESC LIMIT 1"
+        sqlx::query(
+            r#"
+            CREATE TRIGGER IF NOT EXISTS point_ledger_no_delete
+            BEFORE DELETE ON point_ledger
+            BEGIN
+              SELECT RAISE(ABORT, 'point_ledger entries cannot be deleted');
+            END
+            "#,
         )
-        .bind(user_id as i64)
-        .fetch_optional(&self.pool)
+        .execute(&mut *conn)
         .await
-        .map_err(|e| format!("Database error: {}", e))?;
+        .map_err(|e| format!("Failed to create trigger: {}", e))?;
 
-        // If no ledger entry exists, get from users table
-        if let Some(balance) = balance {
-            Ok(balance as u32)
-        } else {
-            let user_points: Option<i64> = sqlx::query_scalar(
-                "SELECT points FROM users WHERE id = ?"
-            )
-            .bind(user_id as i64)
-            .fetch_optional(&self.pool)
+        // Entries without a hash did not come through the ledger; they are left as found for verify-ledger to report
+        let unhashed: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM point_ledger WHERE hash IS NULL")
+            .fetch_one(&mut *conn)
             .await
             .map_err(|e| format!("Database error: {}", e))?;
 
-            Ok(user_points.unwrap_or(0) as u32)
-        }
+        Ok(unhashed as u64)
+    }
+
+    // Moves balances that only live in users.points (seed data, or databases from before the ledger was the
+    // source of truth) into an opening adjust entry, then brings users.points in line with the ledger.
+    // Safe to run on every start: once a user has an entry, there is nothing left to move.
+    async fn migrate_opening_balances(&self, chain_is_new: bool) -> Result<(), String> {
+        let mut conn = self.session.acquire().await?;
+        let mut tx = sqlx::Connection::begin(&mut *conn)
+            .await
+            .map_err(|e| format!("Failed to begin transaction: {}", e))?;
+
+        if chain_is_new {
+            seal_entries_from_before_the_chain(&mut tx).await?;
+        }
+
+        let users = sqlx::query(
+            "SELECT id, points, created_at FROM users u WHERE points > 0 AND NOT EXISTS (SELECT 1 FROM point_ledger l WHERE l.user_id = u.id)"
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| format!("Database error: {}", e))?;
+        for user in &users {
+            let points = user.get::<i64, _>("points");
+            let mut entry = unposted_entry(
+                user.get::<i64, _>("id") as u32,
+                points as i32,
+                points as u32,
+                EventType::Adjust,
+                None,
+                Some("Opening balance".to_string()),
+                Some(r#"{"opening_balance":true}"#.to_string()),
+            );
+            entry.created_at = DateTime::parse_from_rfc3339(user.get("created_at"))
+                .map_err(|e| format!("Invalid created_at date: {}", e))?
+                .with_timezone(&Utc);
+            insert_sealed_entry(&mut tx, &mut entry).await
+                .map_err(|e| format!("Failed to migrate opening balances: {}", e))?;
+        }
+
+        sqlx::query(
+            r#"
+            UPDATE users SET points = COALESCE(
+              (SELECT l.balance_after FROM point_ledger l WHERE l.user_id = users.id ORDER BY l.sequence DESC LIMIT 1),
+              0
+            )
+            "#,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to sync user points: {}", e))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit transaction: {}", e))
+    }
+
+    // Builds lots for users whose ledger predates them by replaying it in the order it was written: credits open lots
+    // and debits drain the oldest first, and transfers carry the age of their points across, exactly as
+    // post_ledger_entry would have
+    async fn migrate_point_lots(&self) -> Result<(), String> {
+        let mut conn = self.session.acquire().await?;
+        let mut tx = sqlx::Connection::begin(&mut *conn)
+            .await
+            .map_err(|e| format!("Failed to begin transaction: {}", e))?;
+
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM point_ledger l WHERE NOT EXISTS (SELECT 1 FROM point_lots p WHERE p.user_id = l.user_id) ORDER BY id",
+            LEDGER_COLUMNS
+        ))
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| format!("Database error: {}", e))?;
+
+        // Split legs carry the points that left the sender under the parent transfer
+        let split_parents: HashMap<u32, u32> = sqlx::query("SELECT id, parent_transfer_id FROM transfers WHERE parent_transfer_id IS NOT NULL")
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(|e| format!("Database error: {}", e))?
+            .iter()
+            .map(|row| (row.get::<i64, _>("id") as u32, row.get::<i64, _>("parent_transfer_id") as u32))
+            .collect();
+
+        let mut lots: HashMap<u32, Vec<ReplayedLot>> = HashMap::new();
+        let mut lots_in_flight: HashMap<u32, VecDeque<(DateTime<Utc>, u32)>> = HashMap::new();
+        for row in &rows {
+            let entry = ledger_from_row(row)?;
+            let carrier = entry.transfer_id.map(|transfer_id| split_parents.get(&transfer_id).copied().unwrap_or(transfer_id));
+            let user_lots = lots.entry(entry.user_id).or_default();
+            if entry.change > 0 {
+                let amount = entry.change as u32;
+                let carried = match carrier {
+                    Some(carrier) if entry.event_type == EventType::TransferIn => {
+                        carry_lots(lots_in_flight.entry(carrier).or_default(), amount, entry.created_at)
+                    }
+                    _ => vec![(entry.created_at, amount)],
+                };
+                for (earned_at, amount) in carried {
+                    user_lots.push((entry.id, earned_at, amount, amount));
+                }
+                continue;
+            }
+
+            // Oldest first, and in the order they were opened among lots of the same age
+            user_lots.sort_by_key(|&(_, earned_at, _, _)| earned_at);
+            let mut left = entry.change.unsigned_abs();
+            for (_, earned_at, _, remaining) in user_lots.iter_mut() {
+                if left == 0 {
+                    break;
+                }
+                let taken = left.min(*remaining);
+                *remaining -= taken;
+                left -= taken;
+                if let Some(carrier) = carrier
+                    && entry.event_type == EventType::TransferOut
+                {
+                    lots_in_flight.entry(carrier).or_default().push_back((*earned_at, taken));
+                }
+            }
+        }
+
+        for (user_id, user_lots) in lots {
+            for (entry_id, earned_at, amount, remaining) in user_lots {
+                sqlx::query("INSERT INTO point_lots (user_id, ledger_entry_id, amount, remaining, earned_at) VALUES (?, ?, ?, ?, ?)")
+                    .bind(user_id as i64)
+                    .bind(entry_id as i64)
+                    .bind(amount as i64)
+                    .bind(remaining as i64)
+                    .bind(earned_at.to_rfc3339())
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(|e| format!("Failed to backfill point lots: {}", e))?;
+            }
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit transaction: {}", e))
+    }
+}
+
+// One-off upgrade of a ledger written before the hash chain existed: opens each user's chain with their opening
+// balance, numbers the old entries in created_at order and seals them. It runs only on the start that adds the
+// hash column; an unhashed entry found after that did not come through the ledger and is never sealed.
+async fn seal_entries_from_before_the_chain(conn: &mut SqliteConnection) -> Result<(), String> {
+    // A user's opening balance is whatever their first ledger entry started from, or users.points if they have none
+    sqlx::query(
+        r#"
+        INSERT INTO point_ledger (user_id, change, balance_after, event_type, transfer_id, reference, metadata, created_at)
+        SELECT id, opening, opening, 'adjust', NULL, 'Opening balance', '{"opening_balance":true}', created_at
+        FROM (
+          SELECT u.id, u.created_at,
+            COALESCE(
+              (SELECT l.balance_after - l.change FROM point_ledger l WHERE l.user_id = u.id ORDER BY COALESCE(l.sequence, 0), l.created_at, l.id LIMIT 1),
+              u.points
+            ) AS opening
+          FROM users u
+        )
+        WHERE opening > 0
+        "#,
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| format!("Failed to migrate opening balances: {}", e))?;
+
+    // Renumber users that have unnumbered entries in created_at order; parking the old numbers below zero
+    // first keeps the renumbering clear of the unique index
+    sqlx::query(
+        r#"
+        UPDATE point_ledger SET sequence = -id
+        WHERE user_id IN (SELECT user_id FROM point_ledger WHERE sequence IS NULL)
+        "#,
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| format!("Failed to backfill ledger sequences: {}", e))?;
+
+    sqlx::query(
+        r#"
+        UPDATE point_ledger SET sequence = (
+          SELECT COUNT(*) FROM point_ledger earlier
+          WHERE earlier.user_id = point_ledger.user_id
+            AND (earlier.created_at < point_ledger.created_at
+              OR (earlier.created_at = point_ledger.created_at AND earlier.id <= point_ledger.id))
+        )
+        WHERE sequence < 0
+        "#,
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| format!("Failed to backfill ledger sequences: {}", e))?;
+
+    // Seal every entry, each chained to the one before it
+    let user_ids: Vec<i64> = sqlx::query_scalar("SELECT DISTINCT user_id FROM point_ledger")
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| format!("Database error: {}", e))?;
+    for user_id in user_ids {
+        let rows = sqlx::query(&format!("SELECT {} FROM point_ledger WHERE user_id = ? ORDER BY sequence", LEDGER_COLUMNS))
+            .bind(user_id)
+            .fetch_all(&mut *conn)
+            .await
+            .map_err(|e| format!("Database error: {}", e))?;
+
+        let mut prev_hash = GENESIS_HASH.to_string();
+        for row in &rows {
+            let entry = ledger_from_row(row)?;
+            let hash = entry.compute_hash(&prev_hash);
+            sqlx::query("UPDATE point_ledger SET hash = ?, prev_hash = ? WHERE id = ?")
+                .bind(&hash)
+                .bind(&prev_hash)
+                .bind(entry.id as i64)
+                .execute(&mut *conn)
+                .await
+                .map_err(|e| format!("Failed to backfill ledger hashes: {}", e))?;
+            prev_hash = hash;
+        }
+    }
+
+    Ok(())
+}
+
+fn point_ledger_table_sql(table: &str) -> String {
+    format!(
+        r#"
+        CREATE TABLE IF NOT EXISTS {} (
+          id INTEGER PRIMARY KEY AUTOINCREMENT,
+          user_id INTEGER NOT NULL,
+          sequence INTEGER,
+          change INTEGER NOT NULL,
+          balance_after INTEGER NOT NULL,
+          event_type TEXT NOT NULL CHECK (event_type IN ('transfer_out','transfer_in','adjust','earn','redeem','expire')),
+          transfer_id INTEGER,
+          reference TEXT,
+          metadata TEXT,
+          created_at TEXT NOT NULL,
+          idempotency_key TEXT,
+          hash TEXT,
+          prev_hash TEXT,
+          FOREIGN KEY (user_id) REFERENCES users(id),
+          FOREIGN KEY (transfer_id) REFERENCES transfers(id)
+        )
+        "#,
+        table
+    )
+}
+
+// SQLite cannot change a CHECK constraint in place, so a point_ledger created before the newest event type is
+// copied into a fresh table. Its indexes and triggers go with the old table; init_database recreates them.
+async fn migrate_event_types(conn: &mut SqliteConnection) -> Result<(), String> {
+    let table_sql: String = sqlx::query_scalar("SELECT sql FROM sqlite_master WHERE type = 'table' AND name = 'point_ledger'")
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to inspect point_ledger table: {}", e))?;
+    if table_sql.contains("'expire'") {
+        return Ok(());
+    }
+
+    // Other tables reference point_ledger; the references stay pointed at the name while the table is swapped
+    sqlx::query("PRAGMA foreign_keys = OFF")
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to disable foreign keys: {}", e))?;
+
+    let result = rebuild_point_ledger(&mut *conn).await;
+
+    sqlx::query("PRAGMA foreign_keys = ON")
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to enable foreign keys: {}", e))?;
+
+    result
+}
+
+async fn rebuild_point_ledger(conn: &mut SqliteConnection) -> Result<(), String> {
+    let mut tx = sqlx::Connection::begin(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to begin transaction: {}", e))?;
+
+    sqlx::query(&point_ledger_table_sql("point_ledger_new"))
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to create point_ledger table: {}", e))?;
+
+    sqlx::query(&format!("INSERT INTO point_ledger_new ({}) SELECT {} FROM point_ledger", LEDGER_COLUMNS, LEDGER_COLUMNS))
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to copy point_ledger: {}", e))?;
+
+    sqlx::query("DROP TABLE point_ledger")
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to drop old point_ledger: {}", e))?;
+
+    sqlx::query("ALTER TABLE point_ledger_new RENAME TO point_ledger")
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to rename point_ledger: {}", e))?;
+
+    let violations = sqlx::query("PRAGMA foreign_key_check(point_ledger)")
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to check foreign keys: {}", e))?;
+    if !violations.is_empty() {
+        return Err(format!("Rebuilding point_ledger left {} broken foreign keys", violations.len()));
+    }
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit transaction: {}", e))
+}
+
+// Takes `amount` from the user's lots, oldest first, and returns the (earned_at, amount) taken from each. Lots mirror
+// the balance, so a debit the balance allows always finds enough; if they have drifted, what is there is used and the
+// reconciler reports the rest.
+async fn consume_lots(conn: &mut SqliteConnection, user_id: u32, amount: u32) -> Result<Vec<(DateTime<Utc>, u32)>, String> {
+    let mut drained = Vec::new();
+    let mut left = amount;
+    while left > 0 {
+        let lot = sqlx::query("SELECT id, remaining, earned_at FROM point_lots WHERE user_id = ? AND remaining > 0 ORDER BY earned_at, id LIMIT 1")
+            .bind(user_id as i64)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| format!("Database error: {}", e))?;
+        let Some(lot) = lot else {
+            break;
+        };
+
+        let taken = left.min(lot.get::<i64, _>("remaining") as u32);
+        sqlx::query("UPDATE point_lots SET remaining = remaining - ? WHERE id = ?")
+            .bind(taken as i64)
+            .bind(lot.get::<i64, _>("id"))
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| format!("Failed to update point lot: {}", e))?;
+        let earned_at = DateTime::parse_from_rfc3339(lot.get("earned_at"))
+            .map_err(|e| format!("Invalid earned_at date: {}", e))?
+            .with_timezone(&Utc);
+        drained.push((earned_at, taken));
+        left -= taken;
+    }
+
+    Ok(drained)
+}
+
+// The transfer whose lots a transfer_out or transfer_in entry moves: its own, or for a split leg the parent transfer,
+// which is what the sender's single transfer_out was posted against
+async fn carrier_transfer_id(conn: &mut SqliteConnection, transfer_id: u32) -> Result<u32, String> {
+    let parent: Option<Option<i64>> = sqlx::query_scalar("SELECT parent_transfer_id FROM transfers WHERE id = ?")
+        .bind(transfer_id as i64)
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(|e| format!("Database error: {}", e))?;
+
+    Ok(parent.flatten().map_or(transfer_id, |parent| parent as u32))
+}
+
+// Lots for a credit of `amount` that carries points in flight, oldest first; anything the points in flight do not
+// cover (only if lots had drifted from the balance) counts as earned at `now`
+fn carry_lots(in_flight: &mut VecDeque<(DateTime<Utc>, u32)>, amount: u32, now: DateTime<Utc>) -> Vec<(DateTime<Utc>, u32)> {
+    let mut lots = Vec::new();
+    let mut left = amount;
+    while left > 0 {
+        let Some((earned_at, available)) = in_flight.pop_front() else {
+            lots.push((now, left));
+            break;
+        };
+        let taken = left.min(available);
+        if taken < available {
+            in_flight.push_front((earned_at, available - taken));
+        }
+        lots.push((earned_at, taken));
+        left -= taken;
+    }
+    lots
+}
+
+const LOT_COLUMNS: &str = "id, user_id, ledger_entry_id, amount, remaining, earned_at";
+
+fn lot_from_row(row: &SqliteRow) -> Result<PointLot, String> {
+    let lot_db = PointLotDb {
+        id: row.get::<i64, _>("id") as u32,
+        user_id: row.get::<i64, _>("user_id") as u32,
+        ledger_entry_id: row.get::<i64, _>("ledger_entry_id") as u32,
+        amount: row.get::<i64, _>("amount") as u32,
+        remaining: row.get::<i64, _>("remaining") as u32,
+        earned_at: row.get("earned_at"),
+    };
+    lot_db.into_domain()
+}
+
+const LEDGER_COLUMNS: &str = "id, user_id, sequence, change, balance_after, event_type, transfer_id, reference, metadata, created_at, idempotency_key, hash, prev_hash";
+
+fn ledger_from_row(row: &SqliteRow) -> Result<PointLedger, String> {
+    let ledger_db = PointLedgerDb {
+        id: row.get::<i64, _>("id") as u32,
+        user_id: row.get::<i64, _>("user_id") as u32,
+        sequence: row.get::<i64, _>("sequence") as u32,
+        change: row.get::<i64, _>("change") as i32,
+        balance_after: row.get::<i64, _>("balance_after") as u32,
+        event_type: row.get("event_type"),
+        transfer_id: row.get::<Option<i64>, _>("transfer_id").map(|id| id as u32),
+        reference: row.get("reference"),
+        metadata: row.get("metadata"),
+        created_at: row.get("created_at"),
+        idempotency_key: row.get("idempotency_key"),
+        hash: row.get("hash"),
+        prev_hash: row.get("prev_hash"),
+    };
+    ledger_db.into_domain()
+}
+
+// WHERE clause for a user's filtered ledger; bind_ledger_filter binds its parameters in the same order
+fn ledger_filter_conditions(filter: &LedgerFilter) -> String {
+    let mut conditions = vec!["user_id = ?".to_string()];
+    if !filter.event_types.is_empty() {
+        conditions.push(format!("event_type IN ({})", vec!["?"; filter.event_types.len()].join(", ")));
+    }
+    if filter.from.is_some() {
+        conditions.push("created_at >= ?".to_string());
+    }
+    if filter.to.is_some() {
+        conditions.push("created_at <= ?".to_string());
+    }
+    if filter.min_amount.is_some() {
+        conditions.push("ABS(change) >= ?".to_string());
+    }
+    if filter.max_amount.is_some() {
+        conditions.push("ABS(change) <= ?".to_string());
+    }
+    conditions.join(" AND ")
+}
+
+fn bind_ledger_filter<'q>(
+    mut query: Query<'q, Sqlite, SqliteArguments<'q>>,
+    user_id: u32,
+    filter: &LedgerFilter,
+) -> Query<'q, Sqlite, SqliteArguments<'q>> {
+    query = query.bind(user_id as i64);
+    for event_type in &filter.event_types {
+        query = query.bind(event_type.to_string());
+    }
+    if let Some(from) = filter.from {
+        query = query.bind(from.to_rfc3339());
+    }
+    if let Some(to) = filter.to {
+        query = query.bind(to.to_rfc3339());
+    }
+    if let Some(min_amount) = filter.min_amount {
+        query = query.bind(min_amount as i64);
+    }
+    if let Some(max_amount) = filter.max_amount {
+        query = query.bind(max_amount as i64);
+    }
+    query
+}
+
+// An entry as the caller describes it; insert_sealed_entry fills in its place in the chain
+fn unposted_entry(
+    user_id: u32,
+    change: i32,
+    balance_after: u32,
+    event_type: EventType,
+    transfer_id: Option<u32>,
+    reference: Option<String>,
+    metadata: Option<String>,
+) -> PointLedger {
+    PointLedger {
+        id: 0,
+        user_id,
+        sequence: 0,
+        change,
+        balance_after,
+        event_type,
+        transfer_id,
+        reference,
+        metadata,
+        created_at: Utc::now(),
+        idempotency_key: None,
+        hash: String::new(),
+        prev_hash: String::new(),
+    }
+}
+
+// Inserts the entry in the next slot of the user's ledger, chained to the entry before it and hashed over
+// everything it is written with; callers hold the account lock, and the unique index catches anyone who doesn't
+async fn insert_sealed_entry(conn: &mut SqliteConnection, entry: &mut PointLedger) -> Result<(), String> {
+    let previous = sqlx::query("SELECT sequence, hash FROM point_ledger WHERE user_id = ? ORDER BY sequence DESC LIMIT 1")
+        .bind(entry.user_id as i64)
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(|e| format!("Database error: {}", e))?;
+    (entry.sequence, entry.prev_hash) = match previous {
+        Some(row) => (row.get::<i64, _>("sequence") as u32 + 1, row.get::<Option<String>, _>("hash").unwrap_or_default()),
+        None => (1, GENESIS_HASH.to_string()),
+    };
+    entry.hash = entry.compute_hash(&entry.prev_hash);
+
+    let id: i64 = sqlx::query_scalar(
+        r#"
+        INSERT INTO point_ledger (user_id, sequence, change, balance_after, event_type, transfer_id, reference, metadata, created_at, idempotency_key, hash, prev_hash)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        RETURNING id
+        "#,
+    )
+    .bind(entry.user_id as i64)
+    .bind(entry.sequence as i64)
+    .bind(entry.change as i64)
+    .bind(entry.balance_after as i64)
+    .bind(entry.event_type.to_string())
+    .bind(entry.transfer_id.map(|id| id as i64))
+    .bind(&entry.reference)
+    .bind(&entry.metadata)
+    .bind(entry.created_at.to_rfc3339())
+    .bind(&entry.idempotency_key)
+    .bind(&entry.hash)
+    .bind(&entry.prev_hash)
+    .fetch_one(&mut *conn)
+    .await
+    .map_err(|e| format!("Failed to create ledger entry: {}", e))?;
+    entry.id = id as u32;
+
+    Ok(())
+}
+
+#[async_trait]
+impl PointLedgerRepository for SqlitePointLedgerRepository {
+    async fn create_ledger_entry(
+        &self,
+        user_id: u32,
+        change: i32,
+        balance_after: u32,
+        event_type: EventType,
+        transfer_id: Option<u32>,
+        reference: Option<String>,
+        metadata: Option<String>,
+    ) -> Result<PointLedger, String> {
+        self.post_ledger_entry(unposted_entry(user_id, change, balance_after, event_type, transfer_id, reference, metadata)).await
+    }
+
+    async fn get_ledger_by_user_id(&self, user_id: u32, filter: &LedgerFilter, cursor: Option<u32>, page: u32, page_size: u32) -> Result<(Vec<PointLedger>, LedgerTotals), String> {
+        let mut conn = self.session.acquire().await?;
+
+        let limit = page_size as i64;
+        // A cursor picks up right after the last entry already seen, so the page number no longer applies
+        let offset = match cursor {
+            Some(_) => 0,
+            None => ((page - 1) * page_size) as i64,
+        };
+        let conditions = ledger_filter_conditions(filter);
+
+        // Get totals
+        let totals_sql = format!(
+            "SELECT COUNT(*) AS total, COALESCE(SUM(CASE WHEN change > 0 THEN change ELSE 0 END), 0) AS total_in, COALESCE(SUM(CASE WHEN change < 0 THEN -change ELSE 0 END), 0) AS total_out FROM point_ledger WHERE {}",
+            conditions
+        );
+        let row = bind_ledger_filter(sqlx::query(&totals_sql), user_id, filter)
+            .fetch_one(&mut *conn)
+            .await
+            .map_err(|e| format!("Database error: {}", e))?;
+        let totals = LedgerTotals {
+            total: row.get::<i64, _>("total") as u32,
+            total_in: row.get::<i64, _>("total_in") as u64,
+            total_out: row.get::<i64, _>("total_out") as u64,
+        };
+
+        // Get entries
+        let cursor_condition = if cursor.is_some() { " AND sequence < ?" } else { "" };
+        let entries_sql = format!(
+            "SELECT {} FROM point_ledger WHERE {}{} ORDER BY sequence DESC LIMIT ? OFFSET ?",
+            LEDGER_COLUMNS, conditions, cursor_condition
+        );
+        let mut query = bind_ledger_filter(sqlx::query(&entries_sql), user_id, filter);
+        if let Some(cursor) = cursor {
+            query = query.bind(cursor as i64);
+        }
+        let rows = query
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(&mut *conn)
+            .await
+            .map_err(|e| format!("Database error: {}", e))?;
+
+        let entries = rows.iter()
+            .map(ledger_from_row)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok((entries, totals))
+    }
+
+    async fn get_ledger_entry(&self, entry_id: u32) -> Result<Option<PointLedger>, String> {
+        let mut conn = self.session.acquire().await?;
+
+        let row = sqlx::query(&format!("SELECT {} FROM point_ledger WHERE id = ?", LEDGER_COLUMNS))
+            .bind(entry_id as i64)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| format!("Database error: {}", e))?;
+
+        row.as_ref().map(ledger_from_row).transpose()
+    }
+
+    async fn get_full_ledger(&self, user_id: u32) -> Result<Vec<PointLedger>, String> {
+        let mut conn = self.session.acquire().await?;
+
+        let rows = sqlx::query(&format!("SELECT {} FROM point_ledger WHERE user_id = ? ORDER BY sequence", LEDGER_COLUMNS))
+            .bind(user_id as i64)
+            .fetch_all(&mut *conn)
+            .await
+            .map_err(|e| format!("Database error: {}", e))?;
+
+        rows.iter().map(ledger_from_row).collect()
+    }
+
+    async fn get_current_balance(&self, user_id: u32) -> Result<u32, String> {
+        let mut conn = self.session.acquire().await?;
+
+        // The ledger is the only source of truth; a user without entries has no points
+        let balance: Option<i64> = sqlx::query_scalar(
+            "SELECT balance_after FROM point_ledger WHERE user_id = ? ORDER BY sequence DESC LIMIT 1"
+        )
+        .bind(user_id as i64)
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(|e| format!("Database error: {}", e))?;
+
+        Ok(balance.unwrap_or(0) as u32)
+    }
+
+    async fn get_balance_at(&self, user_id: u32, at: DateTime<Utc>) -> Result<Option<(u32, u32)>, String> {
+        let mut conn = self.session.acquire().await?;
+
+        // Among the entries that existed by then, the latest is the one furthest along the sequence. Timestamps are
+        // compared as instants: as text, an entry written with another offset sorts on its local date
+        let row = sqlx::query(
+            "SELECT balance_after, sequence FROM point_ledger WHERE user_id = ? AND julianday(created_at) <= julianday(?) ORDER BY sequence DESC LIMIT 1"
+        )
+        .bind(user_id as i64)
+        .bind(at.to_rfc3339())
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(|e| format!("Database error: {}", e))?;
+
+        Ok(row.map(|row| (row.get::<i64, _>("balance_after") as u32, row.get::<i64, _>("sequence") as u32)))
+    }
+
+    async fn get_held_amount(&self, user_id: u32) -> Result<u32, String> {
+        let mut conn = self.session.acquire().await?;
+
+        let held: i64 = sqlx::query_scalar(
+            r#"
+            SELECT
+              (SELECT COALESCE(SUM(amount), 0) FROM point_holds WHERE user_id = ? AND status = 'active')
+              + (SELECT COALESCE(SUM(amount), 0) FROM transfers WHERE from_user_id = ? AND status = 'pending' AND accept_by IS NOT NULL)
+            "#,
+        )
+        .bind(user_id as i64)
+        .bind(user_id as i64)
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(|e| format!("Database error: {}", e))?;
+
+        Ok(held as u32)
+    }
+
+    async fn get_available_balance(&self, user_id: u32) -> Result<u32, String> {
+        let balance = self.get_current_balance(user_id).await?;
+        let held = self.get_held_amount(user_id).await?;
+
+        Ok(balance.saturating_sub(held))
+    }
+
+    async fn get_ledger_by_transfer_id(&self, transfer_id: u32) -> Result<Vec<PointLedger>, String> {
+        let mut conn = self.session.acquire().await?;
+
+        let rows = sqlx::query(&format!("SELECT {} FROM point_ledger WHERE transfer_id = ? ORDER BY id", LEDGER_COLUMNS))
+            .bind(transfer_id as i64)
+            .fetch_all(&mut *conn)
+            .await
+            .map_err(|e| format!("Database error: {}", e))?;
+
+        rows.iter().map(ledger_from_row).collect()
+    }
+
+    async fn get_ledger_entry_by_idempotency_key(&self, user_id: u32, key: &str) -> Result<Option<PointLedger>, String> {
+        let mut conn = self.session.acquire().await?;
+
+        let row = sqlx::query(&format!("SELECT {} FROM point_ledger WHERE user_id = ? AND idempotency_key = ?", LEDGER_COLUMNS))
+            .bind(user_id as i64)
+            .bind(key)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| format!("Database error: {}", e))?;
+
+        row.as_ref().map(ledger_from_row).transpose()
+    }
+
+    async fn create_keyed_ledger_entry(
+        &self,
+        user_id: u32,
+        change: i32,
+        balance_after: u32,
+        event_type: EventType,
+        reference: Option<String>,
+        metadata: Option<String>,
+        idempotency_key: &str,
+    ) -> Result<PointLedger, String> {
+        let mut entry = unposted_entry(user_id, change, balance_after, event_type, None, reference, metadata);
+        entry.idempotency_key = Some(idempotency_key.to_string());
+        self.post_ledger_entry(entry).await
+    }
+
+    async fn has_debits_after(&self, user_id: u32, after_entry_id: u32) -> Result<bool, String> {
+        let mut conn = self.session.acquire().await?;
+
+        let count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM point_ledger WHERE user_id = ? AND sequence > (SELECT sequence FROM point_ledger WHERE id = ?) AND change < 0"
+        )
+        .bind(user_id as i64)
+        .bind(after_entry_id as i64)
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(|e| format!("Database error: {}", e))?;
+
+        Ok(count > 0)
+    }
+
+    async fn get_open_lots(&self, user_id: u32) -> Result<Vec<PointLot>, String> {
+        let mut conn = self.session.acquire().await?;
+
+        let rows = sqlx::query(&format!("SELECT {} FROM point_lots WHERE user_id = ? AND remaining > 0 ORDER BY earned_at, id", LOT_COLUMNS))
+            .bind(user_id as i64)
+            .fetch_all(&mut *conn)
+            .await
+            .map_err(|e| format!("Database error: {}", e))?;
+
+        rows.iter().map(lot_from_row).collect()
+    }
+
+    async fn get_users_with_lots_earned_before(&self, cutoff: DateTime<Utc>, after_user_id: u32, limit: u32) -> Result<Vec<u32>, String> {
+        let mut conn = self.session.acquire().await?;
+
+        let user_ids: Vec<i64> = sqlx::query_scalar(
+            "SELECT DISTINCT user_id FROM point_lots WHERE remaining > 0 AND earned_at <= ? AND user_id > ? ORDER BY user_id LIMIT ?"
+        )
+        .bind(cutoff.to_rfc3339())
+        .bind(after_user_id as i64)
+        .bind(limit as i64)
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| format!("Database error: {}", e))?;
+
+        Ok(user_ids.into_iter().map(|id| id as u32).collect())
+    }
+
+    async fn get_points_earned(&self, user_id: u32, since: DateTime<Utc>) -> Result<u64, String> {
+        let mut conn = self.session.acquire().await?;
+
+        // A tier bonus is an earn entry too, but it is paid for holding the tier, not towards keeping it
+        let earned: i64 = sqlx::query_scalar(
+            "SELECT COALESCE(SUM(change), 0) FROM point_ledger WHERE user_id = ? AND event_type = 'earn' AND json_extract(metadata, '$.tier_bonus_for') IS NULL AND created_at >= ?"
+        )
+        .bind(user_id as i64)
+        .bind(since.to_rfc3339())
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(|e| format!("Database error: {}", e))?;
+
+        Ok(earned as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{CreateTransferRequest, CreateSplitTransferRequest, SplitRecipient};
+    use crate::infrastructure::test_support::test_pool;
+
+    const ALL_STATUSES: [TransferStatus; 6] = [
+        TransferStatus::Pending,
+        TransferStatus::Processing,
+        TransferStatus::Completed,
+        TransferStatus::Failed,
+        TransferStatus::Cancelled,
+        TransferStatus::Reversed,
+    ];
+
+    async fn transfer_in_status(repository: &SqliteTransferRepository, pool: &SqlitePool, status: TransferStatus) -> String {
+        let request = CreateTransferRequest { from_user_id: 1, to_user_id: 2, amount: 10, note: None, execute_at: None };
+        let transfer = repository.create_transfer(request, Utc::now()).await.unwrap();
+        sqlx::query("UPDATE transfers SET status = ? WHERE idempotency_key = ?")
+            .bind(status.to_string())
+            .bind(&transfer.idem_key)
+            .execute(pool)
+            .await
+            .unwrap();
+        transfer.idem_key
+    }
+
+    async fn status_of(repository: &SqliteTransferRepository, idem_key: &str) -> TransferStatus {
+        repository.get_transfer_by_idem_key(idem_key).await.unwrap().unwrap().status
+    }
+
+    #[tokio::test]
+    async fn disallowed_transitions_leave_the_row_untouched() {
+        let pool = test_pool().await;
+        let repository = SqliteTransferRepository::new(pool.clone());
+
+        for from in ALL_STATUSES {
+            for to in ALL_STATUSES.into_iter().filter(|to| !from.can_transition_to(*to)) {
+                let idem_key = transfer_in_status(&repository, &pool, from).await;
+
+                let err = repository.update_transfer_status(&idem_key, from, to, None, None, Utc::now()).await.unwrap_err();
+                assert!(err.starts_with("Illegal transfer status transition"), "{} -> {}: {}", from, to, err);
+                assert_eq!(status_of(&repository, &idem_key).await, from, "{} -> {} changed the row", from, to);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn a_stale_current_status_loses_the_compare_and_set() {
+        let pool = test_pool().await;
+        let repository = SqliteTransferRepository::new(pool.clone());
+
+        // Another writer already completed it; a caller still holding "pending" must not move it back
+        let idem_key = transfer_in_status(&repository, &pool, TransferStatus::Completed).await;
+        let err = repository
+            .update_transfer_status(&idem_key, TransferStatus::Pending, TransferStatus::Processing, None, None, Utc::now())
+            .await
+            .unwrap_err();
+
+        assert_eq!(err, "Illegal transfer status transition: transfer is no longer pending");
+        assert_eq!(status_of(&repository, &idem_key).await, TransferStatus::Completed);
+    }
+
+    #[tokio::test]
+    async fn allowed_transition_moves_the_row() {
+        let pool = test_pool().await;
+        let repository = SqliteTransferRepository::new(pool.clone());
+
+        let idem_key = transfer_in_status(&repository, &pool, TransferStatus::Pending).await;
+        repository.update_transfer_status(&idem_key, TransferStatus::Pending, TransferStatus::Processing, None, None, Utc::now()).await.unwrap();
+
+        assert_eq!(status_of(&repository, &idem_key).await, TransferStatus::Processing);
+    }
+
+    #[tokio::test]
+    async fn idempotency_key_is_written_with_the_entry_and_hashed() {
+        let pool = test_pool().await;
+        let ledger = SqlitePointLedgerRepository::new(pool.clone());
+
+        let entry = ledger.create_keyed_ledger_entry(1, 50, 1550, EventType::Earn, None, None, "earn-key-1").await.unwrap();
+        let stored = ledger.get_ledger_entry_by_idempotency_key(1, "earn-key-1").await.unwrap().unwrap();
+        assert_eq!(stored.id, entry.id);
+        assert_eq!(stored.hash, stored.compute_hash(&stored.prev_hash));
+
+        let rekeyed = PointLedger { idempotency_key: Some("earn-key-2".to_string()), ..stored.clone() };
+        assert_ne!(rekeyed.compute_hash(&stored.prev_hash), stored.hash);
+    }
+
+    #[tokio::test]
+    async fn every_update_and_delete_is_rejected() {
+        let pool = test_pool().await;
+        let ledger = SqlitePointLedgerRepository::new(pool.clone());
+        let keyed = ledger.create_keyed_ledger_entry(2, 10, 760, EventType::Earn, None, None, "earn-key").await.unwrap();
+        let unkeyed = ledger.create_ledger_entry(2, 10, 770, EventType::Earn, None, None, None).await.unwrap();
+
+        let statements = [
+            format!("UPDATE point_ledger SET idempotency_key = 'other' WHERE id = {}", keyed.id),
+            format!("UPDATE point_ledger SET idempotency_key = 'late' WHERE id = {}", unkeyed.id),
+            format!("UPDATE point_ledger SET change = 1000 WHERE id = {}", unkeyed.id),
+            format!("UPDATE point_ledger SET hash = NULL WHERE id = {}", unkeyed.id),
+            format!("DELETE FROM point_ledger WHERE id = {}", unkeyed.id),
+        ];
+        for statement in &statements {
+            let err = sqlx::query(statement).execute(&pool).await.unwrap_err();
+            assert!(err.to_string().contains("point_ledger entries"), "{}: {}", statement, err);
+        }
+
+        let unchanged = ledger.get_full_ledger(2).await.unwrap();
+        assert_eq!(unchanged.last().unwrap().hash, unkeyed.hash);
+        assert_eq!(unchanged[unchanged.len() - 2].idempotency_key.as_deref(), Some("earn-key"));
+    }
+
+    #[tokio::test]
+    async fn unhashed_inserts_are_rejected() {
+        let pool = test_pool().await;
+
+        let err = sqlx::query(
+            "INSERT INTO point_ledger (user_id, sequence, change, balance_after, event_type, created_at) VALUES (3, 2, 500, 700, 'adjust', ?)"
+        )
+        .bind(Utc::now().to_rfc3339())
+        .execute(&pool)
+        .await
+        .unwrap_err();
+        assert!(err.to_string().contains("must be numbered and hashed"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn restarting_reports_unhashed_entries_instead_of_sealing_them() {
+        let pool = test_pool().await;
+        // Someone with write access to the file gets an entry past the triggers
+        sqlx::query("DROP TRIGGER point_ledger_sealed_insert").execute(&pool).await.unwrap();
+        sqlx::query(
+            "INSERT INTO point_ledger (user_id, sequence, change, balance_after, event_type, created_at) VALUES (3, 2, 500, 700, 'adjust', ?)"
+        )
+        .bind(Utc::now().to_rfc3339())
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        SqlitePointLedgerRepository::new(pool.clone()).init_database().await.unwrap();
+
+        let unhashed: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM point_ledger WHERE hash IS NULL")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(unhashed, 1);
+    }
+
+    #[tokio::test]
+    async fn replaying_the_ledger_rebuilds_the_same_lots() {
+        let pool = test_pool().await;
+        let ledger = SqlitePointLedgerRepository::new(pool.clone());
+        let johns_points_earned_at = ledger.get_open_lots(1).await.unwrap()[0].earned_at;
+        let transfers = SqliteTransferRepository::new(pool.clone());
+        let mut transfer_ids = Vec::new();
+        for (from_user_id, to_user_id, amount) in [(1, 3, 600), (3, 1, 50), (3, 2, 700)] {
+            let request = CreateTransferRequest { from_user_id, to_user_id, amount, note: None, execute_at: None };
+            transfer_ids.push(transfers.create_transfer(request, Utc::now()).await.unwrap().transfer_id);
+        }
+        let [john_to_bob, bob_to_john, bob_to_jane] = transfer_ids[..] else { unreachable!() };
+        // Two transfers in flight at once: each credit picks up the lots of its own transfer, not the first to leave
+        ledger.create_ledger_entry(3, -50, 150, EventType::TransferOut, bob_to_john, None, None).await.unwrap();
+        ledger.create_ledger_entry(1, -600, 900, EventType::TransferOut, john_to_bob, None, None).await.unwrap();
+        ledger.create_ledger_entry(3, 600, 750, EventType::TransferIn, john_to_bob, None, None).await.unwrap();
+        ledger.create_ledger_entry(1, 50, 950, EventType::TransferIn, bob_to_john, None, None).await.unwrap();
+        ledger.create_ledger_entry(3, -700, 50, EventType::TransferOut, bob_to_jane, None, None).await.unwrap();
+        let jane_in = ledger.create_ledger_entry(2, 700, 1450, EventType::TransferIn, bob_to_jane, None, None).await.unwrap();
+        ledger.create_ledger_entry(2, 50, 1500, EventType::Earn, None, None, None).await.unwrap();
+
+        let lots_of = |user_id| {
+            let ledger = ledger.clone();
+            async move {
+                ledger.get_open_lots(user_id).await.unwrap().into_iter()
+                    .map(|lot| (lot.ledger_entry_id, lot.remaining, lot.earned_at))
+                    .collect::<Vec<_>>()
+            }
+        };
+        let posted = [lots_of(1).await, lots_of(2).await, lots_of(3).await];
+        // Bob passed John's 600 on before 100 of Bob's own, and Jane keeps the age of both
+        let jane_received: Vec<_> = posted[1].iter().filter(|lot| lot.0 == jane_in.id).collect();
+        assert_eq!(jane_received.iter().map(|lot| lot.1).sum::<u32>(), 700);
+        assert!(jane_received.iter().any(|lot| lot.1 == 600 && lot.2 == johns_points_earned_at), "{:?}", posted);
+        assert!(posted[0].iter().any(|lot| lot.1 == 50 && lot.2 != johns_points_earned_at), "{:?}", posted);
+
+        sqlx::query("DELETE FROM point_lots").execute(&pool).await.unwrap();
+        ledger.init_database().await.unwrap();
+
+        assert_eq!([lots_of(1).await, lots_of(2).await, lots_of(3).await], posted);
+    }
+
+    #[tokio::test]
+    async fn split_transfer_rechecks_recipients_in_its_transaction() {
+        let pool = test_pool().await;
+        let repository = SqliteTransferRepository::new(pool.clone());
+        let split = |recipients: &[(u32, u32)]| CreateSplitTransferRequest {
+            from_user_id: 1,
+            recipients: recipients.iter().map(|&(to_user_id, amount)| SplitRecipient { to_user_id, amount }).collect(),
+            note: None,
+        };
+
+        let err = repository.create_split_transfer(split(&[(2, 10), (99, 10)]), Utc::now()).await.unwrap_err();
+        assert_eq!(err, "To user 99 not found");
+        let err = repository.create_split_transfer(split(&[(2, 10), (2, 10)]), Utc::now()).await.unwrap_err();
+        assert!(err.contains("only appear once"), "{}", err);
+
+        let transfers: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM transfers").fetch_one(&pool).await.unwrap();
+        assert_eq!(transfers, 0);
     }
 }
\ No newline at end of file
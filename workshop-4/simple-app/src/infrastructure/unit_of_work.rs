@@ -0,0 +1,174 @@
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+use async_trait::async_trait;
+use sqlx::{Sqlite, SqliteConnection, SqlitePool, Transaction};
+use sqlx::pool::PoolConnection;
+use tokio::sync::{Mutex, MutexGuard};
+use crate::domain::{UnitOfWork, UnitOfWorkFactory, TransferRepository, PointLedgerRepository, IdempotencyRepository, HoldRepository, AdjustmentRepository, TierRepository, MandateRepository};
+use super::transfer_repository::{SqliteTransferRepository, SqlitePointLedgerRepository};
+use super::idempotency_repository::SqliteIdempotencyRepository;
+use super::hold_repository::SqliteHoldRepository;
+use super::adjustment_repository::SqliteAdjustmentRepository;
+use super::tier_repository::SqliteTierRepository;
+use super::mandate_repository::SqliteMandateRepository;
+
+type SharedTransaction = Arc<Mutex<Option<Transaction<'static, Sqlite>>>>;
+
+// Where a repository sends its statements: straight to the pool, or into an open transaction
+#[derive(Clone)]
+pub enum SqliteSession {
+    Pool(SqlitePool),
+    Transaction(SharedTransaction),
+}
+
+pub enum SqliteConnectionGuard<'a> {
+    Pool(PoolConnection<Sqlite>),
+    Transaction(MutexGuard<'a, Option<Transaction<'static, Sqlite>>>),
+}
+
+impl SqliteSession {
+    pub async fn acquire(&self) -> Result<SqliteConnectionGuard<'_>, String> {
+        match self {
+            SqliteSession::Pool(pool) => {
+                let conn = pool.acquire()
+                    .await
+                    .map_err(|e| format!("Database error: {}", e))?;
+                Ok(SqliteConnectionGuard::Pool(conn))
+            }
+            SqliteSession::Transaction(tx) => {
+                let guard = tx.lock().await;
+                if guard.is_none() {
+                    return Err("Unit of work already finished".to_string());
+                }
+                Ok(SqliteConnectionGuard::Transaction(guard))
+            }
+        }
+    }
+}
+
+impl Deref for SqliteConnectionGuard<'_> {
+    type Target = SqliteConnection;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            SqliteConnectionGuard::Pool(conn) => conn,
+            SqliteConnectionGuard::Transaction(guard) => guard.as_ref().expect("transaction checked on acquire"),
+        }
+    }
+}
+
+impl DerefMut for SqliteConnectionGuard<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        match self {
+            SqliteConnectionGuard::Pool(conn) => conn,
+            SqliteConnectionGuard::Transaction(guard) => guard.as_mut().expect("transaction checked on acquire"),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct SqliteUnitOfWorkFactory {
+    pool: SqlitePool,
+}
+
+impl SqliteUnitOfWorkFactory {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl UnitOfWorkFactory for SqliteUnitOfWorkFactory {
+    async fn begin(&self) -> Result<Box<dyn UnitOfWork>, String> {
+        // IMMEDIATE takes the write lock up front, so the balance read inside the
+        // transaction cannot go stale before the ledger rows are written
+        let tx = self.pool.begin_with("BEGIN IMMEDIATE")
+            .await
+            .map_err(|e| format!("Failed to begin transaction: {}", e))?;
+
+        Ok(Box::new(SqliteUnitOfWork::new(tx)))
+    }
+}
+
+pub struct SqliteUnitOfWork {
+    tx: SharedTransaction,
+    transfer_repository: Arc<SqliteTransferRepository>,
+    point_ledger_repository: Arc<SqlitePointLedgerRepository>,
+    idempotency_repository: Arc<SqliteIdempotencyRepository>,
+    hold_repository: Arc<SqliteHoldRepository>,
+    adjustment_repository: Arc<SqliteAdjustmentRepository>,
+    tier_repository: Arc<SqliteTierRepository>,
+    mandate_repository: Arc<SqliteMandateRepository>,
+}
+
+impl SqliteUnitOfWork {
+    fn new(tx: Transaction<'static, Sqlite>) -> Self {
+        let tx = Arc::new(Mutex::new(Some(tx)));
+        let session = SqliteSession::Transaction(tx.clone());
+
+        Self {
+            tx,
+            transfer_repository: Arc::new(SqliteTransferRepository::with_session(session.clone())),
+            point_ledger_repository: Arc::new(SqlitePointLedgerRepository::with_session(session.clone())),
+            idempotency_repository: Arc::new(SqliteIdempotencyRepository::with_session(session.clone())),
+            hold_repository: Arc::new(SqliteHoldRepository::with_session(session.clone())),
+            adjustment_repository: Arc::new(SqliteAdjustmentRepository::with_session(session.clone())),
+            tier_repository: Arc::new(SqliteTierRepository::with_session(session.clone())),
+            mandate_repository: Arc::new(SqliteMandateRepository::with_session(session)),
+        }
+    }
+
+    async fn take_transaction(&self) -> Result<Transaction<'static, Sqlite>, String> {
+        self.tx.lock()
+            .await
+            .take()
+            .ok_or("Unit of work already finished".to_string())
+    }
+}
+
+#[async_trait]
+impl UnitOfWork for SqliteUnitOfWork {
+    fn transfers(&self) -> Arc<dyn TransferRepository + Send + Sync> {
+        self.transfer_repository.clone()
+    }
+
+    fn point_ledger(&self) -> Arc<dyn PointLedgerRepository + Send + Sync> {
+        self.point_ledger_repository.clone()
+    }
+
+    fn idempotency_keys(&self) -> Arc<dyn IdempotencyRepository + Send + Sync> {
+        self.idempotency_repository.clone()
+    }
+
+    fn holds(&self) -> Arc<dyn HoldRepository + Send + Sync> {
+        self.hold_repository.clone()
+    }
+
+    fn adjustments(&self) -> Arc<dyn AdjustmentRepository + Send + Sync> {
+        self.adjustment_repository.clone()
+    }
+
+    fn tiers(&self) -> Arc<dyn TierRepository + Send + Sync> {
+        self.tier_repository.clone()
+    }
+
+    fn mandates(&self) -> Arc<dyn MandateRepository + Send + Sync> {
+        self.mandate_repository.clone()
+    }
+
+    async fn commit(self: Box<Self>) -> Result<(), String> {
+        self.take_transaction()
+            .await?
+            .commit()
+            .await
+            .map_err(|e| format!("Failed to commit transaction: {}", e))
+    }
+
+    async fn rollback(self: Box<Self>) -> Result<(), String> {
+        self.take_transaction()
+            .await?
+            .rollback()
+            .await
+            .map_err(|e| format!("Failed to rollback transaction: {}", e))
+    }
+}
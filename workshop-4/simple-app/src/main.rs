@@ -4,37 +4,100 @@ mod application;
 mod presentation;
 
 use std::sync::Arc;
-use utoipa::OpenApi;
+use utoipa::{Modify, OpenApi};
+use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
 use utoipa_swagger_ui::SwaggerUi;
 use sqlx::SqlitePool;
 
-use domain::{User, CreateUserRequest, UpdateUserRequest, Transfer, CreateTransferRequest, TransferCreateResponse, TransferGetResponse, TransferListResponse};
-use infrastructure::{SqliteUserRepository, SqliteTransferRepository, SqlitePointLedgerRepository};
-use application::{UserService, TransferService};
-use presentation::{create_routes, AppState, ErrorResponse, ListUsersResponse};
+use domain::{SystemClock, TIER_QUALIFICATION_MONTHS, UserRepository, User, UserRole, CreateUserRequest, UpdateUserRequest, Transfer, TransferType, CreateTransferRequest, SplitRecipient, CreateSplitTransferRequest, TransferSplitResponse, TransferCreateResponse, TransferGetResponse, TransferListResponse, ReverseTransferRequest, TransferReversal, TransferReverseResponse, CancelTransferRequest, TransferCancelResponse, AcceptTransferRequest, DeclineTransferRequest, TransferAcceptanceResponse, TransferMandate, MandateFrequency, MandateStatus, CreateMandateRequest, UpdateMandateRequest, MandateListResponse, TransferBatch, BatchItem, BatchMode, BatchStatus, BatchItemStatus, CreateBatchRequest, TransferLimits, UpdateTransferLimitsRequest, TransferLimitsListResponse, TransferLimitViolation, PointHold, HoldStatus, CreateHoldRequest, CaptureHoldRequest, HoldListResponse, PointBalance, PointLedger, EventType, LedgerListResponse, LedgerEntryDetail, LedgerCounterparty, PointsOperationRequest, PointsOperationResponse, BalanceAdjustment, AdjustmentStatus, AdjustmentAction, AdjustmentAuditEntry, CreateAdjustmentRequest, ReviewAdjustmentRequest, AdjustmentResponse, AdjustmentListResponse, DiscrepancyKind, Discrepancy, ReconciliationReport, BrokenLink, LedgerChainReport, BalanceInterval, HistoricalBalance, BalanceResponse, BalanceSeriesPoint, BalanceSeriesResponse, ExpiringPoints, ExpiringPointsResponse, MembershipTier, TierChangeReason, TierChange, TierBenefits, TierListResponse, UserTierStatus, SetTierRequest, TierHistoryResponse, TierEvaluationReport, RegisterRequest, LoginRequest, OtpRequest, OtpRequestResponse, RefreshRequest, TokenResponse};
+use infrastructure::{SqliteUserRepository, SqliteTransferRepository, SqlitePointLedgerRepository, SqliteUnitOfWorkFactory, SqliteIdempotencyRepository, SqliteMandateRepository, SqliteBatchRepository, SqliteTransferLimitRepository, SqliteHoldRepository, SqliteAdjustmentRepository, SqliteTierRepository, SqliteAuthRepository, ConsoleOtpSender};
+use application::{UserService, TransferService, TransferWorker, MandateService, MandateWorker, BatchService, BatchWorker, HoldService, HoldWorker, LedgerService, PointsService, AdjustmentService, ReconciliationService, ReconciliationWorker, PointsExpiryService, ExpiryWorker, TierService, TierWorker, AuthService, random_token};
+use presentation::{create_routes, AppState, ErrorResponse, LimitExceededResponse, ListUsersResponse};
 
 #[derive(OpenApi)]
 #[openapi(
     paths(
         presentation::handlers::hello_world,
+        presentation::auth_handlers::register,
+        presentation::auth_handlers::login,
+        presentation::auth_handlers::request_login_otp,
+        presentation::auth_handlers::refresh_token,
+        presentation::auth_handlers::logout,
+        presentation::auth_handlers::get_current_user,
         presentation::handlers::get_user,
         presentation::handlers::list_users,
         presentation::handlers::create_user,
         presentation::handlers::update_user,
         presentation::handlers::delete_user,
         presentation::transfer_handlers::create_transfer,
+        presentation::transfer_handlers::create_split_transfer,
         presentation::transfer_handlers::get_transfer,
         presentation::transfer_handlers::list_transfers,
+        presentation::transfer_handlers::reverse_transfer,
+        presentation::transfer_handlers::cancel_transfer,
+        presentation::transfer_handlers::accept_transfer,
+        presentation::transfer_handlers::decline_transfer,
+        presentation::batch_handlers::create_transfer_batch,
+        presentation::batch_handlers::get_transfer_batch,
+        presentation::transfer_limit_handlers::list_transfer_limits,
+        presentation::transfer_limit_handlers::get_transfer_limits,
+        presentation::transfer_limit_handlers::set_transfer_limits,
+        presentation::hold_handlers::create_hold,
+        presentation::hold_handlers::list_holds,
+        presentation::hold_handlers::get_hold,
+        presentation::hold_handlers::capture_hold,
+        presentation::hold_handlers::release_hold,
+        presentation::hold_handlers::get_balance,
+        presentation::ledger_handlers::list_ledger,
+        presentation::ledger_handlers::get_ledger_entry,
+        presentation::ledger_handlers::get_balance_series,
+        presentation::points_handlers::earn_points,
+        presentation::points_handlers::redeem_points,
+        presentation::points_handlers::list_expiring_points,
+        presentation::tier_handlers::list_tiers,
+        presentation::tier_handlers::get_user_tier,
+        presentation::tier_handlers::set_user_tier,
+        presentation::tier_handlers::get_tier_history,
+        presentation::adjustment_handlers::create_adjustment,
+        presentation::adjustment_handlers::list_adjustments,
+        presentation::adjustment_handlers::get_adjustment,
+        presentation::adjustment_handlers::approve_adjustment,
+        presentation::adjustment_handlers::reject_adjustment,
+        presentation::reconciliation_handlers::get_reconciliation,
+        presentation::reconciliation_handlers::verify_ledger_chain,
+        presentation::tier_handlers::evaluate_tiers,
+        presentation::mandate_handlers::create_mandate,
+        presentation::mandate_handlers::list_mandates,
+        presentation::mandate_handlers::get_mandate,
+        presentation::mandate_handlers::update_mandate,
+        presentation::mandate_handlers::cancel_mandate,
+        presentation::mandate_handlers::pause_mandate,
+        presentation::mandate_handlers::resume_mandate,
     ),
     components(
-        schemas(User, CreateUserRequest, UpdateUserRequest, Transfer, CreateTransferRequest, TransferCreateResponse, TransferGetResponse, TransferListResponse, ErrorResponse, ListUsersResponse)
+        schemas(User, UserRole, CreateUserRequest, UpdateUserRequest, Transfer, TransferType, CreateTransferRequest, SplitRecipient, CreateSplitTransferRequest, TransferSplitResponse, TransferCreateResponse, TransferGetResponse, TransferListResponse, ReverseTransferRequest, TransferReversal, TransferReverseResponse, CancelTransferRequest, TransferCancelResponse, AcceptTransferRequest, DeclineTransferRequest, TransferAcceptanceResponse, TransferMandate, MandateFrequency, MandateStatus, CreateMandateRequest, UpdateMandateRequest, MandateListResponse, TransferBatch, BatchItem, BatchMode, BatchStatus, BatchItemStatus, CreateBatchRequest, TransferLimits, UpdateTransferLimitsRequest, TransferLimitsListResponse, TransferLimitViolation, PointHold, HoldStatus, CreateHoldRequest, CaptureHoldRequest, HoldListResponse, PointBalance, PointLedger, EventType, LedgerListResponse, LedgerEntryDetail, LedgerCounterparty, PointsOperationRequest, PointsOperationResponse, BalanceAdjustment, AdjustmentStatus, AdjustmentAction, AdjustmentAuditEntry, CreateAdjustmentRequest, ReviewAdjustmentRequest, AdjustmentResponse, AdjustmentListResponse, DiscrepancyKind, Discrepancy, ReconciliationReport, BrokenLink, LedgerChainReport, BalanceInterval, HistoricalBalance, BalanceResponse, BalanceSeriesPoint, BalanceSeriesResponse, ExpiringPoints, ExpiringPointsResponse, MembershipTier, TierChangeReason, TierChange, TierBenefits, TierListResponse, UserTierStatus, SetTierRequest, TierHistoryResponse, TierEvaluationReport, RegisterRequest, LoginRequest, OtpRequest, OtpRequestResponse, RefreshRequest, TokenResponse, ErrorResponse, LimitExceededResponse, ListUsersResponse)
     ),
+    modifiers(&SecurityAddon),
     tags(
         (name = "simple-app", description = "Clean Architecture API with User Management and SQLite")
     )
 )]
 struct ApiDoc;
 
+// Registers the bearer scheme that endpoints taking an access token refer to
+struct SecurityAddon;
+
+impl Modify for SecurityAddon {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        if let Some(components) = openapi.components.as_mut() {
+            components.add_security_scheme(
+                "bearer_auth",
+                SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).bearer_format("JWT").build()),
+            );
+        }
+    }
+}
+
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Database setup
@@ -52,24 +115,261 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let user_repository = Arc::new(SqliteUserRepository::new(pool.clone()));
     let transfer_repository = Arc::new(SqliteTransferRepository::new(pool.clone()));
     let point_ledger_repository = Arc::new(SqlitePointLedgerRepository::new(pool.clone()));
+    let idempotency_repository = Arc::new(SqliteIdempotencyRepository::new(pool.clone()));
+    let mandate_repository = Arc::new(SqliteMandateRepository::new(pool.clone()));
+    let batch_repository = Arc::new(SqliteBatchRepository::new(pool.clone()));
+    let transfer_limit_repository = Arc::new(SqliteTransferLimitRepository::new(pool.clone()));
+    let hold_repository = Arc::new(SqliteHoldRepository::new(pool.clone()));
+    let adjustment_repository = Arc::new(SqliteAdjustmentRepository::new(pool.clone()));
+    let tier_repository = Arc::new(SqliteTierRepository::new(pool.clone()));
+    let auth_repository = Arc::new(SqliteAuthRepository::new(pool.clone()));
+    let unit_of_work_factory = Arc::new(SqliteUnitOfWorkFactory::new(pool.clone()));
     
     // Initialize database tables
     user_repository.init_database().await?;
     transfer_repository.init_database().await?;
-    point_ledger_repository.init_database().await?;
+    let unhashed_entries = point_ledger_repository.init_database().await?;
+    if unhashed_entries > 0 {
+        println!("⚠️  {} point_ledger entries have no hash; run `simple-app verify-ledger`", unhashed_entries);
+    }
+    idempotency_repository.init_database().await?;
+    mandate_repository.init_database().await?;
+    batch_repository.init_database().await?;
+    transfer_limit_repository.init_database().await?;
+    hold_repository.init_database().await?;
+    adjustment_repository.init_database().await?;
+    tier_repository.init_database().await?;
+    auth_repository.init_database().await?;
+
+    let reconciliation_service = ReconciliationService::new(
+        user_repository.clone(),
+        transfer_repository.clone(),
+        point_ledger_repository.clone(),
+        Arc::new(SystemClock),
+    );
+
+    // `simple-app reconcile` prints the report as JSON and exits 0 when clean, 1 on discrepancies, 2 on error
+    if std::env::args().nth(1).as_deref() == Some("reconcile") {
+        let code = match reconciliation_service.reconcile().await {
+            Ok(report) => {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+                if report.clean { 0 } else { 1 }
+            }
+            Err(e) => {
+                eprintln!("Reconciliation failed: {}", e);
+                2
+            }
+        };
+        std::process::exit(code);
+    }
+
+    // `simple-app verify-ledger` prints the hash chain report as JSON and exits 0 when intact, 1 on a broken link, 2 on error
+    if std::env::args().nth(1).as_deref() == Some("verify-ledger") {
+        let code = match reconciliation_service.verify_chain().await {
+            Ok(report) => {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+                if report.intact { 0 } else { 1 }
+            }
+            Err(e) => {
+                eprintln!("Ledger verification failed: {}", e);
+                2
+            }
+        };
+        std::process::exit(code);
+    }
+
+    // `simple-app set-role <userId> <Member|Staff|Admin>` grants or takes away back-office access and exits
+    if std::env::args().nth(1).as_deref() == Some("set-role") {
+        let args: Vec<String> = std::env::args().skip(2).collect();
+        let parsed = match args.as_slice() {
+            [user_id, role] => user_id.parse::<u32>()
+                .map_err(|_| format!("Invalid user id: {}", user_id))
+                .and_then(|user_id| role.parse::<UserRole>().map(|role| (user_id, role))),
+            _ => Err("Usage: simple-app set-role <userId> <Member|Staff|Admin>".to_string()),
+        };
+        let code = match parsed {
+            Ok((user_id, role)) => match user_repository.set_role(user_id, role).await {
+                Ok(user) => {
+                    println!("{} <{}> is now {}", user.id, user.email, user.role);
+                    0
+                }
+                Err(e) => {
+                    eprintln!("Setting the role failed: {}", e);
+                    2
+                }
+            },
+            Err(e) => {
+                eprintln!("{}", e);
+                2
+            }
+        };
+        std::process::exit(code);
+    }
     
+    // Idempotency-Key retention window in hours (default: 24)
+    let idempotency_retention_hours = std::env::var("IDEMPOTENCY_KEY_RETENTION_HOURS")
+        .ok()
+        .and_then(|hours| hours.parse::<i64>().ok())
+        .unwrap_or(24);
+
+    // TRANSFER_PROCESSING_MODE=async returns 202 and lets the background worker post the ledger;
+    // the worker always runs so scheduled (executeAt) transfers are executed when due
+    let async_transfers = std::env::var("TRANSFER_PROCESSING_MODE")
+        .map(|mode| mode.eq_ignore_ascii_case("async"))
+        .unwrap_or(false);
+
+    // RECIPIENT_ACCEPTANCE_TIMEOUT_HOURS turns on recipient acceptance: a transfer to someone the sender has
+    // never paid stays pending until they accept it, and is cancelled after this many hours
+    let acceptance_timeout_hours = std::env::var("RECIPIENT_ACCEPTANCE_TIMEOUT_HOURS")
+        .ok()
+        .and_then(|hours| hours.parse::<i64>().ok())
+        .filter(|hours| *hours > 0);
+
+    // ADJUSTMENT_APPROVERS is a comma-separated list of staff emails allowed to approve or reject balance
+    // adjustments; when unset nobody can, and adjustments stay pending
+    let adjustment_approvers: Vec<String> = std::env::var("ADJUSTMENT_APPROVERS")
+        .map(|approvers| approvers.split(',').map(|a| a.trim().to_string()).filter(|a| !a.is_empty()).collect())
+        .unwrap_or_default();
+    if adjustment_approvers.is_empty() {
+        println!("⚠️  ADJUSTMENT_APPROVERS is not set; balance adjustments cannot be approved or rejected");
+    }
+
+    // RECONCILIATION_INTERVAL_HOURS runs the reconciler in the background and logs any discrepancies
+    let reconciliation_interval_hours = std::env::var("RECONCILIATION_INTERVAL_HOURS")
+        .ok()
+        .and_then(|hours| hours.parse::<u64>().ok())
+        .filter(|hours| *hours > 0);
+
+    // POINTS_EXPIRY_MONTHS is how long earned points last before the daily job expires them (default: 24, 0 turns expiry off)
+    let points_expiry_months = std::env::var("POINTS_EXPIRY_MONTHS")
+        .ok()
+        .and_then(|months| months.parse::<u32>().ok())
+        .unwrap_or(24);
+
+    // JWT_SECRET signs access tokens; without it a random one is used and every token dies with the process
+    let jwt_secret = std::env::var("JWT_SECRET")
+        .ok()
+        .filter(|secret| !secret.is_empty())
+        .unwrap_or_else(|| {
+            println!("⚠️  JWT_SECRET is not set; using a random secret, access tokens will not survive a restart");
+            random_token()
+        });
+
+    // ACCESS_TOKEN_TTL_MINUTES (default: 15) and REFRESH_TOKEN_TTL_DAYS (default: 30) set how long tokens last
+    let access_token_ttl_minutes = std::env::var("ACCESS_TOKEN_TTL_MINUTES")
+        .ok()
+        .and_then(|minutes| minutes.parse::<i64>().ok())
+        .filter(|minutes| *minutes > 0)
+        .unwrap_or(15);
+    let refresh_token_ttl_days = std::env::var("REFRESH_TOKEN_TTL_DAYS")
+        .ok()
+        .and_then(|days| days.parse::<i64>().ok())
+        .filter(|days| *days > 0)
+        .unwrap_or(30);
+
     // Application layer - Services
+    let clock = Arc::new(SystemClock);
     let user_service = UserService::new(user_repository.clone());
+    let auth_service = AuthService::new(
+        user_repository.clone(),
+        auth_repository,
+        Arc::new(ConsoleOtpSender),
+        clock.clone(),
+        jwt_secret.as_bytes(),
+    )
+    .with_access_token_ttl(chrono::Duration::minutes(access_token_ttl_minutes))
+    .with_refresh_token_ttl(chrono::Duration::days(refresh_token_ttl_days));
+    let ledger_service = LedgerService::new(
+        point_ledger_repository.clone(),
+        transfer_repository.clone(),
+        user_repository.clone(),
+    );
     let transfer_service = TransferService::new(
         transfer_repository,
+        user_repository.clone(),
+        transfer_limit_repository.clone(),
+        unit_of_work_factory.clone(),
+        clock.clone(),
+    )
+    .with_idempotency_retention(chrono::Duration::hours(idempotency_retention_hours))
+    .with_async_processing(async_transfers)
+    .with_recipient_acceptance(acceptance_timeout_hours.map(chrono::Duration::hours));
+
+    let hold_service = HoldService::new(
+        hold_repository,
+        point_ledger_repository.clone(),
+        user_repository.clone(),
+        unit_of_work_factory.clone(),
+        transfer_service.account_locks(),
+        clock.clone(),
+    );
+    let points_service = PointsService::new(
+        user_repository.clone(),
+        unit_of_work_factory.clone(),
+        transfer_service.account_locks(),
+    );
+    let expiry_service = PointsExpiryService::new(
+        point_ledger_repository.clone(),
+        user_repository.clone(),
+        unit_of_work_factory.clone(),
+        transfer_service.account_locks(),
+        clock.clone(),
+    )
+    .with_lifetime_months(points_expiry_months);
+    let tier_service = TierService::new(
+        user_repository.clone(),
         point_ledger_repository,
+        transfer_limit_repository,
+        tier_repository,
+        unit_of_work_factory.clone(),
+        clock.clone(),
+    );
+    let adjustment_service = AdjustmentService::new(
+        adjustment_repository,
+        user_repository.clone(),
+        unit_of_work_factory,
+        transfer_service.account_locks(),
+        clock.clone(),
+    )
+    .with_approvers(adjustment_approvers);
+    let mandate_service = MandateService::new(
+        mandate_repository,
         user_repository,
+        transfer_service.clone(),
+        clock.clone(),
+    );
+    let batch_service = BatchService::new(
+        batch_repository,
+        transfer_service.clone(),
+        clock,
     );
+
+    TransferWorker::new(transfer_service.clone()).spawn();
+    MandateWorker::new(mandate_service.clone()).spawn();
+    BatchWorker::new(batch_service.clone()).spawn();
+    HoldWorker::new(hold_service.clone()).spawn();
+    TierWorker::new(tier_service.clone()).spawn();
+    if points_expiry_months > 0 {
+        ExpiryWorker::new(expiry_service.clone()).spawn();
+    }
+    if let Some(hours) = reconciliation_interval_hours {
+        ReconciliationWorker::new(reconciliation_service.clone(), std::time::Duration::from_secs(hours * 3600)).spawn();
+    }
     
     // Application state
     let app_state = AppState { 
         user_service,
         transfer_service,
+        mandate_service,
+        batch_service,
+        hold_service,
+        ledger_service,
+        points_service,
+        adjustment_service,
+        reconciliation_service,
+        expiry_service,
+        tier_service,
+        auth_service,
     };
     
     // Presentation layer - Routes
@@ -82,20 +382,92 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("💾 SQLite database: users.db");
     println!("🔗 API Endpoints:");
     println!("   GET    /");
+    println!("   POST   /auth/register");
+    println!("   POST   /auth/login");
+    println!("   POST   /auth/otp");
+    println!("   POST   /auth/refresh");
+    println!("   POST   /auth/logout");
+    println!("   GET    /auth/me");
     println!("   GET    /users?limit=10&offset=0");
     println!("   POST   /users");
     println!("   GET    /users/{{id}}");
     println!("   PUT    /users/{{id}}");
     println!("   DELETE /users/{{id}}");
+    println!("   GET    /users/{{id}}/balance?at=2024-01-01");
+    println!("   GET    /users/{{id}}/balance/series?from=&to=&interval=day");
+    println!("   GET    /users/{{id}}/ledger?eventType=earn,redeem&from=&to=&minAmount=&maxAmount=&cursor=&page=1&pageSize=20");
+    println!("   POST   /users/{{id}}/points/earn");
+    println!("   POST   /users/{{id}}/points/redeem");
+    println!("   GET    /users/{{id}}/points/expiring?withinDays=90");
+    println!("   GET    /users/{{id}}/tier");
+    println!("   PUT    /users/{{id}}/tier");
+    println!("   GET    /users/{{id}}/tier/history");
+    println!("   GET    /tiers");
     println!("   POST   /transfers");
+    println!("   POST   /transfers/split");
     println!("   GET    /transfers?userId={{userId}}&page=1&pageSize=20");
     println!("   GET    /transfers/{{id}}");
-    println!("");
+    println!("   POST   /transfers/{{id}}/reverse");
+    println!("   POST   /transfers/{{id}}/cancel");
+    println!("   POST   /transfers/{{id}}/accept");
+    println!("   POST   /transfers/{{id}}/decline");
+    println!("   POST   /transfers/batch?mode=allOrNothing|bestEffort");
+    println!("   GET    /transfers/batches/{{id}}");
+    println!("   GET    /transfer-limits");
+    println!("   GET    /transfer-limits/{{level}}");
+    println!("   PUT    /transfer-limits/{{level}}");
+    println!("   POST   /holds");
+    println!("   GET    /holds?userId={{userId}}");
+    println!("   GET    /holds/{{id}}");
+    println!("   POST   /holds/{{id}}/capture");
+    println!("   POST   /holds/{{id}}/release");
+    println!("   GET    /ledger/{{id}}");
+    println!("   POST   /adjustments");
+    println!("   GET    /adjustments?status=pending&userId={{userId}}");
+    println!("   GET    /adjustments/{{id}}");
+    println!("   POST   /adjustments/{{id}}/approve");
+    println!("   POST   /adjustments/{{id}}/reject");
+    println!("   GET    /admin/reconciliation");
+    println!("   GET    /admin/ledger/verify");
+    println!("   POST   /admin/tiers/evaluate");
+    println!("   POST   /mandates");
+    println!("   GET    /mandates?userId={{userId}}");
+    println!("   GET    /mandates/{{id}}");
+    println!("   PUT    /mandates/{{id}}");
+    println!("   DELETE /mandates/{{id}}");
+    println!("   POST   /mandates/{{id}}/pause");
+    println!("   POST   /mandates/{{id}}/resume");
+    println!();
     println!("📊 Transfer API Features:");
     println!("   - Point transfer between users");
+    println!("   - Email/phone login with a password or one-time code; POST /transfers sends from the bearer token's user");
     println!("   - Idempotency key for duplicate protection");
-    println!("   - Point ledger for audit trail");
+    println!("   - Point ledger for audit trail, filterable with totals per query, in a gap-free per-user sequence");
     println!("   - Automatic balance management");
+    println!("   - Scheduled transfers via executeAt");
+    println!("   - Recurring transfer mandates (daily, weekly, monthly)");
+    println!("   - Split transfers to several recipients with one debit");
+    println!("   - Batch transfers from JSON or CSV, all-or-nothing or best-effort");
+    println!("   - Earn and redeem points with a reason, source reference and metadata");
+    println!("   - Balance adjustments with maker-checker approval and an audit trail");
+    println!("   - Point holds (authorize/capture) with available vs ledger balance");
+    println!("   - Point-in-time balances and day/week/month balance series from the ledger");
+    if points_expiry_months > 0 {
+        println!("   - Points expire {} months after they were earned, oldest spent first", points_expiry_months);
+    }
+    println!("   - Membership tiers earned on points over {} months, evaluated nightly, with earn multipliers and a tier history", TIER_QUALIFICATION_MONTHS);
+    println!("   - Per-membership-level limits: min/max amount, 24h cap, transfers per hour");
+    println!("   - Ledger reconciliation report (also `simple-app reconcile`, exit code 1 on discrepancies)");
+    println!("   - Append-only, hash-chained ledger (verify with `simple-app verify-ledger`)");
+    if let Some(hours) = reconciliation_interval_hours {
+        println!("   - Background reconciliation every {}h", hours);
+    }
+    if async_transfers {
+        println!("   - Async processing: POST /transfers returns 202, background worker completes it");
+    }
+    if let Some(hours) = acceptance_timeout_hours {
+        println!("   - Recipient acceptance: first transfers to a new recipient wait up to {}h for /accept", hours);
+    }
 
     // run our app with hyper, listening globally on port 3000
     let listener = tokio::net::TcpListener::bind("0.0.0.0:3000").await?;
@@ -0,0 +1,198 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use serde::Deserialize;
+use crate::domain::{AdjustmentStatus, CreateAdjustmentRequest, ReviewAdjustmentRequest, AdjustmentResponse, AdjustmentListResponse};
+use crate::presentation::{AppState, StaffUser, ErrorResponse};
+
+#[derive(Deserialize)]
+pub struct ListAdjustmentsQuery {
+    pub status: Option<AdjustmentStatus>,
+    #[serde(rename = "userId")]
+    pub user_id: Option<u32>,
+}
+
+fn adjustment_error(e: String) -> (StatusCode, Json<ErrorResponse>) {
+    let (status, error) = if e.contains("Adjustment not found") {
+        (StatusCode::NOT_FOUND, "ADJUSTMENT_NOT_FOUND")
+    } else if e.contains("User not found") {
+        (StatusCode::BAD_REQUEST, "USER_NOT_FOUND")
+    } else if e.contains("cannot review their own") {
+        (StatusCode::FORBIDDEN, "SEPARATION_OF_DUTIES")
+    } else if e.contains("not allowed to review") || e.contains("until approvers are configured") {
+        (StatusCode::FORBIDDEN, "NOT_AN_APPROVER")
+    } else if e.contains("Only pending adjustments") {
+        (StatusCode::CONFLICT, "INVALID_ADJUSTMENT_STATE")
+    } else if e.contains("Insufficient points") {
+        (StatusCode::CONFLICT, "INSUFFICIENT_POINTS")
+    } else {
+        (StatusCode::BAD_REQUEST, "VALIDATION_ERROR")
+    };
+
+    (
+        status,
+        Json(ErrorResponse {
+            error: error.to_string(),
+            message: e,
+        }),
+    )
+}
+
+/// Propose a balance adjustment for another staff member to review
+#[utoipa::path(
+    post,
+    path = "/adjustments",
+    request_body = CreateAdjustmentRequest,
+    responses(
+        (status = 201, description = "Adjustment requested; nothing is posted until it is approved", body = AdjustmentResponse),
+        (status = 400, description = "Bad request", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired access token", body = ErrorResponse),
+        (status = 403, description = "Only staff can request adjustments", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Adjustments"
+)]
+pub async fn create_adjustment(
+    State(state): State<AppState>,
+    StaffUser(staff): StaffUser,
+    Json(request): Json<CreateAdjustmentRequest>,
+) -> Result<(StatusCode, Json<AdjustmentResponse>), (StatusCode, Json<ErrorResponse>)> {
+    match state.adjustment_service.request_adjustment(request, &staff.email).await {
+        Ok(response) => Ok((StatusCode::CREATED, Json(response))),
+        Err(e) => Err(adjustment_error(e)),
+    }
+}
+
+/// List adjustments, newest first
+#[utoipa::path(
+    get,
+    path = "/adjustments",
+    params(
+        ("status" = Option<AdjustmentStatus>, Query, description = "Only adjustments in this status"),
+        ("userId" = Option<u32>, Query, description = "Only adjustments to this user's balance")
+    ),
+    responses(
+        (status = 200, description = "Adjustments found", body = AdjustmentListResponse),
+        (status = 401, description = "Missing, invalid or expired access token", body = ErrorResponse),
+        (status = 403, description = "Only staff can see adjustments", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Adjustments"
+)]
+pub async fn list_adjustments(
+    State(state): State<AppState>,
+    StaffUser(_staff): StaffUser,
+    Query(params): Query<ListAdjustmentsQuery>,
+) -> Result<Json<AdjustmentListResponse>, (StatusCode, Json<ErrorResponse>)> {
+    match state.adjustment_service.list_adjustments(params.status, params.user_id).await {
+        Ok(response) => Ok(Json(response)),
+        Err(e) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: "INTERNAL_ERROR".to_string(),
+                message: e,
+            }),
+        )),
+    }
+}
+
+/// Get an adjustment with its audit trail
+#[utoipa::path(
+    get,
+    path = "/adjustments/{id}",
+    params(
+        ("id" = u32, Path, description = "Adjustment ID")
+    ),
+    responses(
+        (status = 200, description = "Adjustment found", body = AdjustmentResponse),
+        (status = 401, description = "Missing, invalid or expired access token", body = ErrorResponse),
+        (status = 403, description = "Only staff can see adjustments", body = ErrorResponse),
+        (status = 404, description = "Adjustment not found", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Adjustments"
+)]
+pub async fn get_adjustment(
+    State(state): State<AppState>,
+    StaffUser(_staff): StaffUser,
+    Path(id): Path<u32>,
+) -> Result<Json<AdjustmentResponse>, (StatusCode, Json<ErrorResponse>)> {
+    match state.adjustment_service.get_adjustment(id).await {
+        Ok(response) => Ok(Json(response)),
+        Err(e) => Err(adjustment_error(e)),
+    }
+}
+
+/// Approve a pending adjustment and post it to the ledger
+#[utoipa::path(
+    post,
+    path = "/adjustments/{id}/approve",
+    params(
+        ("id" = u32, Path, description = "Adjustment ID")
+    ),
+    request_body = ReviewAdjustmentRequest,
+    responses(
+        (status = 200, description = "Adjustment approved and posted as an adjust ledger entry", body = AdjustmentResponse),
+        (status = 400, description = "Bad request", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired access token", body = ErrorResponse),
+        (status = 403, description = "Not staff, or the reviewer is the requester or not in ADJUSTMENT_APPROVERS", body = ErrorResponse),
+        (status = 404, description = "Adjustment not found", body = ErrorResponse),
+        (status = 409, description = "Adjustment is no longer pending, or the debit exceeds the available balance", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Adjustments"
+)]
+pub async fn approve_adjustment(
+    State(state): State<AppState>,
+    StaffUser(staff): StaffUser,
+    Path(id): Path<u32>,
+    Json(request): Json<ReviewAdjustmentRequest>,
+) -> Result<Json<AdjustmentResponse>, (StatusCode, Json<ErrorResponse>)> {
+    match state.adjustment_service.approve_adjustment(id, &staff.email, request).await {
+        Ok(response) => Ok(Json(response)),
+        Err(e) => Err(adjustment_error(e)),
+    }
+}
+
+/// Reject a pending adjustment
+#[utoipa::path(
+    post,
+    path = "/adjustments/{id}/reject",
+    params(
+        ("id" = u32, Path, description = "Adjustment ID")
+    ),
+    request_body = ReviewAdjustmentRequest,
+    responses(
+        (status = 200, description = "Adjustment rejected", body = AdjustmentResponse),
+        (status = 400, description = "Bad request", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired access token", body = ErrorResponse),
+        (status = 403, description = "Not staff, or the reviewer is the requester or not in ADJUSTMENT_APPROVERS", body = ErrorResponse),
+        (status = 404, description = "Adjustment not found", body = ErrorResponse),
+        (status = 409, description = "Adjustment is no longer pending", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Adjustments"
+)]
+pub async fn reject_adjustment(
+    State(state): State<AppState>,
+    StaffUser(staff): StaffUser,
+    Path(id): Path<u32>,
+    Json(request): Json<ReviewAdjustmentRequest>,
+) -> Result<Json<AdjustmentResponse>, (StatusCode, Json<ErrorResponse>)> {
+    match state.adjustment_service.reject_adjustment(id, &staff.email, request).await {
+        Ok(response) => Ok(Json(response)),
+        Err(e) => Err(adjustment_error(e)),
+    }
+}
\ No newline at end of file
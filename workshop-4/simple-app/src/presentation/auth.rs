@@ -0,0 +1,260 @@
+use axum::{
+    extract::FromRequestParts,
+    http::{header::AUTHORIZATION, request::Parts, StatusCode},
+    response::Json,
+};
+use crate::domain::User;
+use crate::presentation::{AppState, ErrorResponse};
+
+// The user named by the request's `Authorization: Bearer <access token>` header. Handlers that take it
+// answer 401 when the header is missing or the token is invalid or expired.
+#[derive(Debug, Clone)]
+pub struct AuthenticatedUser(pub User);
+
+impl FromRequestParts<AppState> for AuthenticatedUser {
+    type Rejection = (StatusCode, Json<ErrorResponse>);
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self, Self::Rejection> {
+        let token = parts.headers.get(AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .map(str::trim)
+            .filter(|token| !token.is_empty())
+            .ok_or_else(|| unauthorized("Missing bearer access token".to_string()))?;
+
+        match state.auth_service.authenticate(token).await {
+            Ok(user) => Ok(AuthenticatedUser(user)),
+            Err(e) if e.contains("Invalid access token") => Err(unauthorized(e)),
+            Err(e) => Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: "INTERNAL_ERROR".to_string(),
+                    message: e,
+                }),
+            )),
+        }
+    }
+}
+
+// An authenticated user with the Staff or Admin role; anyone else signed in gets 403
+#[derive(Debug, Clone)]
+pub struct StaffUser(pub User);
+
+impl FromRequestParts<AppState> for StaffUser {
+    type Rejection = (StatusCode, Json<ErrorResponse>);
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self, Self::Rejection> {
+        let AuthenticatedUser(user) = AuthenticatedUser::from_request_parts(parts, state).await?;
+        if !user.role.is_staff() {
+            return Err(forbidden("Only staff can do this".to_string()));
+        }
+
+        Ok(StaffUser(user))
+    }
+}
+
+// Requests act as the signed-in user: an id the client sent is accepted when it names them, and 0 (left out)
+// means them. Points only move out of the signed-in user's account this way, and only they can change it.
+pub fn acting_user_id(user: &User, claimed_id: u32, field: &str) -> Result<u32, (StatusCode, Json<ErrorResponse>)> {
+    if claimed_id != 0 && claimed_id != user.id {
+        return Err(forbidden(format!("{} must be the signed-in user", field)));
+    }
+
+    Ok(user.id)
+}
+
+// Reads of a user's account: the user themselves through acting_user_id, or any account for staff
+pub fn viewable_user_id(user: &User, claimed_id: u32, field: &str) -> Result<u32, (StatusCode, Json<ErrorResponse>)> {
+    if user.role.is_staff() && claimed_id != 0 {
+        return Ok(claimed_id);
+    }
+
+    acting_user_id(user, claimed_id, field)
+}
+
+// Reads of a record that belongs to some accounts (a transfer's sender and recipient, a hold's owner, ...):
+// any of them can see it, and so can staff
+pub fn ensure_viewable(user: &User, owner_ids: &[u32], what: &str) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    if user.role.is_staff() || owner_ids.contains(&user.id) {
+        return Ok(());
+    }
+
+    Err(forbidden(format!("{} belongs to another user", what)))
+}
+
+fn unauthorized(message: String) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        StatusCode::UNAUTHORIZED,
+        Json(ErrorResponse {
+            error: "UNAUTHORIZED".to_string(),
+            message,
+        }),
+    )
+}
+
+fn forbidden(message: String) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        StatusCode::FORBIDDEN,
+        Json(ErrorResponse {
+            error: "FORBIDDEN".to_string(),
+            message,
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use serde_json::json;
+    use crate::domain::{SystemClock, UserRepository, UserRole};
+    use crate::infrastructure::SqliteUserRepository;
+    use crate::infrastructure::test_support::test_pool;
+    use crate::presentation::test_support::{test_app, bearer, send};
+
+    #[tokio::test]
+    async fn points_only_move_out_of_the_signed_in_users_account() {
+        let pool = test_pool().await;
+        let app = test_app(&pool, Arc::new(SystemClock));
+
+        let (status, _) = send(&app, "POST", "/holds", None, Some(json!({ "userId": 1, "amount": 10 }))).await;
+        assert_eq!(status, 401);
+        let (status, _) = send(&app, "POST", "/holds", Some(&bearer(2)), Some(json!({ "userId": 1, "amount": 10 }))).await;
+        assert_eq!(status, 403);
+        let (status, _) = send(&app, "POST", "/users/1/points/redeem", Some(&bearer(2)), Some(json!({ "amount": 10, "reason": "coffee" }))).await;
+        assert_eq!(status, 403);
+
+        let (status, hold) = send(&app, "POST", "/holds", Some(&bearer(1)), Some(json!({ "amount": 10 }))).await;
+        assert_eq!(status, 201);
+        assert_eq!(hold["userId"], 1);
+        // Capturing and releasing is the merchant's side of the checkout, not the member's
+        let capture = format!("/holds/{}/capture", hold["holdId"].as_str().unwrap());
+        let release = format!("/holds/{}/release", hold["holdId"].as_str().unwrap());
+        for uri in [&capture, &release] {
+            let (status, _) = send(&app, "POST", uri, Some(&bearer(1)), Some(json!({}))).await;
+            assert_eq!(status, 403, "{}", uri);
+        }
+        SqliteUserRepository::new(pool.clone()).set_role(3, UserRole::Staff).await.unwrap();
+        let (status, body) = send(&app, "POST", &capture, Some(&bearer(3)), Some(json!({ "amount": 4 }))).await;
+        assert_eq!(status, 200, "{}", body);
+        assert_eq!(body["capturedAmount"], 4);
+        let (status, _) = send(&app, "POST", &release, Some(&bearer(3)), None).await;
+        assert_eq!(status, 409);
+    }
+
+    #[tokio::test]
+    async fn back_office_routes_are_staff_only() {
+        let pool = test_pool().await;
+        let app = test_app(&pool, Arc::new(SystemClock));
+        let earn = json!({ "amount": 100, "reason": "goodwill" });
+
+        let (status, _) = send(&app, "POST", "/users/2/points/earn", None, Some(earn.clone())).await;
+        assert_eq!(status, 401);
+        let (status, _) = send(&app, "POST", "/users/2/points/earn", Some(&bearer(1)), Some(earn.clone())).await;
+        assert_eq!(status, 403);
+        let (status, _) = send(&app, "GET", "/admin/reconciliation", Some(&bearer(1)), None).await;
+        assert_eq!(status, 403);
+
+        let users = SqliteUserRepository::new(pool.clone());
+        users.set_role(1, UserRole::Staff).await.unwrap();
+        let (status, _) = send(&app, "GET", "/admin/reconciliation", Some(&bearer(1)), None).await;
+        assert_eq!(status, 200);
+        // A credit from plain staff has to go through an adjustment someone else approves
+        let (status, _) = send(&app, "POST", "/users/2/points/earn", Some(&bearer(1)), Some(earn.clone())).await;
+        assert_eq!(status, 403);
+
+        users.set_role(1, UserRole::Admin).await.unwrap();
+        let (status, body) = send(&app, "POST", "/users/2/points/earn", Some(&bearer(1)), Some(earn)).await;
+        assert_eq!(status, 201);
+        let metadata: serde_json::Value = serde_json::from_str(body["entry"]["metadata"].as_str().unwrap()).unwrap();
+        assert_eq!(metadata["credited_by"], "john.doe@example.com");
+    }
+
+    #[tokio::test]
+    async fn a_user_can_only_change_their_own_account() {
+        let pool = test_pool().await;
+        let app = test_app(&pool, Arc::new(SystemClock));
+
+        // Pointing someone else's account at your own email and phone is how their login codes get stolen
+        let takeover = json!({ "email": "bob.johnson@example.com", "phone": "+66856789012" });
+        let (status, _) = send(&app, "PUT", "/users/1", Some(&bearer(3)), Some(takeover)).await;
+        assert_eq!(status, 403);
+        let (status, _) = send(&app, "DELETE", "/users/1", Some(&bearer(3)), None).await;
+        assert_eq!(status, 403);
+
+        let (status, body) = send(&app, "PUT", "/users/3", Some(&bearer(3)), Some(json!({ "phone": "+66812345678" }))).await;
+        assert_eq!(status, 400);
+        assert_eq!(body["message"], "Phone already exists");
+        let (status, _) = send(&app, "PUT", "/users/3", Some(&bearer(3)), Some(json!({ "phone": "+66800000003" }))).await;
+        assert_eq!(status, 200);
+
+        let new_user = json!({ "first_name": "Eve", "last_name": "Doe", "phone": "+66812345678", "email": "eve@example.com" });
+        let (status, _) = send(&app, "POST", "/users", Some(&bearer(3)), Some(new_user.clone())).await;
+        assert_eq!(status, 403);
+        SqliteUserRepository::new(pool.clone()).set_role(1, UserRole::Staff).await.unwrap();
+        let (status, body) = send(&app, "POST", "/users", Some(&bearer(1)), Some(new_user)).await;
+        assert_eq!(status, 400);
+        assert_eq!(body["message"], "Phone already exists");
+    }
+
+    #[tokio::test]
+    async fn new_users_start_at_bronze_and_only_staff_move_them() {
+        let pool = test_pool().await;
+        let app = test_app(&pool, Arc::new(SystemClock));
+
+        SqliteUserRepository::new(pool.clone()).set_role(1, UserRole::Staff).await.unwrap();
+        let new_user = json!({ "first_name": "Eve", "last_name": "Doe", "phone": "+66800000004", "email": "eve@example.com", "membership_level": "Platinum" });
+        let (status, _) = send(&app, "POST", "/users", None, Some(new_user.clone())).await;
+        assert_eq!(status, 401);
+        let (status, user) = send(&app, "POST", "/users", Some(&bearer(1)), Some(new_user)).await;
+        assert_eq!(status, 201);
+        assert_eq!(user["membership_level"], "Bronze");
+
+        let tier_uri = format!("/users/{}/tier", user["id"]);
+        let promotion = json!({ "tier": "Platinum", "note": "VIP" });
+        let (status, _) = send(&app, "PUT", &tier_uri, Some(&bearer(user["id"].as_u64().unwrap() as u32)), Some(promotion.clone())).await;
+        assert_eq!(status, 403);
+
+        let (status, body) = send(&app, "PUT", &tier_uri, Some(&bearer(1)), Some(promotion)).await;
+        assert_eq!(status, 200, "{}", body);
+        assert_eq!(body["toTier"], "Platinum");
+        assert_eq!(body["changedBy"], "john.doe@example.com");
+    }
+
+    #[tokio::test]
+    async fn members_only_read_their_own_accounts() {
+        let pool = test_pool().await;
+        let app = test_app(&pool, Arc::new(SystemClock));
+
+        let (status, body) = send(&app, "POST", "/transfers", Some(&bearer(1)), Some(json!({ "toUserId": 2, "amount": 100 }))).await;
+        assert_eq!(status, 201);
+        let transfer_uri = format!("/transfers/{}", body["transfer"]["idemKey"].as_str().unwrap());
+
+        let own_reads = ["/users/1", "/users/1/balance", "/users/1/balance/series?from=2024-01-01&to=2024-01-02", "/users/1/ledger", "/users/1/points/expiring",
+            "/users/1/tier", "/users/1/tier/history", "/transfers?userId=1", "/holds?userId=1", "/mandates?userId=1"];
+        for uri in own_reads {
+            let (status, _) = send(&app, "GET", uri, None, None).await;
+            assert_eq!(status, 401, "{}", uri);
+            let (status, _) = send(&app, "GET", uri, Some(&bearer(3)), None).await;
+            assert_eq!(status, 403, "{}", uri);
+            let (status, body) = send(&app, "GET", uri, Some(&bearer(1)), None).await;
+            assert_eq!(status, 200, "{} {}", uri, body);
+        }
+
+        // Both ends of a transfer can look it up, nobody else can
+        let (status, _) = send(&app, "GET", &transfer_uri, Some(&bearer(2)), None).await;
+        assert_eq!(status, 200);
+        let (status, _) = send(&app, "GET", &transfer_uri, Some(&bearer(3)), None).await;
+        assert_eq!(status, 403);
+
+        for uri in ["/users", "/adjustments"] {
+            let (status, _) = send(&app, "GET", uri, Some(&bearer(1)), None).await;
+            assert_eq!(status, 403, "{}", uri);
+        }
+
+        SqliteUserRepository::new(pool.clone()).set_role(3, UserRole::Staff).await.unwrap();
+        for uri in own_reads.iter().copied().chain([transfer_uri.as_str(), "/users", "/adjustments"]) {
+            let (status, body) = send(&app, "GET", uri, Some(&bearer(3)), None).await;
+            assert_eq!(status, 200, "{} {}", uri, body);
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,152 @@
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::Json,
+};
+use crate::domain::{User, RegisterRequest, LoginRequest, OtpRequest, OtpRequestResponse, RefreshRequest, TokenResponse};
+use crate::presentation::{AppState, AuthenticatedUser, ErrorResponse};
+
+fn auth_error(e: String) -> (StatusCode, Json<ErrorResponse>) {
+    let (status, error) = if e.contains("Invalid credentials") || e.contains("Invalid refresh token") {
+        (StatusCode::UNAUTHORIZED, "UNAUTHORIZED")
+    } else if e.contains("already exists") {
+        (StatusCode::CONFLICT, "ALREADY_EXISTS")
+    } else if e.contains("Database error") || e.contains("Failed to") {
+        (StatusCode::INTERNAL_SERVER_ERROR, "INTERNAL_ERROR")
+    } else {
+        (StatusCode::BAD_REQUEST, "VALIDATION_ERROR")
+    };
+
+    (
+        status,
+        Json(ErrorResponse {
+            error: error.to_string(),
+            message: e,
+        }),
+    )
+}
+
+/// Create an account with a password and sign straight in
+#[utoipa::path(
+    post,
+    path = "/auth/register",
+    request_body = RegisterRequest,
+    responses(
+        (status = 201, description = "Account created and tokens issued", body = TokenResponse),
+        (status = 400, description = "Bad request", body = ErrorResponse),
+        (status = 409, description = "Email or phone already in use", body = ErrorResponse)
+    ),
+    tag = "Auth"
+)]
+pub async fn register(
+    State(state): State<AppState>,
+    Json(payload): Json<RegisterRequest>,
+) -> Result<(StatusCode, Json<TokenResponse>), (StatusCode, Json<ErrorResponse>)> {
+    match state.auth_service.register(payload).await {
+        Ok(tokens) => Ok((StatusCode::CREATED, Json(tokens))),
+        Err(e) => Err(auth_error(e)),
+    }
+}
+
+/// Sign in with an email address or phone number and either the password or a one-time code
+#[utoipa::path(
+    post,
+    path = "/auth/login",
+    request_body = LoginRequest,
+    responses(
+        (status = 200, description = "Tokens issued", body = TokenResponse),
+        (status = 400, description = "Bad request", body = ErrorResponse),
+        (status = 401, description = "Invalid credentials", body = ErrorResponse)
+    ),
+    tag = "Auth"
+)]
+pub async fn login(
+    State(state): State<AppState>,
+    Json(payload): Json<LoginRequest>,
+) -> Result<Json<TokenResponse>, (StatusCode, Json<ErrorResponse>)> {
+    match state.auth_service.login(payload).await {
+        Ok(tokens) => Ok(Json(tokens)),
+        Err(e) => Err(auth_error(e)),
+    }
+}
+
+/// Send a one-time login code to an email address or phone number
+#[utoipa::path(
+    post,
+    path = "/auth/otp",
+    request_body = OtpRequest,
+    responses(
+        (status = 202, description = "A code was sent if the identifier belongs to a user who has not had too many codes this hour", body = OtpRequestResponse),
+        (status = 400, description = "Bad request", body = ErrorResponse)
+    ),
+    tag = "Auth"
+)]
+pub async fn request_login_otp(
+    State(state): State<AppState>,
+    Json(payload): Json<OtpRequest>,
+) -> Result<(StatusCode, Json<OtpRequestResponse>), (StatusCode, Json<ErrorResponse>)> {
+    match state.auth_service.request_login_otp(payload).await {
+        Ok(response) => Ok((StatusCode::ACCEPTED, Json(response))),
+        Err(e) => Err(auth_error(e)),
+    }
+}
+
+/// Trade a refresh token for a new access token and refresh token
+#[utoipa::path(
+    post,
+    path = "/auth/refresh",
+    request_body = RefreshRequest,
+    responses(
+        (status = 200, description = "Tokens issued", body = TokenResponse),
+        (status = 401, description = "Invalid, expired or already used refresh token", body = ErrorResponse)
+    ),
+    tag = "Auth"
+)]
+pub async fn refresh_token(
+    State(state): State<AppState>,
+    Json(payload): Json<RefreshRequest>,
+) -> Result<Json<TokenResponse>, (StatusCode, Json<ErrorResponse>)> {
+    match state.auth_service.refresh(payload).await {
+        Ok(tokens) => Ok(Json(tokens)),
+        Err(e) => Err(auth_error(e)),
+    }
+}
+
+/// Revoke a refresh token
+#[utoipa::path(
+    post,
+    path = "/auth/logout",
+    request_body = RefreshRequest,
+    responses(
+        (status = 204, description = "Signed out")
+    ),
+    tag = "Auth"
+)]
+pub async fn logout(
+    State(state): State<AppState>,
+    Json(payload): Json<RefreshRequest>,
+) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+    match state.auth_service.logout(payload).await {
+        Ok(()) => Ok(StatusCode::NO_CONTENT),
+        Err(e) => Err(auth_error(e)),
+    }
+}
+
+/// Get the user the access token belongs to
+#[utoipa::path(
+    get,
+    path = "/auth/me",
+    responses(
+        (status = 200, description = "Signed-in user", body = User),
+        (status = 401, description = "Missing, invalid or expired access token", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Auth"
+)]
+pub async fn get_current_user(
+    AuthenticatedUser(user): AuthenticatedUser,
+) -> Json<User> {
+    Json(user)
+}
\ No newline at end of file
@@ -0,0 +1,168 @@
+use axum::{
+    body::Bytes,
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::Json,
+};
+use serde::Deserialize;
+use crate::domain::{TransferBatch, BatchMode, CreateBatchRequest, CreateTransferRequest};
+use crate::presentation::{AppState, AuthenticatedUser, ErrorResponse, acting_user_id, ensure_viewable};
+
+#[derive(Deserialize)]
+pub struct CreateBatchQuery {
+    pub mode: Option<BatchMode>,
+}
+
+fn validation_error(message: String) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(ErrorResponse {
+            error: "VALIDATION_ERROR".to_string(),
+            message,
+        }),
+    )
+}
+
+// CSV upload: a header row with toUserId,amount and optional fromUserId,note,executeAt columns
+fn parse_csv_items(body: &[u8]) -> Result<Vec<CreateTransferRequest>, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(body);
+
+    reader.deserialize()
+        .enumerate()
+        .map(|(row, item)| item.map_err(|e| format!("Invalid CSV row {}: {}", row + 1, e)))
+        .collect()
+}
+
+/// Submit a batch of transfers (JSON or CSV)
+#[utoipa::path(
+    post,
+    path = "/transfers/batch",
+    params(
+        ("Idempotency-Key" = String, Header, description = "Batch key (8-128 chars); a retry with the same key and body returns the original batch"),
+        ("mode" = Option<BatchMode>, Query, description = "allOrNothing (default) or bestEffort; overrides the JSON body, and is the only way to set it for CSV")
+    ),
+    request_body(
+        description = "JSON batch, or a text/csv upload with columns toUserId,amount[,fromUserId,note,executeAt]; every item is sent by the signed-in user",
+        content(
+            (CreateBatchRequest = "application/json"),
+            (String = "text/csv")
+        )
+    ),
+    responses(
+        (status = 200, description = "Idempotency-Key seen before; the original batch is returned", body = TransferBatch),
+        (status = 202, description = "Batch accepted; poll GET /transfers/batches/{id} for per-item results", body = TransferBatch),
+        (status = 400, description = "Bad request", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired access token", body = ErrorResponse),
+        (status = 403, description = "An item's fromUserId names someone other than the signed-in user", body = ErrorResponse),
+        (status = 409, description = "Idempotency-Key reused with a different body", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Transfers"
+)]
+pub async fn create_transfer_batch(
+    State(state): State<AppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Query(params): Query<CreateBatchQuery>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<(StatusCode, Json<TransferBatch>), (StatusCode, Json<ErrorResponse>)> {
+    let idempotency_key = match headers.get("Idempotency-Key").map(|value| value.to_str()) {
+        Some(Ok(key)) => key.to_string(),
+        Some(Err(_)) => return Err(validation_error("Idempotency key must be visible ASCII".to_string())),
+        None => return Err(validation_error("Idempotency-Key header is required for batch transfers".to_string())),
+    };
+
+    let is_csv = headers.get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|content_type| content_type.starts_with("text/csv"));
+
+    let mut request = if is_csv {
+        CreateBatchRequest {
+            mode: BatchMode::default(),
+            items: parse_csv_items(&body).map_err(validation_error)?,
+        }
+    } else {
+        serde_json::from_slice::<CreateBatchRequest>(&body)
+            .map_err(|e| validation_error(format!("Invalid batch body: {}", e)))?
+    };
+    if let Some(mode) = params.mode {
+        request.mode = mode;
+    }
+    for item in &mut request.items {
+        item.from_user_id = acting_user_id(&user, item.from_user_id, "fromUserId")?;
+    }
+
+    match state.batch_service.submit_batch(request, idempotency_key).await {
+        Ok((batch, true)) => Ok((StatusCode::OK, Json(batch))),
+        Ok((batch, false)) => Ok((StatusCode::ACCEPTED, Json(batch))),
+        Err(e) => {
+            if e.contains("different request") {
+                Err((
+                    StatusCode::CONFLICT,
+                    Json(ErrorResponse {
+                        error: "IDEMPOTENCY_KEY_REUSED".to_string(),
+                        message: e,
+                    }),
+                ))
+            } else {
+                Err(validation_error(e))
+            }
+        }
+    }
+}
+
+/// Get a transfer batch with per-item results
+#[utoipa::path(
+    get,
+    path = "/transfers/batches/{id}",
+    params(
+        ("id" = String, Path, description = "Batch ID")
+    ),
+    responses(
+        (status = 200, description = "Batch found", body = TransferBatch),
+        (status = 401, description = "Missing, invalid or expired access token", body = ErrorResponse),
+        (status = 403, description = "The batch was submitted by another user, and the caller is not staff", body = ErrorResponse),
+        (status = 404, description = "Batch not found", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Transfers"
+)]
+pub async fn get_transfer_batch(
+    State(state): State<AppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Path(id): Path<String>,
+) -> Result<Json<TransferBatch>, (StatusCode, Json<ErrorResponse>)> {
+    match state.batch_service.get_batch(&id).await {
+        Ok(batch) => {
+            // Every item is sent from the account of whoever submitted the batch
+            let senders: Vec<u32> = batch.items.iter().map(|item| item.from_user_id).collect();
+            ensure_viewable(&user, &senders, "Batch")?;
+            Ok(Json(batch))
+        }
+        Err(e) => {
+            if e.contains("not found") {
+                Err((
+                    StatusCode::NOT_FOUND,
+                    Json(ErrorResponse {
+                        error: "BATCH_NOT_FOUND".to_string(),
+                        message: e,
+                    }),
+                ))
+            } else {
+                Err((
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(ErrorResponse {
+                        error: "INTERNAL_ERROR".to_string(),
+                        message: e,
+                    }),
+                ))
+            }
+        }
+    }
+}
\ No newline at end of file
@@ -5,13 +5,24 @@ use axum::{
 };
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
-use crate::application::{UserService, TransferService};
+use crate::application::{UserService, TransferService, MandateService, BatchService, HoldService, LedgerService, PointsService, AdjustmentService, ReconciliationService, PointsExpiryService, TierService, AuthService};
 use crate::domain::{User, CreateUserRequest, UpdateUserRequest};
+use crate::presentation::{AuthenticatedUser, StaffUser, acting_user_id, viewable_user_id};
 
 #[derive(Clone)]
 pub struct AppState {
     pub user_service: UserService,
     pub transfer_service: TransferService,
+    pub mandate_service: MandateService,
+    pub batch_service: BatchService,
+    pub hold_service: HoldService,
+    pub ledger_service: LedgerService,
+    pub points_service: PointsService,
+    pub adjustment_service: AdjustmentService,
+    pub reconciliation_service: ReconciliationService,
+    pub expiry_service: PointsExpiryService,
+    pub tier_service: TierService,
+    pub auth_service: AuthService,
 }
 
 #[derive(Deserialize)]
@@ -53,13 +64,21 @@ pub async fn hello_world() -> &'static str {
     ),
     responses(
         (status = 200, description = "User found successfully", body = User),
+        (status = 401, description = "Missing, invalid or expired access token", body = ErrorResponse),
+        (status = 403, description = "The user is not the signed-in user, and the caller is not staff", body = ErrorResponse),
         (status = 404, description = "User not found", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
     )
 )]
 pub async fn get_user(
     State(state): State<AppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
     Path(id): Path<u32>,
 ) -> Result<Json<User>, (StatusCode, Json<ErrorResponse>)> {
+    let id = viewable_user_id(&user, id, "User ID")?;
+
     match state.user_service.get_user(id).await {
         Ok(Some(user)) => Ok(Json(user)),
         Ok(None) => Err((
@@ -88,11 +107,17 @@ pub async fn get_user(
         ("offset" = Option<i64>, Query, description = "Number of users to skip (default: 0)")
     ),
     responses(
-        (status = 200, description = "List of users", body = ListUsersResponse)
+        (status = 200, description = "List of users", body = ListUsersResponse),
+        (status = 401, description = "Missing, invalid or expired access token", body = ErrorResponse),
+        (status = 403, description = "The caller is not staff", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
     )
 )]
 pub async fn list_users(
     State(state): State<AppState>,
+    StaffUser(_staff): StaffUser,
     Query(params): Query<ListUsersQuery>,
 ) -> Result<Json<ListUsersResponse>, (StatusCode, Json<ErrorResponse>)> {
     match state.user_service.list_users(params.limit, params.offset).await {
@@ -110,18 +135,24 @@ pub async fn list_users(
     }
 }
 
-/// Create a new user
+/// Create a user without a password (staff only; members sign up through /auth/register)
 #[utoipa::path(
     post,
     path = "/users",
     request_body = CreateUserRequest,
     responses(
         (status = 201, description = "User created successfully", body = User),
-        (status = 400, description = "Bad request", body = ErrorResponse)
+        (status = 400, description = "Bad request", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired access token", body = ErrorResponse),
+        (status = 403, description = "The caller is not staff", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
     )
 )]
 pub async fn create_user(
     State(state): State<AppState>,
+    StaffUser(_staff): StaffUser,
     Json(payload): Json<CreateUserRequest>,
 ) -> Result<(StatusCode, Json<User>), (StatusCode, Json<ErrorResponse>)> {
     match state.user_service.create_user(payload).await {
@@ -147,14 +178,22 @@ pub async fn create_user(
     responses(
         (status = 200, description = "User updated successfully", body = User),
         (status = 400, description = "Bad request", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired access token", body = ErrorResponse),
+        (status = 403, description = "The user is not the signed-in user", body = ErrorResponse),
         (status = 404, description = "User not found", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
     )
 )]
 pub async fn update_user(
     State(state): State<AppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
     Path(id): Path<u32>,
     Json(payload): Json<UpdateUserRequest>,
 ) -> Result<Json<User>, (StatusCode, Json<ErrorResponse>)> {
+    let id = acting_user_id(&user, id, "User ID")?;
+
     match state.user_service.update_user(id, payload).await {
         Ok(user) => Ok(Json(user)),
         Err(err) => {
@@ -180,13 +219,21 @@ pub async fn update_user(
     ),
     responses(
         (status = 204, description = "User deleted successfully"),
+        (status = 401, description = "Missing, invalid or expired access token", body = ErrorResponse),
+        (status = 403, description = "The user is not the signed-in user", body = ErrorResponse),
         (status = 404, description = "User not found", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
     )
 )]
 pub async fn delete_user(
     State(state): State<AppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
     Path(id): Path<u32>,
 ) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+    let id = acting_user_id(&user, id, "User ID")?;
+
     match state.user_service.delete_user(id).await {
         Ok(true) => Ok(StatusCode::NO_CONTENT),
         Ok(false) => Err((
@@ -0,0 +1,252 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use serde::Deserialize;
+use crate::domain::{PointHold, CreateHoldRequest, CaptureHoldRequest, HoldListResponse, BalanceResponse};
+use crate::presentation::{AppState, AuthenticatedUser, StaffUser, ErrorResponse, acting_user_id, viewable_user_id, ensure_viewable};
+use super::ledger_handlers::parse_ledger_date;
+
+#[derive(Deserialize)]
+pub struct ListHoldsQuery {
+    #[serde(rename = "userId", default)]
+    pub user_id: u32,
+}
+
+#[derive(Deserialize)]
+pub struct BalanceQuery {
+    pub at: Option<String>,
+}
+
+fn hold_error(e: String) -> (StatusCode, Json<ErrorResponse>) {
+    let (status, error) = if e.contains("Hold not found") {
+        (StatusCode::NOT_FOUND, "HOLD_NOT_FOUND")
+    } else if e.contains("User not found") {
+        (StatusCode::BAD_REQUEST, "USER_NOT_FOUND")
+    } else if e.contains("Only active holds") || e.contains("Hold has expired") {
+        (StatusCode::CONFLICT, "INVALID_HOLD_STATE")
+    } else if e.contains("Insufficient points") {
+        (StatusCode::CONFLICT, "INSUFFICIENT_POINTS")
+    } else {
+        (StatusCode::BAD_REQUEST, "VALIDATION_ERROR")
+    };
+
+    (
+        status,
+        Json(ErrorResponse {
+            error: error.to_string(),
+            message: e,
+        }),
+    )
+}
+
+/// Reserve points against a user's available balance
+#[utoipa::path(
+    post,
+    path = "/holds",
+    request_body = CreateHoldRequest,
+    responses(
+        (status = 201, description = "Hold created; the points leave the available balance", body = PointHold),
+        (status = 400, description = "Bad request", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired access token", body = ErrorResponse),
+        (status = 403, description = "userId names someone other than the signed-in user", body = ErrorResponse),
+        (status = 409, description = "Insufficient available points", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Holds"
+)]
+pub async fn create_hold(
+    State(state): State<AppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Json(mut request): Json<CreateHoldRequest>,
+) -> Result<(StatusCode, Json<PointHold>), (StatusCode, Json<ErrorResponse>)> {
+    request.user_id = acting_user_id(&user, request.user_id, "userId")?;
+
+    match state.hold_service.create_hold(request).await {
+        Ok(hold) => Ok((StatusCode::CREATED, Json(hold))),
+        Err(e) => Err(hold_error(e)),
+    }
+}
+
+/// List a user's holds, newest first
+#[utoipa::path(
+    get,
+    path = "/holds",
+    params(
+        ("userId" = Option<u32>, Query, description = "User ID to filter holds (default: the signed-in user)")
+    ),
+    responses(
+        (status = 200, description = "Holds found", body = HoldListResponse),
+        (status = 400, description = "Bad request", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired access token", body = ErrorResponse),
+        (status = 403, description = "userId names someone other than the signed-in user, and the caller is not staff", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Holds"
+)]
+pub async fn list_holds(
+    State(state): State<AppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Query(params): Query<ListHoldsQuery>,
+) -> Result<Json<HoldListResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let user_id = viewable_user_id(&user, params.user_id, "userId")?;
+
+    match state.hold_service.list_holds(user_id).await {
+        Ok(response) => Ok(Json(response)),
+        Err(e) => Err(hold_error(e)),
+    }
+}
+
+/// Get hold by ID
+#[utoipa::path(
+    get,
+    path = "/holds/{id}",
+    params(
+        ("id" = String, Path, description = "Hold ID")
+    ),
+    responses(
+        (status = 200, description = "Hold found", body = PointHold),
+        (status = 401, description = "Missing, invalid or expired access token", body = ErrorResponse),
+        (status = 403, description = "The hold belongs to another user, and the caller is not staff", body = ErrorResponse),
+        (status = 404, description = "Hold not found", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Holds"
+)]
+pub async fn get_hold(
+    State(state): State<AppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Path(id): Path<String>,
+) -> Result<Json<PointHold>, (StatusCode, Json<ErrorResponse>)> {
+    match state.hold_service.get_hold(&id).await {
+        Ok(hold) => {
+            ensure_viewable(&user, &[hold.user_id], "Hold")?;
+            Ok(Json(hold))
+        }
+        Err(e) => Err(hold_error(e)),
+    }
+}
+
+/// Capture a hold as a redeem ledger entry (all of it, or part and release the rest)
+#[utoipa::path(
+    post,
+    path = "/holds/{id}/capture",
+    params(
+        ("id" = String, Path, description = "Hold ID")
+    ),
+    request_body = CaptureHoldRequest,
+    responses(
+        (status = 200, description = "Hold captured", body = PointHold),
+        (status = 400, description = "Bad request", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired access token", body = ErrorResponse),
+        (status = 403, description = "Only staff or the merchant's service account can capture a hold", body = ErrorResponse),
+        (status = 404, description = "Hold not found", body = ErrorResponse),
+        (status = 409, description = "Hold is no longer active, or has expired", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Holds"
+)]
+pub async fn capture_hold(
+    State(state): State<AppState>,
+    StaffUser(staff): StaffUser,
+    Path(id): Path<String>,
+    Json(request): Json<CaptureHoldRequest>,
+) -> Result<Json<PointHold>, (StatusCode, Json<ErrorResponse>)> {
+    match state.hold_service.capture_hold(&id, &staff.email, request).await {
+        Ok(hold) => Ok(Json(hold)),
+        Err(e) => Err(hold_error(e)),
+    }
+}
+
+/// Release a hold without taking any points
+#[utoipa::path(
+    post,
+    path = "/holds/{id}/release",
+    params(
+        ("id" = String, Path, description = "Hold ID")
+    ),
+    responses(
+        (status = 200, description = "Hold released", body = PointHold),
+        (status = 401, description = "Missing, invalid or expired access token", body = ErrorResponse),
+        (status = 403, description = "Only staff or the merchant's service account can release a hold", body = ErrorResponse),
+        (status = 404, description = "Hold not found", body = ErrorResponse),
+        (status = 409, description = "Hold is no longer active", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Holds"
+)]
+pub async fn release_hold(
+    State(state): State<AppState>,
+    StaffUser(_staff): StaffUser,
+    Path(id): Path<String>,
+) -> Result<Json<PointHold>, (StatusCode, Json<ErrorResponse>)> {
+    match state.hold_service.release_hold(&id).await {
+        Ok(hold) => Ok(Json(hold)),
+        Err(e) => Err(hold_error(e)),
+    }
+}
+
+/// Get a user's ledger, held and available balance, or their ledger balance at a past instant
+#[utoipa::path(
+    get,
+    path = "/users/{id}/balance",
+    params(
+        ("id" = u32, Path, description = "User ID"),
+        ("at" = Option<String>, Query, description = "Past instant, RFC 3339 or YYYY-MM-DD (end of that day); returns the ledger balance as of then")
+    ),
+    responses(
+        (status = 200, description = "Balance found", body = BalanceResponse),
+        (status = 400, description = "Bad request", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired access token", body = ErrorResponse),
+        (status = 403, description = "The user is not the signed-in user, and the caller is not staff", body = ErrorResponse),
+        (status = 404, description = "User not found", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Holds"
+)]
+pub async fn get_balance(
+    State(state): State<AppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Path(id): Path<u32>,
+    Query(params): Query<BalanceQuery>,
+) -> Result<Json<BalanceResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let id = viewable_user_id(&user, id, "User ID")?;
+    let result = match &params.at {
+        Some(at) => {
+            let at = parse_ledger_date("at", at, true).map_err(hold_error)?;
+            state.ledger_service.balance_at(id, at).await.map(BalanceResponse::Historical)
+        }
+        None => state.hold_service.get_balance(id).await.map(BalanceResponse::Current),
+    };
+
+    match result {
+        Ok(balance) => Ok(Json(balance)),
+        Err(e) if e.contains("User not found") => Err((
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: "USER_NOT_FOUND".to_string(),
+                message: e,
+            }),
+        )),
+        Err(e) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: "INTERNAL_ERROR".to_string(),
+                message: e,
+            }),
+        )),
+    }
+}
\ No newline at end of file
@@ -0,0 +1,225 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Deserialize;
+use crate::domain::{EventType, LedgerFilter, LedgerListResponse, LedgerEntryDetail, BalanceInterval, BalanceSeriesResponse};
+use crate::presentation::{AppState, AuthenticatedUser, ErrorResponse, viewable_user_id, ensure_viewable};
+
+#[derive(Deserialize)]
+pub struct LedgerQuery {
+    // Comma-separated, e.g. earn,transfer_in
+    #[serde(rename = "eventType")]
+    pub event_type: Option<String>,
+    pub from: Option<String>,
+    pub to: Option<String>,
+    #[serde(rename = "minAmount")]
+    pub min_amount: Option<u32>,
+    #[serde(rename = "maxAmount")]
+    pub max_amount: Option<u32>,
+    pub cursor: Option<u32>,
+    pub page: Option<u32>,
+    #[serde(rename = "pageSize")]
+    pub page_size: Option<u32>,
+}
+
+#[derive(Deserialize)]
+pub struct BalanceSeriesQuery {
+    pub from: String,
+    pub to: String,
+    pub interval: Option<String>,
+}
+
+// Accepts an RFC 3339 timestamp or a plain date; a plain `to` date covers that whole day
+pub(crate) fn parse_ledger_date(name: &str, value: &str, end_of_day: bool) -> Result<DateTime<Utc>, String> {
+    if let Ok(date_time) = DateTime::parse_from_rfc3339(value) {
+        return Ok(date_time.with_timezone(&Utc));
+    }
+
+    let date = NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| format!("{} must be an RFC 3339 timestamp or a YYYY-MM-DD date", name))?;
+    let date_time = if end_of_day {
+        date.and_hms_milli_opt(23, 59, 59, 999)
+    } else {
+        date.and_hms_opt(0, 0, 0)
+    };
+
+    Ok(date_time.ok_or(format!("Invalid {} date", name))?.and_utc())
+}
+
+fn ledger_filter(params: &LedgerQuery) -> Result<LedgerFilter, String> {
+    let event_types = match &params.event_type {
+        Some(event_type) => event_type
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(|s| s.parse::<EventType>())
+            .collect::<Result<Vec<_>, _>>()?,
+        None => Vec::new(),
+    };
+
+    Ok(LedgerFilter {
+        event_types,
+        from: params.from.as_deref().map(|from| parse_ledger_date("from", from, false)).transpose()?,
+        to: params.to.as_deref().map(|to| parse_ledger_date("to", to, true)).transpose()?,
+        min_amount: params.min_amount,
+        max_amount: params.max_amount,
+    })
+}
+
+fn validation_error(e: String) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(ErrorResponse {
+            error: "VALIDATION_ERROR".to_string(),
+            message: e,
+        }),
+    )
+}
+
+/// List a user's point ledger, newest first
+#[utoipa::path(
+    get,
+    path = "/users/{id}/ledger",
+    params(
+        ("id" = u32, Path, description = "User ID"),
+        ("eventType" = Option<String>, Query, description = "Comma-separated event types: earn, redeem, transfer_in, transfer_out, adjust, expire"),
+        ("from" = Option<String>, Query, description = "Earliest entry, RFC 3339 or YYYY-MM-DD"),
+        ("to" = Option<String>, Query, description = "Latest entry, RFC 3339 or YYYY-MM-DD (inclusive)"),
+        ("minAmount" = Option<u32>, Query, description = "Smallest absolute change"),
+        ("maxAmount" = Option<u32>, Query, description = "Largest absolute change"),
+        ("cursor" = Option<u32>, Query, description = "nextCursor from the previous page; takes the place of page"),
+        ("page" = Option<u32>, Query, description = "Page number (default: 1)"),
+        ("pageSize" = Option<u32>, Query, description = "Page size (default: 20, max: 200)")
+    ),
+    responses(
+        (status = 200, description = "Ledger entries found, with totals over every matching entry", body = LedgerListResponse),
+        (status = 400, description = "Bad request", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired access token", body = ErrorResponse),
+        (status = 403, description = "The user is not the signed-in user, and the caller is not staff", body = ErrorResponse),
+        (status = 404, description = "User not found", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Ledger"
+)]
+pub async fn list_ledger(
+    State(state): State<AppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Path(id): Path<u32>,
+    Query(params): Query<LedgerQuery>,
+) -> Result<Json<LedgerListResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let id = viewable_user_id(&user, id, "User ID")?;
+    let filter = ledger_filter(&params).map_err(validation_error)?;
+    let page = params.page.unwrap_or(1);
+    let page_size = params.page_size.unwrap_or(20);
+
+    match state.ledger_service.list_ledger(id, filter, params.cursor, page, page_size).await {
+        Ok(response) => Ok(Json(response)),
+        Err(e) if e.contains("User not found") => Err((
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: "USER_NOT_FOUND".to_string(),
+                message: e,
+            }),
+        )),
+        Err(e) => Err(validation_error(e)),
+    }
+}
+
+/// Get a ledger entry with its counterparty and linked transfer
+#[utoipa::path(
+    get,
+    path = "/ledger/{id}",
+    params(
+        ("id" = u32, Path, description = "Ledger entry ID")
+    ),
+    responses(
+        (status = 200, description = "Ledger entry found", body = LedgerEntryDetail),
+        (status = 401, description = "Missing, invalid or expired access token", body = ErrorResponse),
+        (status = 403, description = "The entry is on another user's ledger, and the caller is not staff", body = ErrorResponse),
+        (status = 404, description = "Ledger entry not found", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Ledger"
+)]
+pub async fn get_ledger_entry(
+    State(state): State<AppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Path(id): Path<u32>,
+) -> Result<Json<LedgerEntryDetail>, (StatusCode, Json<ErrorResponse>)> {
+    match state.ledger_service.get_ledger_entry(id).await {
+        Ok(detail) => {
+            ensure_viewable(&user, &[detail.entry.user_id], "Ledger entry")?;
+            Ok(Json(detail))
+        }
+        Err(e) if e.contains("Ledger entry not found") => Err((
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: "LEDGER_ENTRY_NOT_FOUND".to_string(),
+                message: e,
+            }),
+        )),
+        Err(e) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: "INTERNAL_ERROR".to_string(),
+                message: e,
+            }),
+        )),
+    }
+}
+
+/// End-of-period ledger balances for a user over a date range
+#[utoipa::path(
+    get,
+    path = "/users/{id}/balance/series",
+    params(
+        ("id" = u32, Path, description = "User ID"),
+        ("from" = String, Query, description = "Start of the range, RFC 3339 or YYYY-MM-DD; the first period is the one this falls in"),
+        ("to" = String, Query, description = "End of the range, RFC 3339 or YYYY-MM-DD (inclusive)"),
+        ("interval" = Option<String>, Query, description = "day, week or month (default: day)")
+    ),
+    responses(
+        (status = 200, description = "One balance per period", body = BalanceSeriesResponse),
+        (status = 400, description = "Bad request", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired access token", body = ErrorResponse),
+        (status = 403, description = "The user is not the signed-in user, and the caller is not staff", body = ErrorResponse),
+        (status = 404, description = "User not found", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Ledger"
+)]
+pub async fn get_balance_series(
+    State(state): State<AppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Path(id): Path<u32>,
+    Query(params): Query<BalanceSeriesQuery>,
+) -> Result<Json<BalanceSeriesResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let id = viewable_user_id(&user, id, "User ID")?;
+    let from = parse_ledger_date("from", &params.from, false).map_err(validation_error)?;
+    let to = parse_ledger_date("to", &params.to, true).map_err(validation_error)?;
+    let interval = match &params.interval {
+        Some(interval) => interval.parse::<BalanceInterval>().map_err(validation_error)?,
+        None => BalanceInterval::Day,
+    };
+
+    match state.ledger_service.balance_series(id, from, to, interval).await {
+        Ok(response) => Ok(Json(response)),
+        Err(e) if e.contains("User not found") => Err((
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: "USER_NOT_FOUND".to_string(),
+                message: e,
+            }),
+        )),
+        Err(e) => Err(validation_error(e)),
+    }
+}
\ No newline at end of file
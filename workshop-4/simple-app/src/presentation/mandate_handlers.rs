@@ -0,0 +1,254 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use serde::Deserialize;
+use crate::domain::{TransferMandate, CreateMandateRequest, UpdateMandateRequest, MandateListResponse};
+use crate::presentation::{AppState, AuthenticatedUser, ErrorResponse, acting_user_id, viewable_user_id, ensure_viewable};
+
+#[derive(Deserialize)]
+pub struct ListMandatesQuery {
+    #[serde(rename = "userId", default)]
+    pub user_id: u32,
+}
+
+fn mandate_error(e: String) -> (StatusCode, Json<ErrorResponse>) {
+    let (status, error) = if e.contains("Mandate not found") {
+        (StatusCode::NOT_FOUND, "MANDATE_NOT_FOUND")
+    } else if e.contains("User not found") {
+        (StatusCode::BAD_REQUEST, "USER_NOT_FOUND")
+    } else if e.contains("Only the payer") {
+        (StatusCode::FORBIDDEN, "NOT_MANDATE_PAYER")
+    } else if e.contains("Only active") || e.contains("Only paused") {
+        (StatusCode::CONFLICT, "INVALID_MANDATE_STATE")
+    } else if e.contains("Cannot transfer to the same user") {
+        (StatusCode::UNPROCESSABLE_ENTITY, "INVALID_TRANSFER")
+    } else {
+        (StatusCode::BAD_REQUEST, "VALIDATION_ERROR")
+    };
+
+    (
+        status,
+        Json(ErrorResponse {
+            error: error.to_string(),
+            message: e,
+        }),
+    )
+}
+
+/// Create a recurring transfer mandate
+#[utoipa::path(
+    post,
+    path = "/mandates",
+    request_body = CreateMandateRequest,
+    responses(
+        (status = 201, description = "Mandate created; the first run is at startAt", body = TransferMandate),
+        (status = 400, description = "Bad request", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired access token", body = ErrorResponse),
+        (status = 403, description = "fromUserId names someone other than the signed-in user", body = ErrorResponse),
+        (status = 422, description = "Unprocessable entity", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Mandates"
+)]
+pub async fn create_mandate(
+    State(state): State<AppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Json(mut request): Json<CreateMandateRequest>,
+) -> Result<(StatusCode, Json<TransferMandate>), (StatusCode, Json<ErrorResponse>)> {
+    request.from_user_id = acting_user_id(&user, request.from_user_id, "fromUserId")?;
+
+    match state.mandate_service.create_mandate(request).await {
+        Ok(mandate) => Ok((StatusCode::CREATED, Json(mandate))),
+        Err(e) => Err(mandate_error(e)),
+    }
+}
+
+/// List mandates a user sends or receives
+#[utoipa::path(
+    get,
+    path = "/mandates",
+    params(
+        ("userId" = Option<u32>, Query, description = "User ID to filter mandates (default: the signed-in user)")
+    ),
+    responses(
+        (status = 200, description = "Mandates found", body = MandateListResponse),
+        (status = 400, description = "Bad request", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired access token", body = ErrorResponse),
+        (status = 403, description = "userId names someone other than the signed-in user, and the caller is not staff", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Mandates"
+)]
+pub async fn list_mandates(
+    State(state): State<AppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Query(params): Query<ListMandatesQuery>,
+) -> Result<Json<MandateListResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let user_id = viewable_user_id(&user, params.user_id, "userId")?;
+
+    match state.mandate_service.list_mandates(user_id).await {
+        Ok(response) => Ok(Json(response)),
+        Err(e) => Err(mandate_error(e)),
+    }
+}
+
+/// Get mandate by ID
+#[utoipa::path(
+    get,
+    path = "/mandates/{id}",
+    params(
+        ("id" = u32, Path, description = "Mandate ID")
+    ),
+    responses(
+        (status = 200, description = "Mandate found", body = TransferMandate),
+        (status = 401, description = "Missing, invalid or expired access token", body = ErrorResponse),
+        (status = 403, description = "The signed-in user is neither the payer nor the payee, and not staff", body = ErrorResponse),
+        (status = 404, description = "Mandate not found", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Mandates"
+)]
+pub async fn get_mandate(
+    State(state): State<AppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Path(id): Path<u32>,
+) -> Result<Json<TransferMandate>, (StatusCode, Json<ErrorResponse>)> {
+    match state.mandate_service.get_mandate(id).await {
+        Ok(mandate) => {
+            ensure_viewable(&user, &[mandate.from_user_id, mandate.to_user_id], "Mandate")?;
+            Ok(Json(mandate))
+        }
+        Err(e) => Err(mandate_error(e)),
+    }
+}
+
+/// Change the amount, note, endAt or maxRuns of future runs
+#[utoipa::path(
+    put,
+    path = "/mandates/{id}",
+    params(
+        ("id" = u32, Path, description = "Mandate ID")
+    ),
+    request_body = UpdateMandateRequest,
+    responses(
+        (status = 200, description = "Mandate updated", body = TransferMandate),
+        (status = 400, description = "Bad request", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired access token", body = ErrorResponse),
+        (status = 403, description = "Only the payer can change the mandate", body = ErrorResponse),
+        (status = 404, description = "Mandate not found", body = ErrorResponse),
+        (status = 409, description = "Mandate is completed or cancelled", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Mandates"
+)]
+pub async fn update_mandate(
+    State(state): State<AppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Path(id): Path<u32>,
+    Json(request): Json<UpdateMandateRequest>,
+) -> Result<Json<TransferMandate>, (StatusCode, Json<ErrorResponse>)> {
+    match state.mandate_service.update_mandate(id, user.id, request).await {
+        Ok(mandate) => Ok(Json(mandate)),
+        Err(e) => Err(mandate_error(e)),
+    }
+}
+
+/// Cancel a mandate; no further runs are made
+#[utoipa::path(
+    delete,
+    path = "/mandates/{id}",
+    params(
+        ("id" = u32, Path, description = "Mandate ID")
+    ),
+    responses(
+        (status = 200, description = "Mandate cancelled", body = TransferMandate),
+        (status = 401, description = "Missing, invalid or expired access token", body = ErrorResponse),
+        (status = 403, description = "Only the payer can change the mandate", body = ErrorResponse),
+        (status = 404, description = "Mandate not found", body = ErrorResponse),
+        (status = 409, description = "Mandate is already completed or cancelled", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Mandates"
+)]
+pub async fn cancel_mandate(
+    State(state): State<AppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Path(id): Path<u32>,
+) -> Result<Json<TransferMandate>, (StatusCode, Json<ErrorResponse>)> {
+    match state.mandate_service.cancel_mandate(id, user.id).await {
+        Ok(mandate) => Ok(Json(mandate)),
+        Err(e) => Err(mandate_error(e)),
+    }
+}
+
+/// Pause an active mandate
+#[utoipa::path(
+    post,
+    path = "/mandates/{id}/pause",
+    params(
+        ("id" = u32, Path, description = "Mandate ID")
+    ),
+    responses(
+        (status = 200, description = "Mandate paused", body = TransferMandate),
+        (status = 401, description = "Missing, invalid or expired access token", body = ErrorResponse),
+        (status = 403, description = "Only the payer can change the mandate", body = ErrorResponse),
+        (status = 404, description = "Mandate not found", body = ErrorResponse),
+        (status = 409, description = "Mandate is not active", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Mandates"
+)]
+pub async fn pause_mandate(
+    State(state): State<AppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Path(id): Path<u32>,
+) -> Result<Json<TransferMandate>, (StatusCode, Json<ErrorResponse>)> {
+    match state.mandate_service.pause_mandate(id, user.id).await {
+        Ok(mandate) => Ok(Json(mandate)),
+        Err(e) => Err(mandate_error(e)),
+    }
+}
+
+/// Resume a paused mandate; runs missed while paused are skipped
+#[utoipa::path(
+    post,
+    path = "/mandates/{id}/resume",
+    params(
+        ("id" = u32, Path, description = "Mandate ID")
+    ),
+    responses(
+        (status = 200, description = "Mandate resumed", body = TransferMandate),
+        (status = 401, description = "Missing, invalid or expired access token", body = ErrorResponse),
+        (status = 403, description = "Only the payer can change the mandate", body = ErrorResponse),
+        (status = 404, description = "Mandate not found", body = ErrorResponse),
+        (status = 409, description = "Mandate is not paused", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Mandates"
+)]
+pub async fn resume_mandate(
+    State(state): State<AppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Path(id): Path<u32>,
+) -> Result<Json<TransferMandate>, (StatusCode, Json<ErrorResponse>)> {
+    match state.mandate_service.resume_mandate(id, user.id).await {
+        Ok(mandate) => Ok(Json(mandate)),
+        Err(e) => Err(mandate_error(e)),
+    }
+}
\ No newline at end of file
@@ -1,6 +1,21 @@
 pub mod handlers;
 pub mod routes;
 pub mod transfer_handlers;
+pub mod mandate_handlers;
+pub mod batch_handlers;
+pub mod transfer_limit_handlers;
+pub mod hold_handlers;
+pub mod ledger_handlers;
+pub mod points_handlers;
+pub mod adjustment_handlers;
+pub mod reconciliation_handlers;
+pub mod tier_handlers;
+pub mod auth;
+pub mod auth_handlers;
+#[cfg(test)]
+pub mod test_support;
 
 pub use handlers::{AppState, ErrorResponse, ListUsersResponse};
+pub use transfer_handlers::LimitExceededResponse;
+pub use auth::{AuthenticatedUser, StaffUser, acting_user_id, viewable_user_id, ensure_viewable};
 pub use routes::create_routes;
\ No newline at end of file
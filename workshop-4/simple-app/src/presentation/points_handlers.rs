@@ -0,0 +1,169 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::Json,
+};
+use serde::Deserialize;
+use crate::domain::{PointsOperationRequest, PointsOperationResponse, ExpiringPointsResponse, UserRole};
+use crate::presentation::{AppState, AuthenticatedUser, StaffUser, ErrorResponse, acting_user_id, viewable_user_id};
+
+#[derive(Deserialize)]
+pub struct ExpiringPointsQuery {
+    #[serde(rename = "withinDays")]
+    pub within_days: Option<u32>,
+}
+
+fn idempotency_key(headers: &HeaderMap) -> Result<Option<String>, (StatusCode, Json<ErrorResponse>)> {
+    match headers.get("Idempotency-Key").map(|value| value.to_str()) {
+        Some(Ok(key)) => Ok(Some(key.to_string())),
+        Some(Err(_)) => Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "VALIDATION_ERROR".to_string(),
+                message: "Idempotency key must be visible ASCII".to_string(),
+            }),
+        )),
+        None => Ok(None),
+    }
+}
+
+fn points_error(e: String) -> (StatusCode, Json<ErrorResponse>) {
+    let (status, error) = if e.contains("User not found") {
+        (StatusCode::NOT_FOUND, "USER_NOT_FOUND")
+    } else if e.contains("different request") {
+        (StatusCode::CONFLICT, "IDEMPOTENCY_KEY_REUSED")
+    } else if e.contains("Insufficient points") {
+        (StatusCode::CONFLICT, "INSUFFICIENT_POINTS")
+    } else {
+        (StatusCode::BAD_REQUEST, "VALIDATION_ERROR")
+    };
+
+    (
+        status,
+        Json(ErrorResponse {
+            error: error.to_string(),
+            message: e,
+        }),
+    )
+}
+
+/// Credit points to a user as an earn ledger entry
+#[utoipa::path(
+    post,
+    path = "/users/{id}/points/earn",
+    params(
+        ("id" = u32, Path, description = "User ID"),
+        ("Idempotency-Key" = Option<String>, Header, description = "Client key (8-128 chars); a retry with the same key and body returns the original entry")
+    ),
+    request_body = PointsOperationRequest,
+    responses(
+        (status = 201, description = "Points earned", body = PointsOperationResponse),
+        (status = 400, description = "Bad request", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired access token", body = ErrorResponse),
+        (status = 403, description = "Only admins can credit points directly; staff go through an adjustment", body = ErrorResponse),
+        (status = 404, description = "User not found", body = ErrorResponse),
+        (status = 409, description = "Idempotency-Key reused with a different body", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Points"
+)]
+pub async fn earn_points(
+    State(state): State<AppState>,
+    StaffUser(staff): StaffUser,
+    Path(id): Path<u32>,
+    headers: HeaderMap,
+    Json(request): Json<PointsOperationRequest>,
+) -> Result<(StatusCode, Json<PointsOperationResponse>), (StatusCode, Json<ErrorResponse>)> {
+    // Anyone else needs a second person to approve the credit, which is what adjustments are for
+    if staff.role != UserRole::Admin {
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(ErrorResponse {
+                error: "FORBIDDEN".to_string(),
+                message: "Only admins can credit points directly; request an adjustment instead".to_string(),
+            }),
+        ));
+    }
+    let idempotency_key = idempotency_key(&headers)?;
+
+    match state.points_service.earn_points(id, &staff.email, request, idempotency_key).await {
+        Ok(response) => Ok((StatusCode::CREATED, Json(response))),
+        Err(e) => Err(points_error(e)),
+    }
+}
+
+/// Debit points from a user's available balance as a redeem ledger entry
+#[utoipa::path(
+    post,
+    path = "/users/{id}/points/redeem",
+    params(
+        ("id" = u32, Path, description = "User ID"),
+        ("Idempotency-Key" = Option<String>, Header, description = "Client key (8-128 chars); a retry with the same key and body returns the original entry")
+    ),
+    request_body = PointsOperationRequest,
+    responses(
+        (status = 201, description = "Points redeemed", body = PointsOperationResponse),
+        (status = 400, description = "Bad request", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired access token", body = ErrorResponse),
+        (status = 403, description = "The user is not the signed-in user", body = ErrorResponse),
+        (status = 404, description = "User not found", body = ErrorResponse),
+        (status = 409, description = "Conflict (insufficient points, or Idempotency-Key reused with a different body)", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Points"
+)]
+pub async fn redeem_points(
+    State(state): State<AppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Path(id): Path<u32>,
+    headers: HeaderMap,
+    Json(request): Json<PointsOperationRequest>,
+) -> Result<(StatusCode, Json<PointsOperationResponse>), (StatusCode, Json<ErrorResponse>)> {
+    let id = acting_user_id(&user, id, "User ID")?;
+    let idempotency_key = idempotency_key(&headers)?;
+
+    match state.points_service.redeem_points(id, request, idempotency_key).await {
+        Ok(response) => Ok((StatusCode::CREATED, Json(response))),
+        Err(e) => Err(points_error(e)),
+    }
+}
+
+/// Points that will expire within the next few days, soonest first
+#[utoipa::path(
+    get,
+    path = "/users/{id}/points/expiring",
+    params(
+        ("id" = u32, Path, description = "User ID"),
+        ("withinDays" = Option<u32>, Query, description = "How far ahead to look (default: 90, max: 3650)")
+    ),
+    responses(
+        (status = 200, description = "Expiring points found", body = ExpiringPointsResponse),
+        (status = 400, description = "Bad request", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired access token", body = ErrorResponse),
+        (status = 403, description = "The user is not the signed-in user, and the caller is not staff", body = ErrorResponse),
+        (status = 404, description = "User not found", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Points"
+)]
+pub async fn list_expiring_points(
+    State(state): State<AppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Path(id): Path<u32>,
+    Query(params): Query<ExpiringPointsQuery>,
+) -> Result<Json<ExpiringPointsResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let id = viewable_user_id(&user, id, "User ID")?;
+
+    let within_days = params.within_days.unwrap_or(90);
+
+    match state.expiry_service.list_expiring(id, within_days).await {
+        Ok(response) => Ok(Json(response)),
+        Err(e) => Err(points_error(e)),
+    }
+}
\ No newline at end of file
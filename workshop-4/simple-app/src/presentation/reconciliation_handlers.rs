@@ -0,0 +1,69 @@
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::Json,
+};
+use crate::domain::{ReconciliationReport, LedgerChainReport};
+use crate::presentation::{AppState, StaffUser, ErrorResponse};
+
+/// Replay the ledger and report any discrepancies
+#[utoipa::path(
+    get,
+    path = "/admin/reconciliation",
+    responses(
+        (status = 200, description = "Reconciliation report; clean is false when discrepancies were found", body = ReconciliationReport),
+        (status = 401, description = "Missing, invalid or expired access token", body = ErrorResponse),
+        (status = 403, description = "Only staff can run the reconciliation", body = ErrorResponse),
+        (status = 500, description = "Reconciliation could not run", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Admin"
+)]
+pub async fn get_reconciliation(
+    State(state): State<AppState>,
+    StaffUser(_staff): StaffUser,
+) -> Result<Json<ReconciliationReport>, (StatusCode, Json<ErrorResponse>)> {
+    match state.reconciliation_service.reconcile().await {
+        Ok(report) => Ok(Json(report)),
+        Err(e) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: "INTERNAL_ERROR".to_string(),
+                message: e,
+            }),
+        )),
+    }
+}
+
+/// Walk every user's hash chain and report the first broken link
+#[utoipa::path(
+    get,
+    path = "/admin/ledger/verify",
+    responses(
+        (status = 200, description = "Verification report; intact is false when a link is broken", body = LedgerChainReport),
+        (status = 401, description = "Missing, invalid or expired access token", body = ErrorResponse),
+        (status = 403, description = "Only staff can verify the ledger", body = ErrorResponse),
+        (status = 500, description = "Verification could not run", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Admin"
+)]
+pub async fn verify_ledger_chain(
+    State(state): State<AppState>,
+    StaffUser(_staff): StaffUser,
+) -> Result<Json<LedgerChainReport>, (StatusCode, Json<ErrorResponse>)> {
+    match state.reconciliation_service.verify_chain().await {
+        Ok(report) => Ok(Json(report)),
+        Err(e) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: "INTERNAL_ERROR".to_string(),
+                message: e,
+            }),
+        )),
+    }
+}
\ No newline at end of file
@@ -6,18 +6,93 @@ use super::handlers::{
     hello_world, get_user, list_users, create_user, update_user, delete_user, AppState
 };
 use super::transfer_handlers::{
-    create_transfer, get_transfer, list_transfers
+    create_transfer, create_split_transfer, get_transfer, list_transfers, reverse_transfer, cancel_transfer, accept_transfer, decline_transfer
+};
+use super::batch_handlers::{
+    create_transfer_batch, get_transfer_batch
+};
+use super::transfer_limit_handlers::{
+    list_transfer_limits, get_transfer_limits, set_transfer_limits
+};
+use super::hold_handlers::{
+    create_hold, list_holds, get_hold, capture_hold, release_hold, get_balance
+};
+use super::ledger_handlers::{
+    list_ledger, get_ledger_entry, get_balance_series
+};
+use super::points_handlers::{
+    earn_points, redeem_points, list_expiring_points
+};
+use super::adjustment_handlers::{
+    create_adjustment, list_adjustments, get_adjustment, approve_adjustment, reject_adjustment
+};
+use super::reconciliation_handlers::{get_reconciliation, verify_ledger_chain};
+use super::tier_handlers::{
+    list_tiers, get_user_tier, set_user_tier, get_tier_history, evaluate_tiers
+};
+use super::auth_handlers::{
+    register, login, request_login_otp, refresh_token, logout, get_current_user
+};
+use super::mandate_handlers::{
+    create_mandate, list_mandates, get_mandate, update_mandate, cancel_mandate, pause_mandate, resume_mandate
 };
 
 pub fn create_routes() -> Router<AppState> {
     Router::new()
         .route("/", get(hello_world))
+        .route("/auth/register", post(register))
+        .route("/auth/login", post(login))
+        .route("/auth/otp", post(request_login_otp))
+        .route("/auth/refresh", post(refresh_token))
+        .route("/auth/logout", post(logout))
+        .route("/auth/me", get(get_current_user))
         .route("/users", get(list_users))
         .route("/users", post(create_user))
         .route("/users/{id}", get(get_user))
         .route("/users/{id}", put(update_user))
         .route("/users/{id}", delete(delete_user))
+        .route("/users/{id}/balance", get(get_balance))
+        .route("/users/{id}/balance/series", get(get_balance_series))
+        .route("/users/{id}/ledger", get(list_ledger))
+        .route("/users/{id}/points/earn", post(earn_points))
+        .route("/users/{id}/points/redeem", post(redeem_points))
+        .route("/users/{id}/points/expiring", get(list_expiring_points))
+        .route("/users/{id}/tier", get(get_user_tier))
+        .route("/users/{id}/tier", put(set_user_tier))
+        .route("/users/{id}/tier/history", get(get_tier_history))
         .route("/transfers", post(create_transfer))
         .route("/transfers", get(list_transfers))
         .route("/transfers/{id}", get(get_transfer))
+        .route("/transfers/{id}/reverse", post(reverse_transfer))
+        .route("/transfers/{id}/cancel", post(cancel_transfer))
+        .route("/transfers/{id}/accept", post(accept_transfer))
+        .route("/transfers/{id}/decline", post(decline_transfer))
+        .route("/transfers/split", post(create_split_transfer))
+        .route("/transfers/batch", post(create_transfer_batch))
+        .route("/transfers/batches/{id}", get(get_transfer_batch))
+        .route("/transfer-limits", get(list_transfer_limits))
+        .route("/transfer-limits/{level}", get(get_transfer_limits))
+        .route("/transfer-limits/{level}", put(set_transfer_limits))
+        .route("/tiers", get(list_tiers))
+        .route("/holds", post(create_hold))
+        .route("/holds", get(list_holds))
+        .route("/holds/{id}", get(get_hold))
+        .route("/holds/{id}/capture", post(capture_hold))
+        .route("/holds/{id}/release", post(release_hold))
+        .route("/ledger/{id}", get(get_ledger_entry))
+        .route("/adjustments", post(create_adjustment))
+        .route("/adjustments", get(list_adjustments))
+        .route("/adjustments/{id}", get(get_adjustment))
+        .route("/adjustments/{id}/approve", post(approve_adjustment))
+        .route("/adjustments/{id}/reject", post(reject_adjustment))
+        .route("/admin/reconciliation", get(get_reconciliation))
+        .route("/admin/ledger/verify", get(verify_ledger_chain))
+        .route("/admin/tiers/evaluate", post(evaluate_tiers))
+        .route("/mandates", post(create_mandate))
+        .route("/mandates", get(list_mandates))
+        .route("/mandates/{id}", get(get_mandate))
+        .route("/mandates/{id}", put(update_mandate))
+        .route("/mandates/{id}", delete(cancel_mandate))
+        .route("/mandates/{id}/pause", post(pause_mandate))
+        .route("/mandates/{id}/resume", post(resume_mandate))
 }
\ No newline at end of file
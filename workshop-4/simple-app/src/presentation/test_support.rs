@@ -0,0 +1,131 @@
+use std::sync::Arc;
+use axum::{body::Body, http::Request, Router};
+use serde_json::Value;
+use sqlx::SqlitePool;
+use tower::ServiceExt;
+use crate::application::{
+    UserService, TransferService, MandateService, BatchService, HoldService, LedgerService, PointsService,
+    AdjustmentService, ReconciliationService, PointsExpiryService, TierService, AuthService,
+};
+use crate::domain::{AccessTokenClaims, Clock};
+use crate::infrastructure::{
+    SqliteUserRepository, SqliteTransferRepository, SqlitePointLedgerRepository, SqliteUnitOfWorkFactory,
+    SqliteMandateRepository, SqliteBatchRepository, SqliteTransferLimitRepository, SqliteHoldRepository,
+    SqliteAdjustmentRepository, SqliteTierRepository, SqliteAuthRepository, ConsoleOtpSender,
+};
+use super::{create_routes, AppState};
+
+const TEST_JWT_SECRET: &[u8] = b"test-secret";
+
+// The router wired the way main() wires it, on the given database and clock
+pub fn test_app(pool: &SqlitePool, clock: Arc<dyn Clock + Send + Sync>) -> Router {
+    let user_repository = Arc::new(SqliteUserRepository::new(pool.clone()));
+    let transfer_repository = Arc::new(SqliteTransferRepository::new(pool.clone()));
+    let point_ledger_repository = Arc::new(SqlitePointLedgerRepository::new(pool.clone()));
+    let transfer_limit_repository = Arc::new(SqliteTransferLimitRepository::new(pool.clone()));
+    let unit_of_work_factory = Arc::new(SqliteUnitOfWorkFactory::new(pool.clone()));
+
+    let transfer_service = TransferService::new(
+        transfer_repository.clone(),
+        user_repository.clone(),
+        transfer_limit_repository.clone(),
+        unit_of_work_factory.clone(),
+        clock.clone(),
+    );
+    let app_state = AppState {
+        user_service: UserService::new(user_repository.clone()),
+        mandate_service: MandateService::new(
+            Arc::new(SqliteMandateRepository::new(pool.clone())),
+            user_repository.clone(),
+            transfer_service.clone(),
+            clock.clone(),
+        ),
+        batch_service: BatchService::new(
+            Arc::new(SqliteBatchRepository::new(pool.clone())),
+            transfer_service.clone(),
+            clock.clone(),
+        ),
+        hold_service: HoldService::new(
+            Arc::new(SqliteHoldRepository::new(pool.clone())),
+            point_ledger_repository.clone(),
+            user_repository.clone(),
+            unit_of_work_factory.clone(),
+            transfer_service.account_locks(),
+            clock.clone(),
+        ),
+        ledger_service: LedgerService::new(
+            point_ledger_repository.clone(),
+            transfer_repository.clone(),
+            user_repository.clone(),
+        ),
+        points_service: PointsService::new(
+            user_repository.clone(),
+            unit_of_work_factory.clone(),
+            transfer_service.account_locks(),
+        ),
+        adjustment_service: AdjustmentService::new(
+            Arc::new(SqliteAdjustmentRepository::new(pool.clone())),
+            user_repository.clone(),
+            unit_of_work_factory.clone(),
+            transfer_service.account_locks(),
+            clock.clone(),
+        ),
+        reconciliation_service: ReconciliationService::new(
+            user_repository.clone(),
+            transfer_repository,
+            point_ledger_repository.clone(),
+            clock.clone(),
+        ),
+        expiry_service: PointsExpiryService::new(
+            point_ledger_repository.clone(),
+            user_repository.clone(),
+            unit_of_work_factory.clone(),
+            transfer_service.account_locks(),
+            clock.clone(),
+        ),
+        tier_service: TierService::new(
+            user_repository.clone(),
+            point_ledger_repository,
+            transfer_limit_repository,
+            Arc::new(SqliteTierRepository::new(pool.clone())),
+            unit_of_work_factory,
+            clock.clone(),
+        ),
+        auth_service: AuthService::new(
+            user_repository,
+            Arc::new(SqliteAuthRepository::new(pool.clone())),
+            Arc::new(ConsoleOtpSender),
+            clock,
+            TEST_JWT_SECRET,
+        ),
+        transfer_service,
+    };
+
+    create_routes().with_state(app_state)
+}
+
+// An `Authorization` header value for the user, valid for ten minutes
+pub fn bearer(user_id: u32) -> String {
+    let now = chrono::Utc::now().timestamp();
+    let claims = AccessTokenClaims { sub: user_id.to_string(), iat: now, exp: now + 600 };
+    let token = jsonwebtoken::encode(&jsonwebtoken::Header::default(), &claims, &jsonwebtoken::EncodingKey::from_secret(TEST_JWT_SECRET))
+        .expect("sign test token");
+    format!("Bearer {}", token)
+}
+
+// Sends one request through the router and returns the status code and the JSON body
+pub async fn send(app: &Router, method: &str, uri: &str, authorization: Option<&str>, body: Option<Value>) -> (u16, Value) {
+    let mut request = Request::builder().method(method).uri(uri).header("content-type", "application/json");
+    if let Some(authorization) = authorization {
+        request = request.header("authorization", authorization);
+    }
+    let request = request
+        .body(Body::from(body.map(|body| body.to_string()).unwrap_or_default()))
+        .unwrap();
+
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status().as_u16();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
\ No newline at end of file
@@ -0,0 +1,165 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+};
+use crate::domain::{TierListResponse, UserTierStatus, TierChange, SetTierRequest, TierHistoryResponse, TierEvaluationReport};
+use crate::presentation::{AppState, AuthenticatedUser, StaffUser, ErrorResponse, viewable_user_id};
+
+fn tier_error(e: String) -> (StatusCode, Json<ErrorResponse>) {
+    let (status, error) = if e.contains("User not found") {
+        (StatusCode::NOT_FOUND, "USER_NOT_FOUND")
+    } else if e.contains("already") || e.contains("no longer") {
+        (StatusCode::CONFLICT, "TIER_UNCHANGED")
+    } else if e.contains("Database error") || e.contains("Failed to") {
+        (StatusCode::INTERNAL_SERVER_ERROR, "INTERNAL_ERROR")
+    } else {
+        (StatusCode::BAD_REQUEST, "VALIDATION_ERROR")
+    };
+
+    (
+        status,
+        Json(ErrorResponse {
+            error: error.to_string(),
+            message: e,
+        }),
+    )
+}
+
+/// List every membership tier with what it takes to reach it and its benefits
+#[utoipa::path(
+    get,
+    path = "/tiers",
+    responses(
+        (status = 200, description = "Tiers, lowest first", body = TierListResponse)
+    ),
+    tag = "Tiers"
+)]
+pub async fn list_tiers(
+    State(state): State<AppState>,
+) -> Result<Json<TierListResponse>, (StatusCode, Json<ErrorResponse>)> {
+    match state.tier_service.list_tiers().await {
+        Ok(response) => Ok(Json(response)),
+        Err(e) => Err(tier_error(e)),
+    }
+}
+
+/// Get a user's tier, their progress towards the next one and their benefits
+#[utoipa::path(
+    get,
+    path = "/users/{id}/tier",
+    params(
+        ("id" = u32, Path, description = "User ID")
+    ),
+    responses(
+        (status = 200, description = "Tier status", body = UserTierStatus),
+        (status = 401, description = "Missing, invalid or expired access token", body = ErrorResponse),
+        (status = 403, description = "The user is not the signed-in user, and the caller is not staff", body = ErrorResponse),
+        (status = 404, description = "User not found", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Tiers"
+)]
+pub async fn get_user_tier(
+    State(state): State<AppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Path(id): Path<u32>,
+) -> Result<Json<UserTierStatus>, (StatusCode, Json<ErrorResponse>)> {
+    let id = viewable_user_id(&user, id, "User ID")?;
+
+    match state.tier_service.get_user_tier(id).await {
+        Ok(status) => Ok(Json(status)),
+        Err(e) => Err(tier_error(e)),
+    }
+}
+
+/// Move a user to another tier by hand; the change is recorded in their tier history
+#[utoipa::path(
+    put,
+    path = "/users/{id}/tier",
+    params(
+        ("id" = u32, Path, description = "User ID")
+    ),
+    request_body = SetTierRequest,
+    responses(
+        (status = 200, description = "Tier changed", body = TierChange),
+        (status = 400, description = "Bad request", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired access token", body = ErrorResponse),
+        (status = 403, description = "Only staff can set a tier", body = ErrorResponse),
+        (status = 404, description = "User not found", body = ErrorResponse),
+        (status = 409, description = "User is already in that tier", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Tiers"
+)]
+pub async fn set_user_tier(
+    State(state): State<AppState>,
+    StaffUser(staff): StaffUser,
+    Path(id): Path<u32>,
+    Json(request): Json<SetTierRequest>,
+) -> Result<Json<TierChange>, (StatusCode, Json<ErrorResponse>)> {
+    match state.tier_service.set_tier(id, &staff.email, request).await {
+        Ok(change) => Ok(Json(change)),
+        Err(e) => Err(tier_error(e)),
+    }
+}
+
+/// List a user's tier changes, newest first
+#[utoipa::path(
+    get,
+    path = "/users/{id}/tier/history",
+    params(
+        ("id" = u32, Path, description = "User ID")
+    ),
+    responses(
+        (status = 200, description = "Tier history", body = TierHistoryResponse),
+        (status = 401, description = "Missing, invalid or expired access token", body = ErrorResponse),
+        (status = 403, description = "The user is not the signed-in user, and the caller is not staff", body = ErrorResponse),
+        (status = 404, description = "User not found", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Tiers"
+)]
+pub async fn get_tier_history(
+    State(state): State<AppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Path(id): Path<u32>,
+) -> Result<Json<TierHistoryResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let id = viewable_user_id(&user, id, "User ID")?;
+
+    match state.tier_service.get_tier_history(id).await {
+        Ok(response) => Ok(Json(response)),
+        Err(e) => Err(tier_error(e)),
+    }
+}
+
+/// Run the tier evaluation now instead of waiting for the nightly job
+#[utoipa::path(
+    post,
+    path = "/admin/tiers/evaluate",
+    responses(
+        (status = 200, description = "Promotions and demotions made", body = TierEvaluationReport),
+        (status = 401, description = "Missing, invalid or expired access token", body = ErrorResponse),
+        (status = 403, description = "Only staff can run the tier evaluation", body = ErrorResponse),
+        (status = 500, description = "Evaluation could not run", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Admin"
+)]
+pub async fn evaluate_tiers(
+    State(state): State<AppState>,
+    StaffUser(_staff): StaffUser,
+) -> Result<Json<TierEvaluationReport>, (StatusCode, Json<ErrorResponse>)> {
+    match state.tier_service.evaluate_tiers().await {
+        Ok(report) => Ok(Json(report)),
+        Err(e) => Err(tier_error(e)),
+    }
+}
\ No newline at end of file
@@ -1,62 +1,286 @@
 use axum::{
     extract::{Path, Query, State},
-    http::StatusCode,
-    response::Json,
+    http::{HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Json, Response},
 };
-use serde::Deserialize;
-use crate::domain::{CreateTransferRequest, TransferCreateResponse, TransferGetResponse, TransferListResponse};
-use crate::presentation::{AppState, ErrorResponse};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use crate::domain::{UserRole, TransferStatus, CreateTransferRequest, TransferCreateResponse, CreateSplitTransferRequest, TransferSplitResponse, TransferGetResponse, TransferListResponse, ReverseTransferRequest, TransferReverseResponse, CancelTransferRequest, TransferCancelResponse, AcceptTransferRequest, DeclineTransferRequest, TransferAcceptanceResponse, TransferLimitViolation};
+use crate::presentation::{AppState, AuthenticatedUser, StaffUser, ErrorResponse, acting_user_id, viewable_user_id, ensure_viewable};
 
 #[derive(Deserialize)]
 pub struct ListTransfersQuery {
-    #[serde(rename = "userId")]
+    #[serde(rename = "userId", default)]
     pub user_id: u32,
     pub page: Option<u32>,
     #[serde(rename = "pageSize")]
     pub page_size: Option<u32>,
 }
 
+#[derive(Serialize, ToSchema)]
+pub struct LimitExceededResponse {
+    pub error: String,
+    pub message: String,
+    #[serde(flatten)]
+    pub violation: TransferLimitViolation,
+}
+
+fn acceptance_error(e: String) -> (StatusCode, Json<ErrorResponse>) {
+    let (status, error) = if e.contains("Transfer not found") {
+        (StatusCode::NOT_FOUND, "TRANSFER_NOT_FOUND")
+    } else if e.contains("Only the recipient") {
+        (StatusCode::FORBIDDEN, "NOT_TRANSFER_RECIPIENT")
+    } else if e.contains("awaiting acceptance") || e.contains("acceptance window") || e.contains("Illegal transfer status transition") {
+        (StatusCode::CONFLICT, "INVALID_TRANSFER_STATE")
+    } else {
+        (StatusCode::BAD_REQUEST, "VALIDATION_ERROR")
+    };
+
+    (
+        status,
+        Json(ErrorResponse {
+            error: error.to_string(),
+            message: e,
+        }),
+    )
+}
+
+// POST /transfers and POST /transfers/split answer the same way; LIMIT_EXCEEDED names the limit and what is left
+fn transfer_error(e: String) -> Response {
+    let (status, error) = if e.contains("different request") {
+        (StatusCode::CONFLICT, "IDEMPOTENCY_KEY_REUSED")
+    } else if e.contains("Insufficient points") {
+        (StatusCode::CONFLICT, "INSUFFICIENT_POINTS")
+    } else if let Ok(violation) = e.parse::<TransferLimitViolation>() {
+        return (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(LimitExceededResponse {
+                error: "LIMIT_EXCEEDED".to_string(),
+                message: e,
+                violation,
+            }),
+        ).into_response();
+    } else if e.contains("Cannot transfer to the same user") {
+        (StatusCode::UNPROCESSABLE_ENTITY, "INVALID_TRANSFER")
+    } else {
+        (StatusCode::BAD_REQUEST, "VALIDATION_ERROR")
+    };
+
+    (
+        status,
+        Json(ErrorResponse {
+            error: error.to_string(),
+            message: e,
+        }),
+    ).into_response()
+}
+
 /// Create a new transfer
 #[utoipa::path(
     post,
     path = "/transfers",
+    params(
+        ("Idempotency-Key" = Option<String>, Header, description = "Client key (8-128 chars); a retry with the same key and body returns the original transfer")
+    ),
     request_body = CreateTransferRequest,
     responses(
-        (status = 201, description = "Transfer created successfully", body = TransferCreateResponse),
+        (status = 201, description = "Transfer created successfully", body = TransferCreateResponse,
+            headers(("Idempotency-Key" = String, description = "idemKey of the transfer, used as /transfers/{id}"))),
+        (status = 202, description = "Transfer accepted as pending (async processing mode, or waiting for a new recipient to accept it); poll GET /transfers/{id}", body = TransferCreateResponse,
+            headers(("Idempotency-Key" = String, description = "idemKey of the transfer, used as /transfers/{id}"))),
         (status = 400, description = "Bad request", body = ErrorResponse),
-        (status = 409, description = "Conflict (insufficient points)", body = ErrorResponse),
-        (status = 422, description = "Unprocessable entity", body = ErrorResponse)
+        (status = 401, description = "Missing, invalid or expired access token", body = ErrorResponse),
+        (status = 403, description = "fromUserId names someone other than the signed-in user", body = ErrorResponse),
+        (status = 409, description = "Conflict (insufficient points, or Idempotency-Key reused with a different body)", body = ErrorResponse),
+        (status = 422, description = "Unprocessable entity: INVALID_TRANSFER for the same user, or LIMIT_EXCEEDED with the limit hit and the remaining allowance", body = LimitExceededResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
     ),
     tag = "Transfers"
 )]
 pub async fn create_transfer(
     State(state): State<AppState>,
-    Json(request): Json<CreateTransferRequest>,
-) -> Result<(StatusCode, Json<TransferCreateResponse>), (StatusCode, Json<ErrorResponse>)> {
-    match state.transfer_service.create_transfer(request).await {
-        Ok(response) => Ok((StatusCode::CREATED, Json(response))),
+    AuthenticatedUser(user): AuthenticatedUser,
+    headers: HeaderMap,
+    Json(mut request): Json<CreateTransferRequest>,
+) -> Result<(StatusCode, HeaderMap, Json<TransferCreateResponse>), Response> {
+    let idempotency_key = match headers.get("Idempotency-Key").map(|value| value.to_str()) {
+        Some(Ok(key)) => Some(key.to_string()),
+        Some(Err(_)) => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse {
+                    error: "VALIDATION_ERROR".to_string(),
+                    message: "Idempotency key must be visible ASCII".to_string(),
+                }),
+            ).into_response());
+        }
+        None => None,
+    };
+
+    request.from_user_id = acting_user_id(&user, request.from_user_id, "fromUserId").map_err(IntoResponse::into_response)?;
+
+    match state.transfer_service.create_transfer(request, idempotency_key).await {
+        Ok(response) => {
+            let mut response_headers = HeaderMap::new();
+            if let Ok(value) = HeaderValue::from_str(&response.transfer.idem_key) {
+                response_headers.insert("Idempotency-Key", value);
+            }
+            let status = if response.transfer.status == TransferStatus::Pending {
+                StatusCode::ACCEPTED
+            } else {
+                StatusCode::CREATED
+            };
+            Ok((status, response_headers, Json(response)))
+        }
+        Err(e) => Err(transfer_error(e)),
+    }
+}
+
+/// Send points to several recipients with one debit from the sender
+#[utoipa::path(
+    post,
+    path = "/transfers/split",
+    params(
+        ("Idempotency-Key" = Option<String>, Header, description = "Client key (8-128 chars); a retry with the same key and body returns the original split")
+    ),
+    request_body = CreateSplitTransferRequest,
+    responses(
+        (status = 201, description = "Split transfer completed; one child transfer per recipient", body = TransferSplitResponse,
+            headers(("Idempotency-Key" = String, description = "idemKey of the parent transfer, used as /transfers/{id}"))),
+        (status = 400, description = "Bad request", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired access token", body = ErrorResponse),
+        (status = 403, description = "fromUserId names someone other than the signed-in user", body = ErrorResponse),
+        (status = 409, description = "Conflict (insufficient points for the total, or Idempotency-Key reused with a different body)", body = ErrorResponse),
+        (status = 422, description = "Unprocessable entity: INVALID_TRANSFER for the same user, or LIMIT_EXCEEDED for the total with the limit hit and the remaining allowance", body = LimitExceededResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Transfers"
+)]
+pub async fn create_split_transfer(
+    State(state): State<AppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    headers: HeaderMap,
+    Json(mut request): Json<CreateSplitTransferRequest>,
+) -> Result<(StatusCode, HeaderMap, Json<TransferSplitResponse>), Response> {
+    let idempotency_key = match headers.get("Idempotency-Key").map(|value| value.to_str()) {
+        Some(Ok(key)) => Some(key.to_string()),
+        Some(Err(_)) => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse {
+                    error: "VALIDATION_ERROR".to_string(),
+                    message: "Idempotency key must be visible ASCII".to_string(),
+                }),
+            ).into_response());
+        }
+        None => None,
+    };
+
+    request.from_user_id = acting_user_id(&user, request.from_user_id, "fromUserId").map_err(IntoResponse::into_response)?;
+
+    match state.transfer_service.create_split_transfer(request, idempotency_key).await {
+        Ok(response) => {
+            let mut response_headers = HeaderMap::new();
+            if let Ok(value) = HeaderValue::from_str(&response.transfer.idem_key) {
+                response_headers.insert("Idempotency-Key", value);
+            }
+            Ok((StatusCode::CREATED, response_headers, Json(response)))
+        }
+        Err(e) => Err(transfer_error(e)),
+    }
+}
+
+/// Get transfer by idempotency key
+#[utoipa::path(
+    get,
+    path = "/transfers/{id}",
+    params(
+        ("id" = String, Path, description = "Transfer idempotency key")
+    ),
+    responses(
+        (status = 200, description = "Transfer found", body = TransferGetResponse),
+        (status = 401, description = "Missing, invalid or expired access token", body = ErrorResponse),
+        (status = 403, description = "The signed-in user is neither the sender nor the recipient, and not staff", body = ErrorResponse),
+        (status = 404, description = "Transfer not found", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Transfers"
+)]
+pub async fn get_transfer(
+    State(state): State<AppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Path(id): Path<String>,
+) -> Result<Json<TransferGetResponse>, (StatusCode, Json<ErrorResponse>)> {
+    match state.transfer_service.get_transfer(&id).await {
+        Ok(response) => {
+            ensure_viewable(&user, &[response.transfer.from_user_id, response.transfer.to_user_id], "Transfer")?;
+            Ok(Json(response))
+        }
         Err(e) => {
             if e.contains("not found") {
                 Err((
-                    StatusCode::BAD_REQUEST,
+                    StatusCode::NOT_FOUND,
                     Json(ErrorResponse {
-                        error: "VALIDATION_ERROR".to_string(),
+                        error: "TRANSFER_NOT_FOUND".to_string(),
                         message: e,
                     }),
                 ))
-            } else if e.contains("Insufficient points") {
+            } else {
                 Err((
-                    StatusCode::CONFLICT,
+                    StatusCode::BAD_REQUEST,
                     Json(ErrorResponse {
-                        error: "INSUFFICIENT_POINTS".to_string(),
+                        error: "VALIDATION_ERROR".to_string(),
                         message: e,
                     }),
                 ))
-            } else if e.contains("Cannot transfer to the same user") {
+            }
+        }
+    }
+}
+
+/// List transfers for a user
+#[utoipa::path(
+    get,
+    path = "/transfers",
+    params(
+        ("userId" = Option<u32>, Query, description = "User ID to filter transfers (default: the signed-in user)"),
+        ("page" = Option<u32>, Query, description = "Page number (default: 1)"),
+        ("pageSize" = Option<u32>, Query, description = "Page size (default: 20, max: 200)")
+    ),
+    responses(
+        (status = 200, description = "Transfers found", body = TransferListResponse),
+        (status = 400, description = "Bad request", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired access token", body = ErrorResponse),
+        (status = 403, description = "userId names someone other than the signed-in user, and the caller is not staff", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Transfers"
+)]
+pub async fn list_transfers(
+    State(state): State<AppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Query(params): Query<ListTransfersQuery>,
+) -> Result<Json<TransferListResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let user_id = viewable_user_id(&user, params.user_id, "userId")?;
+    let page = params.page.unwrap_or(1);
+    let page_size = params.page_size.unwrap_or(20);
+
+    match state.transfer_service.list_transfers(user_id, page, page_size).await {
+        Ok(response) => Ok(Json(response)),
+        Err(e) => {
+            if e.contains("not found") {
                 Err((
-                    StatusCode::UNPROCESSABLE_ENTITY,
+                    StatusCode::BAD_REQUEST,
                     Json(ErrorResponse {
-                        error: "INVALID_TRANSFER".to_string(),
+                        error: "USER_NOT_FOUND".to_string(),
                         message: e,
                     }),
                 ))
@@ -73,24 +297,44 @@ pub async fn create_transfer(
     }
 }
 
-/// Get transfer by idempotency key
+/// Reverse a completed transfer
 #[utoipa::path(
-    get,
-    path = "/transfers/{id}",
+    post,
+    path = "/transfers/{id}/reverse",
     params(
         ("id" = String, Path, description = "Transfer idempotency key")
     ),
+    request_body = ReverseTransferRequest,
     responses(
-        (status = 200, description = "Transfer found", body = TransferGetResponse),
-        (status = 404, description = "Transfer not found", body = ErrorResponse)
+        (status = 200, description = "Transfer reversed", body = TransferReverseResponse),
+        (status = 400, description = "Bad request", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired access token", body = ErrorResponse),
+        (status = 403, description = "Only staff can reverse a transfer, and only admins can force one", body = ErrorResponse),
+        (status = 404, description = "Transfer not found", body = ErrorResponse),
+        (status = 409, description = "Conflict (not completed, or recipient already spent the points)", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
     ),
     tag = "Transfers"
 )]
-pub async fn get_transfer(
+pub async fn reverse_transfer(
     State(state): State<AppState>,
+    StaffUser(staff): StaffUser,
     Path(id): Path<String>,
-) -> Result<Json<TransferGetResponse>, (StatusCode, Json<ErrorResponse>)> {
-    match state.transfer_service.get_transfer(&id).await {
+    Json(request): Json<ReverseTransferRequest>,
+) -> Result<Json<TransferReverseResponse>, (StatusCode, Json<ErrorResponse>)> {
+    if request.force == Some(true) && staff.role != UserRole::Admin {
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(ErrorResponse {
+                error: "FORBIDDEN".to_string(),
+                message: "Only admins can force a reversal".to_string(),
+            }),
+        ));
+    }
+
+    match state.transfer_service.reverse_transfer(&id, &staff.email, request).await {
         Ok(response) => Ok(Json(response)),
         Err(e) => {
             if e.contains("not found") {
@@ -101,6 +345,30 @@ pub async fn get_transfer(
                         message: e,
                     }),
                 ))
+            } else if e.contains("Only completed transfers") || e.contains("Split transfers are reversed") || e.contains("Illegal transfer status transition") {
+                Err((
+                    StatusCode::CONFLICT,
+                    Json(ErrorResponse {
+                        error: "INVALID_TRANSFER_STATE".to_string(),
+                        message: e,
+                    }),
+                ))
+            } else if e.contains("already spent") {
+                Err((
+                    StatusCode::CONFLICT,
+                    Json(ErrorResponse {
+                        error: "POINTS_ALREADY_SPENT".to_string(),
+                        message: e,
+                    }),
+                ))
+            } else if e.contains("Insufficient points") {
+                Err((
+                    StatusCode::CONFLICT,
+                    Json(ErrorResponse {
+                        error: "INSUFFICIENT_POINTS".to_string(),
+                        message: e,
+                    }),
+                ))
             } else {
                 Err((
                     StatusCode::BAD_REQUEST,
@@ -114,36 +382,59 @@ pub async fn get_transfer(
     }
 }
 
-/// List transfers for a user
+/// Cancel a pending or processing transfer
 #[utoipa::path(
-    get,
-    path = "/transfers",
+    post,
+    path = "/transfers/{id}/cancel",
     params(
-        ("userId" = u32, Query, description = "User ID to filter transfers"),
-        ("page" = Option<u32>, Query, description = "Page number (default: 1)"),
-        ("pageSize" = Option<u32>, Query, description = "Page size (default: 20, max: 200)")
+        ("id" = String, Path, description = "Transfer idempotency key")
     ),
+    request_body = CancelTransferRequest,
     responses(
-        (status = 200, description = "Transfers found", body = TransferListResponse),
-        (status = 400, description = "Bad request", body = ErrorResponse)
+        (status = 200, description = "Transfer cancelled", body = TransferCancelResponse),
+        (status = 400, description = "Bad request", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired access token", body = ErrorResponse),
+        (status = 403, description = "Only the sender can cancel", body = ErrorResponse),
+        (status = 404, description = "Transfer not found", body = ErrorResponse),
+        (status = 409, description = "Transfer is no longer pending or processing", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
     ),
     tag = "Transfers"
 )]
-pub async fn list_transfers(
+pub async fn cancel_transfer(
     State(state): State<AppState>,
-    Query(params): Query<ListTransfersQuery>,
-) -> Result<Json<TransferListResponse>, (StatusCode, Json<ErrorResponse>)> {
-    let page = params.page.unwrap_or(1);
-    let page_size = params.page_size.unwrap_or(20);
+    AuthenticatedUser(user): AuthenticatedUser,
+    Path(id): Path<String>,
+    Json(mut request): Json<CancelTransferRequest>,
+) -> Result<Json<TransferCancelResponse>, (StatusCode, Json<ErrorResponse>)> {
+    request.cancelled_by = acting_user_id(&user, request.cancelled_by, "cancelledBy")?;
 
-    match state.transfer_service.list_transfers(params.user_id, page, page_size).await {
+    match state.transfer_service.cancel_transfer(&id, request).await {
         Ok(response) => Ok(Json(response)),
         Err(e) => {
             if e.contains("not found") {
                 Err((
-                    StatusCode::BAD_REQUEST,
+                    StatusCode::NOT_FOUND,
                     Json(ErrorResponse {
-                        error: "USER_NOT_FOUND".to_string(),
+                        error: "TRANSFER_NOT_FOUND".to_string(),
+                        message: e,
+                    }),
+                ))
+            } else if e.contains("Only the sender") {
+                Err((
+                    StatusCode::FORBIDDEN,
+                    Json(ErrorResponse {
+                        error: "NOT_TRANSFER_SENDER".to_string(),
+                        message: e,
+                    }),
+                ))
+            } else if e.contains("can be cancelled") || e.contains("Illegal transfer status transition") {
+                Err((
+                    StatusCode::CONFLICT,
+                    Json(ErrorResponse {
+                        error: "INVALID_TRANSFER_STATE".to_string(),
                         message: e,
                     }),
                 ))
@@ -158,4 +449,182 @@ pub async fn list_transfers(
             }
         }
     }
+}
+
+/// Accept a transfer waiting for the recipient
+#[utoipa::path(
+    post,
+    path = "/transfers/{id}/accept",
+    params(
+        ("id" = String, Path, description = "Transfer idempotency key")
+    ),
+    request_body = AcceptTransferRequest,
+    responses(
+        (status = 200, description = "Transfer accepted and executed (or failed, e.g. insufficient points)", body = TransferAcceptanceResponse),
+        (status = 400, description = "Bad request", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired access token", body = ErrorResponse),
+        (status = 403, description = "Only the recipient can accept", body = ErrorResponse),
+        (status = 404, description = "Transfer not found", body = ErrorResponse),
+        (status = 409, description = "Transfer is not awaiting acceptance, or its acceptance window has closed", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Transfers"
+)]
+pub async fn accept_transfer(
+    State(state): State<AppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Path(id): Path<String>,
+    Json(mut request): Json<AcceptTransferRequest>,
+) -> Result<Json<TransferAcceptanceResponse>, (StatusCode, Json<ErrorResponse>)> {
+    request.accepted_by = acting_user_id(&user, request.accepted_by, "acceptedBy")?;
+
+    match state.transfer_service.accept_transfer(&id, request).await {
+        Ok(response) => Ok(Json(response)),
+        Err(e) => Err(acceptance_error(e)),
+    }
+}
+
+/// Decline a transfer waiting for the recipient
+#[utoipa::path(
+    post,
+    path = "/transfers/{id}/decline",
+    params(
+        ("id" = String, Path, description = "Transfer idempotency key")
+    ),
+    request_body = DeclineTransferRequest,
+    responses(
+        (status = 200, description = "Transfer declined and cancelled", body = TransferAcceptanceResponse),
+        (status = 400, description = "Bad request", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired access token", body = ErrorResponse),
+        (status = 403, description = "Only the recipient can decline", body = ErrorResponse),
+        (status = 404, description = "Transfer not found", body = ErrorResponse),
+        (status = 409, description = "Transfer is not awaiting acceptance, or its acceptance window has closed", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Transfers"
+)]
+pub async fn decline_transfer(
+    State(state): State<AppState>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Path(id): Path<String>,
+    Json(mut request): Json<DeclineTransferRequest>,
+) -> Result<Json<TransferAcceptanceResponse>, (StatusCode, Json<ErrorResponse>)> {
+    request.declined_by = acting_user_id(&user, request.declined_by, "declinedBy")?;
+
+    match state.transfer_service.decline_transfer(&id, request).await {
+        Ok(response) => Ok(Json(response)),
+        Err(e) => Err(acceptance_error(e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use serde_json::json;
+    use crate::domain::{SystemClock, UserRepository, UserRole, TransferLimitRepository, UpdateTransferLimitsRequest};
+    use crate::infrastructure::{SqliteUserRepository, SqliteTransferLimitRepository};
+    use crate::infrastructure::test_support::test_pool;
+    use crate::presentation::test_support::{test_app, bearer, send};
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 8)]
+    async fn hundreds_of_parallel_transfers_never_overdraw_an_account() {
+        let pool = test_pool().await;
+        let app = test_app(&pool, Arc::new(SystemClock));
+
+        // A ring of transfers between the three seed users, far more than any of them can afford
+        let mut requests = Vec::new();
+        for i in 0..300u32 {
+            let app = app.clone();
+            let (from, to) = match i % 3 {
+                0 => (1, 2),
+                1 => (2, 3),
+                _ => (3, 1),
+            };
+            requests.push(tokio::spawn(async move {
+                let body = serde_json::json!({ "toUserId": to, "amount": 37 });
+                send(&app, "POST", "/transfers", Some(&bearer(from)), Some(body)).await
+            }));
+        }
+        for request in requests {
+            let (status, body) = request.await.unwrap();
+            assert!(status == 201 || status == 409, "unexpected {}: {}", status, body);
+        }
+
+        for user_id in 1..=3i64 {
+            let rows: Vec<(i64, i64)> = sqlx::query_as("SELECT change, balance_after FROM point_ledger WHERE user_id = ? ORDER BY id")
+                .bind(user_id)
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+            let mut balance = 0;
+            for (change, balance_after) in rows {
+                balance += change;
+                assert_eq!(balance, balance_after, "balance_after chain broken for user {}", user_id);
+                assert!(balance_after >= 0, "user {} went negative", user_id);
+            }
+        }
+        let total: i64 = sqlx::query_scalar("SELECT SUM(change) FROM point_ledger").fetch_one(&pool).await.unwrap();
+        assert_eq!(total, 1500 + 750 + 200);
+    }
+    #[tokio::test]
+    async fn reversals_are_recorded_against_the_staff_member_and_only_admins_force_them() {
+        let pool = test_pool().await;
+        let app = test_app(&pool, Arc::new(SystemClock));
+        let users = SqliteUserRepository::new(pool.clone());
+
+        let (status, body) = send(&app, "POST", "/transfers", Some(&bearer(1)), Some(json!({ "toUserId": 2, "amount": 100 }))).await;
+        assert_eq!(status, 201);
+        let reverse = format!("/transfers/{}/reverse", body["transfer"]["idemKey"].as_str().unwrap());
+        // Jane spends some of it, so only a forced reversal can take it back
+        let (status, _) = send(&app, "POST", "/users/2/points/redeem", Some(&bearer(2)), Some(json!({ "amount": 100, "reason": "gift card" }))).await;
+        assert_eq!(status, 201);
+
+        let forced = json!({ "reason": "fraud", "force": true, "reversedBy": "someone else" });
+        let (status, _) = send(&app, "POST", &reverse, Some(&bearer(1)), Some(forced.clone())).await;
+        assert_eq!(status, 403);
+        users.set_role(3, UserRole::Staff).await.unwrap();
+        let (status, _) = send(&app, "POST", &reverse, Some(&bearer(3)), Some(json!({ "reason": "fraud" }))).await;
+        assert_eq!(status, 409);
+        let (status, _) = send(&app, "POST", &reverse, Some(&bearer(3)), Some(forced.clone())).await;
+        assert_eq!(status, 403);
+
+        users.set_role(3, UserRole::Admin).await.unwrap();
+        let (status, body) = send(&app, "POST", &reverse, Some(&bearer(3)), Some(forced)).await;
+        assert_eq!(status, 200, "{}", body);
+        assert_eq!(body["reversal"]["reversedBy"], "bob.johnson@example.com");
+        assert_eq!(body["reversal"]["forced"], true);
+    }
+
+    #[tokio::test]
+    async fn limit_exceeded_names_the_limit_and_what_is_left() {
+        let pool = test_pool().await;
+        let app = test_app(&pool, Arc::new(SystemClock));
+        SqliteTransferLimitRepository::new(pool.clone()).set_limits("Bronze", UpdateTransferLimitsRequest {
+            min_amount: None,
+            max_amount: Some(100),
+            daily_cap: Some(150),
+            max_transfers_per_hour: None,
+        }).await.unwrap();
+
+        // Over the per-transfer maximum: the whole day's allowance is still there
+        let (status, body) = send(&app, "POST", "/transfers", Some(&bearer(3)), Some(json!({ "toUserId": 1, "amount": 120 }))).await;
+        assert_eq!(status, 422);
+        assert_eq!(body["error"], "LIMIT_EXCEEDED");
+        assert_eq!(body["limit"], "maxAmount");
+        assert_eq!(body["limitValue"], 100);
+        assert_eq!(body["remaining"], 150);
+
+        let (status, _) = send(&app, "POST", "/transfers", Some(&bearer(3)), Some(json!({ "toUserId": 1, "amount": 100 }))).await;
+        assert_eq!(status, 201);
+        let (status, body) = send(&app, "POST", "/transfers", Some(&bearer(3)), Some(json!({ "toUserId": 1, "amount": 60 }))).await;
+        assert_eq!(status, 422);
+        assert_eq!(body["limit"], "dailyCap");
+        assert_eq!(body["limitValue"], 150);
+        assert_eq!(body["remaining"], 50);
+        assert_eq!(body["membershipLevel"], "Bronze");
+    }
 }
\ No newline at end of file
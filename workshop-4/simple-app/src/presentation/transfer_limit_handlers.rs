@@ -0,0 +1,97 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+};
+use crate::domain::{TransferLimits, UpdateTransferLimitsRequest, TransferLimitsListResponse};
+use crate::presentation::{AppState, StaffUser, ErrorResponse};
+
+fn transfer_limit_error(e: String) -> (StatusCode, Json<ErrorResponse>) {
+    let (status, error) = if e.contains("No transfer limits configured") {
+        (StatusCode::NOT_FOUND, "TRANSFER_LIMITS_NOT_FOUND")
+    } else if e.contains("Database error") || e.contains("Failed to") {
+        (StatusCode::INTERNAL_SERVER_ERROR, "INTERNAL_ERROR")
+    } else {
+        (StatusCode::BAD_REQUEST, "VALIDATION_ERROR")
+    };
+
+    (
+        status,
+        Json(ErrorResponse {
+            error: error.to_string(),
+            message: e,
+        }),
+    )
+}
+
+/// List the transfer limits of every membership level
+#[utoipa::path(
+    get,
+    path = "/transfer-limits",
+    responses(
+        (status = 200, description = "Configured limits; levels not listed are not limited", body = TransferLimitsListResponse)
+    ),
+    tag = "Transfer Limits"
+)]
+pub async fn list_transfer_limits(
+    State(state): State<AppState>,
+) -> Result<Json<TransferLimitsListResponse>, (StatusCode, Json<ErrorResponse>)> {
+    match state.transfer_service.list_transfer_limits().await {
+        Ok(data) => Ok(Json(TransferLimitsListResponse { data })),
+        Err(e) => Err(transfer_limit_error(e)),
+    }
+}
+
+/// Get the transfer limits of a membership level
+#[utoipa::path(
+    get,
+    path = "/transfer-limits/{level}",
+    params(
+        ("level" = String, Path, description = "Membership level (case-insensitive)")
+    ),
+    responses(
+        (status = 200, description = "Limits found", body = TransferLimits),
+        (status = 404, description = "No limits configured for the level", body = ErrorResponse)
+    ),
+    tag = "Transfer Limits"
+)]
+pub async fn get_transfer_limits(
+    State(state): State<AppState>,
+    Path(level): Path<String>,
+) -> Result<Json<TransferLimits>, (StatusCode, Json<ErrorResponse>)> {
+    match state.transfer_service.get_transfer_limits(&level).await {
+        Ok(limits) => Ok(Json(limits)),
+        Err(e) => Err(transfer_limit_error(e)),
+    }
+}
+
+/// Set the transfer limits of a membership level; an empty limit is not enforced
+#[utoipa::path(
+    put,
+    path = "/transfer-limits/{level}",
+    params(
+        ("level" = String, Path, description = "Membership tier: Bronze, Silver, Gold or Platinum (case-insensitive)")
+    ),
+    request_body = UpdateTransferLimitsRequest,
+    responses(
+        (status = 200, description = "Limits saved; applies to the next transfer", body = TransferLimits),
+        (status = 400, description = "Bad request", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired access token", body = ErrorResponse),
+        (status = 403, description = "Only staff can change transfer limits", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Transfer Limits"
+)]
+pub async fn set_transfer_limits(
+    State(state): State<AppState>,
+    StaffUser(_staff): StaffUser,
+    Path(level): Path<String>,
+    Json(request): Json<UpdateTransferLimitsRequest>,
+) -> Result<Json<TransferLimits>, (StatusCode, Json<ErrorResponse>)> {
+    match state.transfer_service.set_transfer_limits(&level, request).await {
+        Ok(limits) => Ok(Json(limits)),
+        Err(e) => Err(transfer_limit_error(e)),
+    }
+}
\ No newline at end of file